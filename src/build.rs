@@ -0,0 +1,140 @@
+// --- Dựng cây FdonValue bằng code (không qua parser) ---
+
+use bumpalo::{Bump, collections::Vec as BumpVec};
+
+use crate::{to_fdon_string, FdonArray, FdonObject, FdonValue};
+
+/// Builds an `Object` from `(key, value)` pairs. The map lives in `arena`;
+/// keys are borrowed, not copied. Entries keep the iterator's order; a repeated key
+/// keeps the last value at its first position, like the parser.
+pub fn object_from_iter<'a, 'bump, I>(arena: &'bump Bump, iter: I) -> FdonValue<'a, 'bump>
+where
+    I: IntoIterator<Item = (&'bump str, FdonValue<'a, 'bump>)>,
+{
+    let iter = iter.into_iter();
+    let mut obj = FdonObject::with_capacity_in(iter.size_hint().0, arena);
+    for (key, value) in iter {
+        obj.insert(key, value);
+    }
+    FdonValue::Object(obj)
+}
+
+/// Builds an `Array` in `arena` from `iter`, keeping its order.
+pub fn array_from_iter<'a, 'bump, I>(arena: &'bump Bump, iter: I) -> FdonValue<'a, 'bump>
+where
+    I: IntoIterator<Item = FdonValue<'a, 'bump>>,
+{
+    FdonValue::Array(BumpVec::from_iter_in(iter, arena))
+}
+
+/// Builds a tree with `build` in `arena`, serializes it to minified FDON (see
+/// `to_fdon_string`) and resets `arena`, so output-heavy code can reuse one arena
+/// per response instead of keeping every intermediate tree alive.
+///
+/// `build` gets the arena to allocate builders in; its keys and strings must be
+/// arena or `'static` strings. Anything allocated in `arena` earlier is freed as
+/// well (the `&mut` borrow proves nothing still uses it).
+pub fn build_and_serialize<F>(arena: &mut Bump, build: F) -> String
+where
+    F: for<'bump> FnOnce(&'bump Bump) -> FdonValue<'bump, 'bump>,
+{
+    let out = to_fdon_string(&build(arena));
+    arena.reset();
+    out
+}
+
+/// Incremental builder for an `Object` in an arena.
+pub struct FdonObjectBuilder<'a, 'bump> {
+    obj: FdonObject<'a, 'bump>,
+}
+
+impl<'a, 'bump> FdonObjectBuilder<'a, 'bump> {
+    #[inline]
+    pub fn new(arena: &'bump Bump) -> Self {
+        Self::with_capacity(arena, 0)
+    }
+
+    /// Pre-sizes the object for `capacity` entries. Its storage is allocated in `arena`
+    /// right away, so a large object costs one arena allocation instead of a series
+    /// of grow-and-copy steps that each leave the old buffer behind in the arena.
+    #[inline]
+    pub fn with_capacity(arena: &'bump Bump, capacity: usize) -> Self {
+        FdonObjectBuilder {
+            obj: FdonObject::with_capacity_in(capacity, arena),
+        }
+    }
+
+    /// Reserves room for `additional` more entries.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.obj.reserve(additional);
+    }
+
+    /// Adds an entry; a repeated key keeps the last value.
+    #[inline]
+    pub fn insert(&mut self, key: &'bump str, value: FdonValue<'a, 'bump>) -> &mut Self {
+        self.obj.insert(key, value);
+        self
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.obj.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.obj.is_empty()
+    }
+
+    #[inline]
+    pub fn build(self) -> FdonValue<'a, 'bump> {
+        FdonValue::Object(self.obj)
+    }
+}
+
+/// Incremental builder for an `Array` in an arena.
+pub struct FdonArrayBuilder<'a, 'bump> {
+    arr: FdonArray<'a, 'bump>,
+}
+
+impl<'a, 'bump> FdonArrayBuilder<'a, 'bump> {
+    #[inline]
+    pub fn new(arena: &'bump Bump) -> Self {
+        FdonArrayBuilder { arr: BumpVec::new_in(arena) }
+    }
+
+    /// Pre-sizes the array for `capacity` elements, allocated in `arena` right away
+    /// (a growing `BumpVec` leaves each outgrown buffer behind in the arena).
+    #[inline]
+    pub fn with_capacity(arena: &'bump Bump, capacity: usize) -> Self {
+        FdonArrayBuilder { arr: BumpVec::with_capacity_in(capacity, arena) }
+    }
+
+    /// Reserves room for `additional` more elements.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.arr.reserve(additional);
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: FdonValue<'a, 'bump>) -> &mut Self {
+        self.arr.push(value);
+        self
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.arr.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.arr.is_empty()
+    }
+
+    #[inline]
+    pub fn build(self) -> FdonValue<'a, 'bump> {
+        FdonValue::Array(self.arr)
+    }
+}
@@ -0,0 +1,322 @@
+// --- serde Deserializer (đọc thẳng từ FdonParser, không dựng cây) ---
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use ahash::RandomState as AHasher;
+use bumpalo::Bump;
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::Deserialize;
+
+use crate::{check_delimiter, directive, Container, FdonError, FdonNumber, FdonParser, FdonStr, FdonValue, ParseOptions};
+
+/// Deserializes a (minified) FDON document straight into `T`, without building a
+/// `FdonValue` tree.
+///
+/// `O{...}` maps to structs and maps, `A[...]` to sequences and tuples, `N`/`T`
+/// numbers to integer and float types, `B` to `bool`, `U` to `None` or `()`, and
+/// `S`, `SE`, `D` and `T"..."` to strings. `&'de str` fields borrow from `input`
+/// without allocating; an `SE` string with escapes can only go into `String` (or
+/// `Cow<'de, str>` with `#[serde(borrow)]`, which borrows whenever it can).
+/// Enums are read from a string (unit variants) or a one-entry object
+/// `O{Variant:...}`; `#[serde(tag = "...")]` enums from an object holding the tag
+/// key, in any position. `arena` holds unescaped `SE` strings while they are visited.
+///
+/// Type mismatches and serde's own errors (e.g. a missing field) are
+/// `FdonError::Deserialize` at the offset of the value being read. Errors serde
+/// raises after buffering a value (the fields of a tagged enum variant) point at
+/// that value, e.g. the variant's `O{`.
+pub fn from_fdon_str<'de, T: Deserialize<'de>>(input: &'de str, arena: &Bump) -> Result<T, FdonError> {
+    from_fdon_str_with_options(input, arena, ParseOptions::default())
+}
+
+/// Same as `from_fdon_str`, but with custom parser options.
+pub fn from_fdon_str_with_options<'de, T: Deserialize<'de>>(
+    input: &'de str,
+    arena: &Bump,
+    options: ParseOptions,
+) -> Result<T, FdonError> {
+    check_delimiter(options.string_delimiter)?;
+    let mut parser = FdonParser::with_options(input, arena, options);
+    if options.allow_version_directive {
+        parser.index = directive::check_directive(parser.data).map_err(|e| parser.decorate(e))?;
+    }
+    parser.skip_whitespace();
+    if matches!(parser.peek(), Some(b'{' | b'[' | b'"')) {
+        return Err(parser.decorate(FdonError::LooksLikeJson { pos: parser.index }));
+    }
+    let start = parser.index;
+    let mut de = Deserializer { parser, key: String::new() };
+    let value = T::deserialize(&mut de).map_err(|e| de.parser.decorate(at(e, start)))?;
+    de.parser.skip_whitespace();
+    if de.parser.index != de.parser.data.len() {
+        return Err(de.parser.decorate(FdonError::ExtraData { pos: de.parser.index }));
+    }
+    Ok(value)
+}
+
+impl de::Error for FdonError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        FdonError::Deserialize { msg: msg.to_string(), pos: None }
+    }
+}
+
+// Gắn vị trí của value đang đọc cho lỗi serde chưa có vị trí
+#[inline]
+fn at(err: FdonError, pos: usize) -> FdonError {
+    match err {
+        FdonError::Deserialize { msg, pos: None } => FdonError::Deserialize { msg, pos: Some(pos) },
+        e => e,
+    }
+}
+
+struct Deserializer<'de, 'bump> {
+    parser: FdonParser<'de, 'bump>,
+    // Key SE"..." có escape (dùng lại cho mọi key)
+    key: String,
+}
+
+impl<'de: 'bump, 'bump> Deserializer<'de, 'bump> {
+    // Scalar (không phải O/A): parse như bình thường rồi gọi visitor tương ứng
+    fn visit_scalar<V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value, FdonError> {
+        match self.parser.parse_value()? {
+            FdonValue::Null => visitor.visit_unit(),
+            FdonValue::Bool(b) => visitor.visit_bool(b),
+            FdonValue::Number(n) | FdonValue::Timestamp(n) => match n {
+                FdonNumber::Integer(i) => visitor.visit_i64(i),
+                FdonNumber::UInt(u) => visitor.visit_u64(u),
+                FdonNumber::Float(f) => visitor.visit_f64(f),
+            },
+            FdonValue::RawString(s) | FdonValue::Date(s) | FdonValue::Time(s) => visitor.visit_borrowed_str(s),
+            FdonValue::EscapedString(FdonStr::Borrowed(s)) => visitor.visit_borrowed_str(s),
+            FdonValue::EscapedString(FdonStr::Owned(s)) => visitor.visit_str(&s),
+            FdonValue::Array(_) | FdonValue::Object(_) => unreachable!("containers are visited in place"),
+        }
+    }
+
+    // O{...} / A[...] bắt đầu tại `start`: mở, để `visit` đọc nội dung rồi đóng.
+    // Lỗi trả về từ đây (kể cả lỗi serde bên trong) còn được gắn vị trí bởi `at`
+    fn visit_container<T>(
+        &mut self,
+        start: usize,
+        open: u8,
+        close: u8,
+        visit: impl FnOnce(&mut Self) -> Result<T, FdonError>,
+    ) -> Result<T, FdonError> {
+        self.parser.enter(start)?;
+        self.parser.advance();
+        self.parser.skip_whitespace();
+        self.parser.consume(open)?;
+        let value = visit(self)?;
+        self.parser.consume(close)?;
+        self.parser.leave();
+        Ok(value)
+    }
+
+    // Key mượn từ input: visit_borrowed_str; key SE"..." có escape (trong `self.key`): visit_str
+    fn visit_key<K: DeserializeSeed<'de>>(&self, key: Option<&'de str>, seed: K) -> Result<K::Value, FdonError> {
+        match key {
+            Some(key) => seed.deserialize(BorrowedStrDeserializer::new(key)),
+            None => seed.deserialize(self.key.as_str().into_deserializer()),
+        }
+    }
+
+    #[inline]
+    fn is_null(&self) -> bool {
+        let rest = &self.parser.data[self.parser.index..];
+        rest.first() == Some(&b'U') || (self.parser.options.allow_json_keywords && rest.starts_with(b"null"))
+    }
+}
+
+impl<'de: 'bump, 'bump> de::Deserializer<'de> for &mut Deserializer<'de, 'bump> {
+    type Error = FdonError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FdonError> {
+        self.parser.skip_whitespace();
+        let start = self.parser.index;
+        let result = match self.parser.peek() {
+            Some(b'O') => self.visit_container(start, b'{', b'}', |de| {
+                visitor.visit_map(Entries { de, seen: HashSet::default() })
+            }),
+            Some(b'A') => self.visit_container(start, b'[', b']', |de| visitor.visit_seq(Items { de })),
+            _ => self.visit_scalar(visitor),
+        };
+        result.map_err(|e| at(e, start))
+    }
+
+    // Chuỗi S/D/T và SE không có escape: visit_borrowed_str, mượn thẳng từ input (zero-copy);
+    // SE có escape: visit_str với bản đã unescape trong Arena, visitor tự copy nếu cần.
+    // Kiểu khác: visitor tự báo lỗi invalid type (xem visit_scalar)
+    #[inline]
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FdonError> {
+        self.deserialize_any(visitor)
+    }
+
+    #[inline]
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FdonError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FdonError> {
+        self.parser.skip_whitespace();
+        if self.is_null() {
+            self.parser.parse_value()?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, FdonError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FdonError> {
+        self.parser.skip_whitespace();
+        let start = self.parser.index;
+        let result = if self.parser.peek() == Some(b'O') {
+            // O{Variant:value}
+            self.visit_container(start, b'{', b'}', |de| {
+                de.parser.skip_whitespace();
+                let value = visitor.visit_enum(Variant { de: &mut *de })?;
+                de.parser.skip_whitespace();
+                Ok(value)
+            })
+        } else if self.parser.peek() == Some(b'A') {
+            // Vẫn đọc hết array để lỗi cú pháp bên trong được báo trước
+            self.parser.skip_value()?;
+            Err(de::Error::custom(EXPECTED_VARIANT))
+        } else {
+            // Unit variant viết dạng chuỗi
+            match self.parser.parse_value()? {
+                FdonValue::RawString(s) | FdonValue::EscapedString(FdonStr::Borrowed(s)) => {
+                    visitor.visit_enum(s.into_deserializer())
+                }
+                FdonValue::EscapedString(FdonStr::Owned(s)) => visitor.visit_enum(s.as_str().into_deserializer()),
+                _ => Err(de::Error::custom(EXPECTED_VARIANT)),
+            }
+        };
+        result.map_err(|e| at(e, start))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FdonError> {
+        self.parser.skip_value()?;
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+const EXPECTED_VARIANT: &str = "expected an enum variant (a string or O{Variant:...})";
+
+// Các entry của O{...} (theo đúng luật dấu phẩy của parse_value)
+struct Entries<'d, 'de, 'bump> {
+    de: &'d mut Deserializer<'de, 'bump>,
+    // Key đã gặp (chỉ dùng khi reject_duplicate_keys)
+    seen: HashSet<Cow<'de, str>, AHasher>,
+}
+
+impl<'de: 'bump, 'bump> de::MapAccess<'de> for Entries<'_, 'de, 'bump> {
+    type Error = FdonError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, FdonError> {
+        self.de.parser.skip_whitespace();
+        if self.de.parser.peek() == Some(b'}') {
+            return Ok(None);
+        }
+        let start = self.de.parser.index;
+        self.de.key.clear();
+        let key = self.de.parser.parse_key_into(&mut self.de.key)?;
+        self.de.parser.consume(b':')?;
+        if self.de.parser.options.reject_duplicate_keys
+            && !self.seen.insert(key.map_or_else(|| Cow::Owned(self.de.key.clone()), Cow::Borrowed))
+        {
+            return Err(FdonError::DuplicateKey { key: key.unwrap_or(&self.de.key).to_string(), pos: start });
+        }
+        self.de.visit_key(key, seed).map(Some).map_err(|e| at(e, start))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, FdonError> {
+        self.de.parser.skip_whitespace();
+        let start = self.de.parser.index;
+        // Lỗi serde phát sinh sau khi value đã đọc xong (vd. enum có tag) vẫn chỉ về value
+        let value = seed.deserialize(&mut *self.de).map_err(|e| at(e, start))?;
+        self.de.parser.skip_separator(b'}', Container::Object)?;
+        Ok(value)
+    }
+}
+
+// Các phần tử của A[...]
+struct Items<'d, 'de, 'bump> {
+    de: &'d mut Deserializer<'de, 'bump>,
+}
+
+impl<'de: 'bump, 'bump> de::SeqAccess<'de> for Items<'_, 'de, 'bump> {
+    type Error = FdonError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, FdonError> {
+        self.de.parser.skip_whitespace();
+        if self.de.parser.peek() == Some(b']') {
+            return Ok(None);
+        }
+        let start = self.de.parser.index;
+        let value = seed.deserialize(&mut *self.de).map_err(|e| at(e, start))?;
+        self.de.parser.skip_separator(b']', Container::Array)?;
+        Ok(Some(value))
+    }
+}
+
+// Enum dạng O{Variant:value}: key là tên variant
+struct Variant<'d, 'de, 'bump> {
+    de: &'d mut Deserializer<'de, 'bump>,
+}
+
+impl<'d, 'de: 'bump, 'bump> de::EnumAccess<'de> for Variant<'d, 'de, 'bump> {
+    type Error = FdonError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), FdonError> {
+        self.de.key.clear();
+        let key = self.de.parser.parse_key_into(&mut self.de.key)?;
+        self.de.parser.consume(b':')?;
+        let variant = self.de.visit_key(key, seed)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de: 'bump, 'bump> de::VariantAccess<'de> for Variant<'_, 'de, 'bump> {
+    type Error = FdonError;
+
+    fn unit_variant(self) -> Result<(), FdonError> {
+        de::Deserialize::deserialize(&mut *self.de)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, FdonError> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, FdonError> {
+        de::Deserializer::deserialize_any(&mut *self.de, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FdonError> {
+        de::Deserializer::deserialize_any(&mut *self.de, visitor)
+    }
+}
@@ -0,0 +1,57 @@
+// --- Chỉ thị phiên bản đầu tài liệu: `%fdon 1` ---
+
+use crate::FdonError;
+
+/// Highest major format version this parser understands.
+pub const FDON_VERSION: u32 = 1;
+
+/// Version number of a leading `%fdon <N>` directive in `input` (raw or minified,
+/// so `%fdon 1\n...` and `%fdon1...` both work), or `None` if there is no
+/// well-formed directive. Does not check whether the version is supported.
+pub fn document_version(input: &str) -> Option<u32> {
+    read_directive(input.as_bytes()).ok().flatten().map(|d| d.version)
+}
+
+pub(crate) struct Directive {
+    pub(crate) version: u32,
+    /// Offset của chữ số đầu tiên (vị trí báo lỗi UnsupportedVersion)
+    pub(crate) version_pos: usize,
+    /// Tổng số byte của chỉ thị, kể cả whitespace phía sau
+    pub(crate) len: usize,
+}
+
+/// Reads a leading directive: `%fdon`, optional spaces/tabs, decimal digits, then
+/// any whitespace. `Ok(None)` if `data` does not start with `%`.
+pub(crate) fn read_directive(data: &[u8]) -> Result<Option<Directive>, FdonError> {
+    if data.first() != Some(&b'%') {
+        return Ok(None);
+    }
+    if !data.starts_with(b"%fdon") {
+        return Err(FdonError::InvalidDirective { pos: 0 });
+    }
+    let mut i = 5;
+    while i < data.len() && matches!(data[i], b' ' | b'\t') {
+        i += 1;
+    }
+    let digits_start = i;
+    while i < data.len() && data[i].is_ascii_digit() {
+        i += 1;
+    }
+    let version = atoi::atoi::<u32>(&data[digits_start..i])
+        .ok_or(FdonError::InvalidDirective { pos: digits_start })?;
+    while i < data.len() && matches!(data[i], b' ' | b'\t' | b'\r' | b'\n') {
+        i += 1;
+    }
+    Ok(Some(Directive { version, version_pos: digits_start, len: i }))
+}
+
+/// `read_directive` plus the version check; returns how many bytes to skip.
+pub(crate) fn check_directive(data: &[u8]) -> Result<usize, FdonError> {
+    match read_directive(data)? {
+        None => Ok(0),
+        Some(d) if d.version == 0 || d.version > FDON_VERSION => {
+            Err(FdonError::UnsupportedVersion { version: d.version, pos: d.version_pos })
+        }
+        Some(d) => Ok(d.len),
+    }
+}
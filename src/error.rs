@@ -0,0 +1,510 @@
+// --- Lỗi (Structured Error) ---
+
+use std::fmt;
+use std::io;
+
+/// Which container an error was raised in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Container {
+    Object,
+    Array,
+}
+
+impl Container {
+    #[inline]
+    fn name(self) -> &'static str {
+        match self {
+            Container::Object => "object",
+            Container::Array => "array",
+        }
+    }
+
+    #[inline]
+    fn close(self) -> char {
+        match self {
+            Container::Object => '}',
+            Container::Array => ']',
+        }
+    }
+}
+
+/// How much detail the parser puts into its errors (`ParseOptions::error_verbosity`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorVerbosity {
+    /// Bare error codes: no heap allocation on any error path
+    /// (`InvalidFloat::detail` is left empty). Meant for hot-path validation.
+    Terse,
+    /// The plain error variants (the default).
+    #[default]
+    Normal,
+    /// Wraps errors in `FdonError::Annotated` with a snippet of the input and,
+    /// where one applies, a hint on how to fix it.
+    Verbose,
+}
+
+/// Every error the parser (and the file helpers) can produce.
+///
+/// `pos` is a byte offset into the buffer that was handed to the parser.
+/// `Display` reproduces the messages of the old `(String, usize)` errors.
+#[derive(Debug)]
+pub enum FdonError {
+    /// The parser wanted `expected` but saw `found` (`None` = end of input).
+    Expected { expected: u8, found: Option<u8>, pos: usize },
+    /// `SE` was not immediately followed by the string delimiter.
+    ExpectedDelimiterAfterSE { delimiter: u8, pos: usize },
+    UnexpectedEof { pos: usize },
+    UnknownTypeSpecifier { byte: u8, pos: usize },
+    TrailingComma { container: Container, pos: usize },
+    MissingComma { container: Container, pos: usize },
+    /// No `:` after an object key.
+    UnterminatedKey { pos: usize },
+    /// A zero-length key with `ParseOptions::forbid_empty_keys` set.
+    EmptyKey { pos: usize },
+    /// No closing delimiter for a `S`/`D`/`T` (`escaped: false`) or `SE` string.
+    UnterminatedString { escaped: bool, pos: usize },
+    /// Input ended right after a `\` inside `SE"..."`.
+    EofAfterEscape { pos: usize },
+    /// `\ch` is not a known escape (only with `ParseOptions::strict_escapes`);
+    /// `pos` is the backslash.
+    InvalidEscape { ch: u8, pos: usize },
+    /// Malformed `\uXXXX` / `\u{X...}` escape: bad or missing hex digits, a value
+    /// above U+10FFFF, or a lone surrogate. `pos` is the backslash.
+    InvalidUnicodeEscape { pos: usize },
+    /// An unescaped line feed inside a string with
+    /// `ParseOptions::forbid_literal_newlines_in_strings`; `pos` is the `\n` byte.
+    LiteralNewline { pos: usize },
+    /// Containers nested deeper than `ParseOptions::max_depth`; `pos` is the `O`/`A`
+    /// tag of the first container past the limit.
+    DepthLimitExceeded { pos: usize },
+    /// A key, number or string body longer than `ParseOptions::max_token_scan`;
+    /// `pos` is where the token (or string body) starts.
+    TokenTooLong { pos: usize },
+    /// Raised by `from_fdon_str` when the document does not fit the target type
+    /// (wrong kind of value, missing field, ...). `pos` is the start of the value
+    /// being deserialized.
+    Deserialize { msg: String, pos: Option<usize> },
+    EmptyNumber { pos: usize },
+    /// Not an integer: `pos` is the first byte that does not belong to it (`N12x`),
+    /// or the start of the number if it is only a sign.
+    InvalidInteger { pos: usize },
+    /// A well-formed integer outside `i64` (and, if non-negative, `u64`). Unlike
+    /// `InvalidInteger` the input is fine; see `ParseOptions::overflow_to_float`.
+    IntegerOverflow { pos: usize },
+    /// Not a float: `pos` is the first unexpected byte (`N1.2.3`), or the start of
+    /// the number if it could not be read at all or is not finite (`N1e999`, unless
+    /// `ParseOptions::overflow_to_float`).
+    InvalidFloat { detail: String, pos: usize },
+    /// A `_` digit separator that is not between two digits (`N1__0`, `N_1`, `N1_.5`).
+    InvalidDigitSeparator { pos: usize },
+    /// A `i`/`u`/`f` number suffix that contradicts the literal (e.g. `N1.5i`, `N-1u`).
+    InvalidNumberSuffix { suffix: u8, pos: usize },
+    InvalidBool { pos: usize },
+    /// With `ParseOptions::strict_date_time`, a `D"..."` that is not a real
+    /// `YYYY-MM-DD` date (`D"2024-13-40"`). `pos` is the `D` tag.
+    InvalidDate { pos: usize },
+    /// With `ParseOptions::strict_date_time`, a `T"..."` that is not a real
+    /// `HH:MM:SS[.fff]` time (`T"25:00:00"`). `pos` is the `T` tag.
+    InvalidTime { pos: usize },
+    /// A key or string that is not valid UTF-8, in input parsed from bytes
+    /// (`parse_fdon_bytes`). `pos` is the first byte of the bad sequence.
+    InvalidUtf8 { pos: usize },
+    /// A key seen earlier in the same object, with
+    /// `ParseOptions::reject_duplicate_keys`; `pos` is the repeated key.
+    DuplicateKey { key: String, pos: usize },
+    /// Two values in a scalar stream with nothing to tell them apart (`N1N2`).
+    UnseparatedValues { pos: usize },
+    /// An `O`/`A` container where `parse_scalar_stream` expects a scalar.
+    NotAScalar { pos: usize },
+    /// `parse_value_at` was given an offset that is not the start of a value.
+    InvalidOffset { pos: usize },
+    /// A leading `%...` directive that is not `%fdon <N>`.
+    InvalidDirective { pos: usize },
+    /// `ParseOptions::string_delimiter` or `SerializeOptions::string_delimiter` is a
+    /// byte FDON cannot use to quote strings (see `is_valid_string_delimiter`).
+    InvalidDelimiter { byte: u8 },
+    /// `%fdon <N>` names a version this parser does not support.
+    UnsupportedVersion { version: u32, pos: usize },
+    /// The document starts with `{`, `[` or `"`: probably JSON rather than FDON.
+    LooksLikeJson { pos: usize },
+    ExtraData { pos: usize },
+    /// Reading the input failed (file helpers only).
+    Io(io::Error),
+    /// `error` plus context, produced with `ErrorVerbosity::Verbose`.
+    /// `snippet` is the input around the error; the error sits at byte `column` of it.
+    Annotated {
+        error: Box<FdonError>,
+        hint: Option<&'static str>,
+        snippet: String,
+        column: usize,
+    },
+}
+
+impl FdonError {
+    /// Byte offset of the error, or `None` for I/O errors, `InvalidDelimiter` (and
+    /// `Deserialize` errors raised outside any value).
+    pub fn pos(&self) -> Option<usize> {
+        match self {
+            FdonError::Expected { pos, .. }
+            | FdonError::ExpectedDelimiterAfterSE { pos, .. }
+            | FdonError::UnexpectedEof { pos }
+            | FdonError::UnknownTypeSpecifier { pos, .. }
+            | FdonError::TrailingComma { pos, .. }
+            | FdonError::MissingComma { pos, .. }
+            | FdonError::UnterminatedKey { pos }
+            | FdonError::EmptyKey { pos }
+            | FdonError::UnterminatedString { pos, .. }
+            | FdonError::EofAfterEscape { pos }
+            | FdonError::InvalidEscape { pos, .. }
+            | FdonError::InvalidUnicodeEscape { pos }
+            | FdonError::LiteralNewline { pos }
+            | FdonError::DepthLimitExceeded { pos }
+            | FdonError::TokenTooLong { pos }
+            | FdonError::EmptyNumber { pos }
+            | FdonError::InvalidInteger { pos }
+            | FdonError::IntegerOverflow { pos }
+            | FdonError::InvalidDigitSeparator { pos }
+            | FdonError::InvalidFloat { pos, .. }
+            | FdonError::InvalidNumberSuffix { pos, .. }
+            | FdonError::InvalidBool { pos }
+            | FdonError::InvalidDate { pos }
+            | FdonError::InvalidTime { pos }
+            | FdonError::InvalidUtf8 { pos }
+            | FdonError::DuplicateKey { pos, .. }
+            | FdonError::LooksLikeJson { pos }
+            | FdonError::UnseparatedValues { pos }
+            | FdonError::NotAScalar { pos }
+            | FdonError::InvalidOffset { pos }
+            | FdonError::InvalidDirective { pos }
+            | FdonError::UnsupportedVersion { pos, .. }
+            | FdonError::ExtraData { pos } => Some(*pos),
+            FdonError::Deserialize { pos, .. } => *pos,
+            FdonError::InvalidDelimiter { .. } | FdonError::Io(_) => None,
+            FdonError::Annotated { error, .. } => error.pos(),
+        }
+    }
+
+    /// The same error with every position moved `by` bytes later, for errors raised
+    /// on a buffer that starts `by` bytes into the stream.
+    pub(crate) fn offset_by(mut self, by: usize) -> FdonError {
+        if let Some(pos) = self.pos_mut() {
+            *pos += by;
+        }
+        self
+    }
+
+    fn pos_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            FdonError::Expected { pos, .. }
+            | FdonError::ExpectedDelimiterAfterSE { pos, .. }
+            | FdonError::UnexpectedEof { pos }
+            | FdonError::UnknownTypeSpecifier { pos, .. }
+            | FdonError::TrailingComma { pos, .. }
+            | FdonError::MissingComma { pos, .. }
+            | FdonError::UnterminatedKey { pos }
+            | FdonError::EmptyKey { pos }
+            | FdonError::UnterminatedString { pos, .. }
+            | FdonError::EofAfterEscape { pos }
+            | FdonError::InvalidEscape { pos, .. }
+            | FdonError::InvalidUnicodeEscape { pos }
+            | FdonError::LiteralNewline { pos }
+            | FdonError::DepthLimitExceeded { pos }
+            | FdonError::TokenTooLong { pos }
+            | FdonError::EmptyNumber { pos }
+            | FdonError::InvalidInteger { pos }
+            | FdonError::IntegerOverflow { pos }
+            | FdonError::InvalidDigitSeparator { pos }
+            | FdonError::InvalidFloat { pos, .. }
+            | FdonError::InvalidNumberSuffix { pos, .. }
+            | FdonError::InvalidBool { pos }
+            | FdonError::InvalidDate { pos }
+            | FdonError::InvalidTime { pos }
+            | FdonError::InvalidUtf8 { pos }
+            | FdonError::DuplicateKey { pos, .. }
+            | FdonError::LooksLikeJson { pos }
+            | FdonError::UnseparatedValues { pos }
+            | FdonError::NotAScalar { pos }
+            | FdonError::InvalidOffset { pos }
+            | FdonError::InvalidDirective { pos }
+            | FdonError::UnsupportedVersion { pos, .. }
+            | FdonError::ExtraData { pos } => Some(pos),
+            FdonError::Deserialize { pos, .. } => pos.as_mut(),
+            FdonError::InvalidDelimiter { .. } | FdonError::Io(_) => None,
+            FdonError::Annotated { error, .. } => error.pos_mut(),
+        }
+    }
+
+    /// The error without any `Annotated` wrapper.
+    pub fn inner(&self) -> &FdonError {
+        match self {
+            FdonError::Annotated { error, .. } => error.inner(),
+            e => e,
+        }
+    }
+
+    // Bọc lỗi với đoạn input xung quanh + gợi ý (chỉ chạy ở chế độ Verbose)
+    #[cold]
+    pub(crate) fn annotate(self, data: &[u8], delimiter: u8) -> FdonError {
+        let Some(pos) = self.pos() else { return self };
+        let pos = pos.min(data.len());
+        let hint = self.hint(data, pos, delimiter);
+
+        let (start, end) = snippet_window(data, pos, SNIPPET_CONTEXT, SNIPPET_CONTEXT);
+        FdonError::Annotated {
+            error: Box::new(self),
+            hint,
+            snippet: String::from_utf8_lossy(&data[start..end]).into_owned(),
+            column: pos - start,
+        }
+    }
+
+    fn hint(&self, data: &[u8], pos: usize, delimiter: u8) -> Option<&'static str> {
+        match self {
+            // S"a"b" -> chuỗi raw bị cắt sớm bởi dấu nháy bên trong
+            FdonError::MissingComma { .. } | FdonError::ExtraData { .. }
+                if pos > 0 && data[pos - 1] == delimiter =>
+            {
+                Some("did you mean to use SE for a string with quotes? Raw S strings cannot contain the delimiter")
+            }
+            FdonError::MissingComma { .. } => Some("separate items with ','"),
+            FdonError::TrailingComma { .. } => Some("remove the ',' before the closing bracket"),
+            FdonError::ExtraData { .. } => {
+                Some("a document holds a single top-level value; wrap several values in A[...]")
+            }
+            FdonError::UnknownTypeSpecifier { byte, .. } if *byte == delimiter => {
+                Some("strings need a type tag, e.g. S\"...\" or SE\"...\"")
+            }
+            FdonError::UnknownTypeSpecifier { .. } => {
+                Some("values start with one of O, A, S, SE, D, T, N, B or U")
+            }
+            FdonError::UnterminatedKey { .. } => Some("object keys must be followed by ':'"),
+            FdonError::EmptyKey { .. } => Some("give the key a name, or turn off forbid_empty_keys"),
+            FdonError::UnterminatedString { .. } => Some("close the string with the delimiter"),
+            FdonError::ExpectedDelimiterAfterSE { .. } => {
+                Some("SE must be followed directly by the delimiter, e.g. SE\"...\"")
+            }
+            FdonError::InvalidEscape { .. } => {
+                Some("known escapes are \\n \\t \\r \\\" \\\\ \\u; write a literal backslash as \\\\")
+            }
+            FdonError::InvalidUnicodeEscape { .. } => {
+                Some("write \\uXXXX (4 hex digits; pair surrogates as \\uD83D\\uDE00) or \\u{X} with 1 to 6 hex digits")
+            }
+            FdonError::LiteralNewline { .. } => {
+                Some("write line breaks as \\n inside SE\"...\", or turn off forbid_literal_newlines_in_strings")
+            }
+            FdonError::DepthLimitExceeded { .. } => {
+                Some("flatten the document, or raise ParseOptions::max_depth")
+            }
+            FdonError::TokenTooLong { .. } => {
+                Some("look for a missing closing delimiter, or raise max_token_scan")
+            }
+            FdonError::EmptyNumber { .. } => Some("N and T need digits, e.g. N42"),
+            FdonError::InvalidInteger { .. } => Some("integers are an optional sign followed by digits"),
+            FdonError::IntegerOverflow { .. } => Some(
+                "integers must fit in i64 (or u64 when non-negative); add a '.' for a float, \
+                 or set ParseOptions::overflow_to_float",
+            ),
+            FdonError::InvalidDigitSeparator { .. } => Some("'_' may only separate two digits, e.g. N1_000"),
+            FdonError::InvalidNumberSuffix { .. } => {
+                Some("'i' and 'u' cannot mark a float literal, and 'u' cannot mark a negative number")
+            }
+            FdonError::InvalidBool { .. } => Some("booleans are written Btrue or Bfalse"),
+            FdonError::InvalidDate { .. } => Some("dates are written D\"YYYY-MM-DD\", e.g. D\"2024-02-29\""),
+            FdonError::DuplicateKey { .. } => Some("a key may appear only once per object"),
+            FdonError::InvalidUtf8 { .. } => Some("keys and strings must be UTF-8 encoded"),
+            FdonError::InvalidTime { .. } => {
+                Some("times are written T\"HH:MM:SS\" (24-hour, optional .fraction), e.g. T\"08:30:00.5\"")
+            }
+            _ => None,
+        }
+    }
+}
+
+// Số byte input hiển thị mỗi bên vị trí lỗi
+const SNIPPET_CONTEXT: usize = 20;
+// Độ rộng cửa sổ của render_error (giống bản in lỗi cũ của CLI)
+const RENDER_WINDOW: usize = 100;
+
+// Cửa sổ [start, end) quanh `pos`, nới ra để không cắt giữa một ký tự UTF-8
+fn snippet_window(data: &[u8], pos: usize, before: usize, after: usize) -> (usize, usize) {
+    let mut start = pos.saturating_sub(before);
+    let mut end = (pos + after).min(data.len());
+    while start > 0 && (data[start] & 0xC0) == 0x80 {
+        start -= 1;
+    }
+    while end < data.len() && (data[end] & 0xC0) == 0x80 {
+        end += 1;
+    }
+    (start, end)
+}
+
+/// Renders `err` as a multi-line diagnostic against `source`, the buffer that was
+/// parsed (for `parse_fdon_zero_copy_arena`, the *minified* text):
+///
+/// ```text
+/// Missing comma or '}' in object at position 8
+/// O{a:S"x"b:N2}
+///         ^
+/// ```
+///
+/// Long inputs are cut to a window of about 100 bytes around the error, marked with
+/// `...`; the cut never splits a UTF-8 character, and newlines and tabs are shown as
+/// spaces so the caret stays aligned. Errors without a position (I/O) render as
+/// their message alone.
+pub fn render_error(source: &str, err: &FdonError) -> String {
+    let Some(pos) = err.pos() else { return err.to_string() };
+    let data = source.as_bytes();
+    let pos = pos.min(data.len());
+    let (start, end) = if data.len() > RENDER_WINDOW {
+        snippet_window(data, pos, RENDER_WINDOW / 2, RENDER_WINDOW / 2)
+    } else {
+        (0, data.len())
+    };
+    let prefix = if start > 0 { "..." } else { "" };
+    let suffix = if end < data.len() { "..." } else { "" };
+    let snippet: String = source[start..end]
+        .chars()
+        .map(|c| if matches!(c, '\n' | '\r' | '\t') { ' ' } else { c })
+        .collect();
+    let column = prefix.len() + source[start..pos].chars().count();
+    format!(
+        "{} at position {}\n{}{}{}\n{:>width$}",
+        err,
+        pos,
+        prefix,
+        snippet,
+        suffix,
+        "^",
+        width = column + 1
+    )
+}
+
+/// 1-based `(line, column)` of byte offset `pos` in `source`, e.g. for an error's
+/// `pos()`. Lines end at `\n`; columns count characters, not bytes. A `pos` past the
+/// end is clamped to it.
+///
+/// Offsets from `parse_fdon_zero_copy_arena` refer to the text that was parsed. If that
+/// was minified, map the offset back with `source_offset` first, or every error is on
+/// line 1.
+pub fn line_col_at(source: &str, pos: usize) -> (usize, usize) {
+    let mut pos = pos.min(source.len());
+    while !source.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    let before = &source.as_bytes()[..pos];
+    let line = memchr::memchr_iter(b'\n', before).count() + 1;
+    let line_start = memchr::memrchr(b'\n', before).map_or(0, |i| i + 1);
+    (line, source[line_start..pos].chars().count() + 1)
+}
+
+impl fmt::Display for FdonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FdonError::Expected { expected, found: Some(found), .. } => {
+                write!(f, "Expected '{}' but found '{}'", *expected as char, *found as char)
+            }
+            FdonError::Expected { expected, found: None, .. } => {
+                write!(f, "Expected '{}' but found 'EOF'", *expected as char)
+            }
+            FdonError::ExpectedDelimiterAfterSE { delimiter, .. } => {
+                write!(f, "Expected '{}' after SE", *delimiter as char)
+            }
+            FdonError::UnexpectedEof { .. } => f.write_str("Unexpected EOF"),
+            FdonError::UnknownTypeSpecifier { byte, .. } => {
+                write!(f, "Unknown data type specifier '{}'", *byte as char)
+            }
+            FdonError::TrailingComma { container, .. } => {
+                write!(f, "Trailing comma detected in {}", container.name())
+            }
+            FdonError::MissingComma { container, .. } => {
+                write!(f, "Missing comma or '{}' in {}", container.close(), container.name())
+            }
+            FdonError::UnterminatedKey { .. } => f.write_str("EOF while reading key (':' not found)"),
+            FdonError::EmptyKey { .. } => f.write_str("Empty object key"),
+            FdonError::UnterminatedString { escaped: false, .. } => {
+                f.write_str("EOF while reading string ('\"' not found)")
+            }
+            FdonError::UnterminatedString { escaped: true, .. } => {
+                f.write_str("EOF while reading escaped string ('\"' not found)")
+            }
+            FdonError::EofAfterEscape { .. } => f.write_str("EOF after escape character '\\'"),
+            FdonError::InvalidEscape { ch, .. } if ch.is_ascii_graphic() => {
+                write!(f, "Unknown escape sequence '\\{}'", *ch as char)
+            }
+            FdonError::InvalidEscape { ch, .. } => write!(f, "Unknown escape sequence '\\' + 0x{:02x}", ch),
+            FdonError::InvalidUnicodeEscape { .. } => f.write_str("Invalid unicode escape"),
+            FdonError::LiteralNewline { .. } => f.write_str("Literal newline in string"),
+            FdonError::DepthLimitExceeded { .. } => f.write_str("Nesting deeper than max_depth"),
+            FdonError::TokenTooLong { .. } => f.write_str("Token longer than max_token_scan"),
+            FdonError::Deserialize { msg, .. } => f.write_str(msg),
+            FdonError::EmptyNumber { .. } => f.write_str("Empty number value"),
+            FdonError::InvalidInteger { .. } => f.write_str("Invalid integer format"),
+            FdonError::IntegerOverflow { .. } => f.write_str("Integer out of range"),
+            FdonError::InvalidFloat { detail, .. } if detail.is_empty() => f.write_str("Invalid float format"),
+            FdonError::InvalidFloat { detail, .. } => write!(f, "Invalid float format: {}", detail),
+            FdonError::InvalidDigitSeparator { .. } => f.write_str("Misplaced digit separator"),
+            FdonError::InvalidNumberSuffix { suffix, .. } => {
+                write!(f, "Number suffix '{}' conflicts with the literal", *suffix as char)
+            }
+            FdonError::InvalidBool { .. } => f.write_str("Invalid boolean value"),
+            FdonError::InvalidDate { .. } => f.write_str("Invalid date"),
+            FdonError::InvalidTime { .. } => f.write_str("Invalid time"),
+            FdonError::InvalidUtf8 { .. } => f.write_str("Invalid UTF-8"),
+            FdonError::DuplicateKey { key, .. } => write!(f, "Duplicate key '{}'", key),
+            FdonError::UnseparatedValues { .. } => {
+                f.write_str("Values must be separated by whitespace (a number has no terminator)")
+            }
+            FdonError::NotAScalar { .. } => f.write_str("Expected a scalar value, found a container"),
+            FdonError::InvalidOffset { .. } => f.write_str("Offset is not the start of a value"),
+            FdonError::InvalidDirective { .. } => f.write_str("Invalid directive (expected '%fdon <version>')"),
+            FdonError::InvalidDelimiter { byte } if byte.is_ascii_graphic() => {
+                write!(f, "'{}' cannot be used as a string delimiter", *byte as char)
+            }
+            FdonError::InvalidDelimiter { byte } => {
+                write!(f, "0x{:02x} cannot be used as a string delimiter", byte)
+            }
+            FdonError::UnsupportedVersion { version, .. } => {
+                write!(f, "Unsupported FDON version {} (this parser supports up to {})", version, crate::FDON_VERSION)
+            }
+            FdonError::LooksLikeJson { .. } => f.write_str(
+                "Input looks like JSON, not FDON: values need type tags (O{...}, A[...], S\"...\", N1); convert the JSON first",
+            ),
+            FdonError::ExtraData { .. } => f.write_str("Extra data detected at end of file"),
+            FdonError::Io(e) => write!(f, "I/O error: {}", e),
+            FdonError::Annotated { error, hint, snippet, column } => {
+                // Căn dấu ^ theo số ký tự, không phải số byte
+                let width = snippet.get(..*column).map_or(*column, |s| s.chars().count()) + 1;
+                write!(f, "{}\n  near: {}\n        {:>width$}", error, snippet, "^")?;
+                if let Some(hint) = hint {
+                    write!(f, "\n  hint: {}", hint)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FdonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FdonError::Io(e) => Some(e),
+            FdonError::Annotated { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// The pre-enum `(message, position)` error tuple, for code still matching on it.
+/// Annotations are dropped from the message; I/O errors get position 0.
+impl From<FdonError> for (String, usize) {
+    fn from(e: FdonError) -> Self {
+        let pos = e.pos().unwrap_or(0);
+        (e.inner().to_string(), pos)
+    }
+}
+
+impl From<io::Error> for FdonError {
+    #[inline]
+    fn from(e: io::Error) -> Self {
+        FdonError::Io(e)
+    }
+}
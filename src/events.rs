@@ -0,0 +1,209 @@
+// --- Duyệt theo sự kiện (SAX): không dựng cây, không dùng Arena ---
+
+use std::ops::ControlFlow;
+
+use bumpalo::Bump;
+
+use crate::validate::ContainerStack;
+use crate::{check_delimiter, directive, Container, FdonError, FdonNumber, FdonParser, FdonValue, ParseOptions};
+
+/// Callbacks for `parse_events`, called in document order. Every method does
+/// nothing by default; return `ControlFlow::Break(())` from any of them to stop
+/// parsing on the spot.
+pub trait FdonVisitor {
+    fn on_object_start(&mut self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// A key of the innermost open object; the events of its value follow.
+    fn on_key(&mut self, _key: &str) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn on_object_end(&mut self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn on_array_start(&mut self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn on_array_end(&mut self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// An `S"..."` or (unescaped) `SE"..."` string. Unescaped text only lives for
+    /// the duration of the call.
+    fn on_string(&mut self, _s: &str) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn on_number(&mut self, _n: FdonNumber) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn on_bool(&mut self, _b: bool) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn on_null(&mut self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// A `D"..."` value; forwards to `on_string` unless overridden.
+    fn on_date(&mut self, s: &str) -> ControlFlow<()> {
+        self.on_string(s)
+    }
+
+    /// A `T"..."` value; forwards to `on_string` unless overridden.
+    fn on_time(&mut self, s: &str) -> ControlFlow<()> {
+        self.on_string(s)
+    }
+
+    /// A numeric `T` value; forwards to `on_number` unless overridden.
+    fn on_timestamp(&mut self, n: FdonNumber) -> ControlFlow<()> {
+        self.on_number(n)
+    }
+}
+
+/// Parses `input` and reports each value to `visitor` instead of building a tree.
+///
+/// Nothing is allocated in an arena: strings are passed straight from `input`,
+/// and `SE"..."` strings and keys with escapes are unescaped into one reused buffer.
+/// Returns `ControlFlow::Break(())` if the visitor stopped early, in which case
+/// the rest of the input is not checked. Errors are the same as
+/// `parse_fdon_zero_copy_arena`'s, but arrive after the events for everything
+/// before them. A repeated key is reported each time it appears.
+#[inline]
+pub fn parse_events<V: FdonVisitor>(input: &str, visitor: &mut V) -> Result<ControlFlow<()>, FdonError> {
+    parse_events_with_options(input, visitor, ParseOptions::default())
+}
+
+/// Same as `parse_events`, with explicit `ParseOptions`.
+pub fn parse_events_with_options<V: FdonVisitor>(
+    input: &str,
+    visitor: &mut V,
+    options: ParseOptions,
+) -> Result<ControlFlow<()>, FdonError> {
+    // Không value nào được dựng nên Arena không bao giờ cấp phát
+    let arena = Bump::new();
+    let mut parser = FdonParser::with_options(input, &arena, options);
+    drive(&mut parser, visitor).map_err(|e| parser.decorate(e))
+}
+
+// Gọi callback; dừng toàn bộ quá trình parse nếu visitor trả Break
+macro_rules! visit {
+    ($call:expr) => {
+        if let ControlFlow::Break(()) = $call {
+            return Ok(ControlFlow::Break(()));
+        }
+    };
+}
+
+fn drive<'a: 'bump, 'bump, V: FdonVisitor>(
+    parser: &mut FdonParser<'a, 'bump>,
+    visitor: &mut V,
+) -> Result<ControlFlow<()>, FdonError> {
+    check_delimiter(parser.options.string_delimiter)?;
+    if parser.options.allow_version_directive {
+        parser.index = directive::check_directive(parser.data)?;
+    }
+    parser.skip_whitespace();
+    if matches!(parser.peek(), Some(b'{' | b'[' | b'"')) {
+        return Err(FdonError::LooksLikeJson { pos: parser.index });
+    }
+    // Container đang mở (không cấp phát tới 128 tầng)
+    let mut stack = ContainerStack::default();
+    // Dùng lại cho mọi SE"..." có escape (value và key)
+    let mut unescaped = String::new();
+
+    'value: loop {
+        parser.skip_whitespace();
+        let type_char = parser.peek().ok_or(FdonError::UnexpectedEof { pos: parser.index })?;
+        parser.advance();
+
+        match type_char {
+            b'O' => {
+                parser.enter(parser.index - 1)?;
+                parser.skip_whitespace();
+                parser.consume(b'{')?;
+                parser.skip_whitespace();
+                visit!(visitor.on_object_start());
+                if parser.peek() != Some(b'}') {
+                    unescaped.clear();
+                    let key = parser.parse_key_into(&mut unescaped)?;
+                    parser.consume(b':')?;
+                    stack.push(Container::Object);
+                    visit!(visitor.on_key(key.unwrap_or(&unescaped)));
+                    continue 'value;
+                }
+                parser.advance();
+                parser.leave();
+                visit!(visitor.on_object_end());
+            }
+            b'A' => {
+                parser.enter(parser.index - 1)?;
+                parser.skip_whitespace();
+                parser.consume(b'[')?;
+                parser.skip_whitespace();
+                visit!(visitor.on_array_start());
+                if parser.peek() != Some(b']') {
+                    stack.push(Container::Array);
+                    continue 'value;
+                }
+                parser.advance();
+                parser.leave();
+                visit!(visitor.on_array_end());
+            }
+            b'S' if parser.peek() == Some(b'E') => {
+                parser.advance();
+                unescaped.clear();
+                match parser.unescape_string(&mut unescaped)? {
+                    Some(s) => visit!(visitor.on_string(s)),
+                    None => visit!(visitor.on_string(&unescaped)),
+                }
+            }
+            // Các scalar còn lại không đụng tới Arena
+            _ => match parser.parse_scalar(type_char)? {
+                FdonValue::RawString(s) => visit!(visitor.on_string(s)),
+                FdonValue::Date(s) => visit!(visitor.on_date(s)),
+                FdonValue::Time(s) => visit!(visitor.on_time(s)),
+                FdonValue::Number(n) => visit!(visitor.on_number(n)),
+                FdonValue::Timestamp(n) => visit!(visitor.on_timestamp(n)),
+                FdonValue::Bool(b) => visit!(visitor.on_bool(b)),
+                FdonValue::Null => visit!(visitor.on_null()),
+                _ => unreachable!("parse_scalar returned a container or an escaped string"),
+            },
+        }
+
+        // Sau một value: dấu phẩy (key/value kế tiếp) hoặc đóng các container đã xong
+        while let Some(container) = stack.last() {
+            let close = if container == Container::Object { b'}' } else { b']' };
+            parser.skip_separator(close, container)?;
+            if parser.peek() != Some(close) {
+                if container == Container::Object {
+                    unescaped.clear();
+                    let key = parser.parse_key_into(&mut unescaped)?;
+                    parser.consume(b':')?;
+                    visit!(visitor.on_key(key.unwrap_or(&unescaped)));
+                }
+                continue 'value;
+            }
+            parser.advance();
+            parser.leave();
+            stack.pop();
+            if container == Container::Object {
+                visit!(visitor.on_object_end());
+            } else {
+                visit!(visitor.on_array_end());
+            }
+        }
+        break;
+    }
+
+    parser.skip_whitespace();
+    if parser.index != parser.data.len() {
+        return Err(FdonError::ExtraData { pos: parser.index });
+    }
+    Ok(ControlFlow::Continue(()))
+}
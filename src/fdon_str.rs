@@ -0,0 +1,118 @@
+// --- Nội dung chuỗi SE"..." (mượn từ input hoặc nằm trong Arena) ---
+
+use std::fmt;
+use std::ops::Deref;
+
+use bumpalo::{Bump, collections::String as BumpString};
+use serde::{Serialize, Serializer};
+
+/// Payload of `FdonValue::EscapedString`.
+///
+/// The parser borrows the string straight from the input when it contains no
+/// escapes (the common case) and only unescapes into the arena otherwise. Both
+/// forms deref to `str` and compare, print and serialize by content, so callers
+/// rarely need to tell them apart.
+pub enum FdonStr<'a, 'bump> {
+    /// Slice of the input (no escapes in the source).
+    Borrowed(&'a str),
+    /// Unescaped copy in the arena.
+    Owned(BumpString<'bump>),
+}
+
+impl<'a, 'bump> FdonStr<'a, 'bump> {
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match self {
+            FdonStr::Borrowed(s) => s,
+            FdonStr::Owned(s) => s.as_str(),
+        }
+    }
+
+    /// `true` if the content is a slice of the input rather than an arena copy.
+    #[inline]
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, FdonStr::Borrowed(_))
+    }
+
+    // Bản sao trong `arena`; phần mượn từ input vẫn giữ nguyên là mượn
+    #[inline]
+    pub(crate) fn clone_in<'new>(&self, arena: &'new Bump) -> FdonStr<'a, 'new> {
+        match self {
+            FdonStr::Borrowed(s) => FdonStr::Borrowed(s),
+            FdonStr::Owned(s) => FdonStr::Owned(BumpString::from_str_in(s, arena)),
+        }
+    }
+}
+
+impl Deref for FdonStr<'_, '_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for FdonStr<'_, '_> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'a> From<&'a str> for FdonStr<'a, '_> {
+    #[inline]
+    fn from(s: &'a str) -> Self {
+        FdonStr::Borrowed(s)
+    }
+}
+
+impl<'bump> From<BumpString<'bump>> for FdonStr<'_, 'bump> {
+    #[inline]
+    fn from(s: BumpString<'bump>) -> Self {
+        FdonStr::Owned(s)
+    }
+}
+
+// In như một chuỗi bình thường (không lộ ra Borrowed/Owned)
+impl fmt::Debug for FdonStr<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for FdonStr<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq for FdonStr<'_, '_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for FdonStr<'_, '_> {}
+
+impl PartialEq<str> for FdonStr<'_, '_> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for FdonStr<'_, '_> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Serialize for FdonStr<'_, '_> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
@@ -0,0 +1,54 @@
+// --- Bulk Parse (mỗi file một Arena) ---
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use bumpalo::Bump;
+
+use crate::{minify_fdon, parse_fdon_zero_copy_arena, FdonError, OwnedFdonValue};
+
+/// Parses every file in `paths`, each in its own short-lived arena, and returns
+/// owned results in the same order as `paths`.
+///
+/// A failure in one file (I/O or syntax) does not affect the others. With the
+/// `rayon` feature enabled, files are parsed in parallel.
+pub fn parse_files(paths: &[PathBuf]) -> Vec<(PathBuf, Result<OwnedFdonValue, FdonError>)> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        paths
+            .par_iter()
+            .map(|path| (path.clone(), parse_file_owned(path)))
+            .collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        paths
+            .iter()
+            .map(|path| (path.clone(), parse_file_owned(path)))
+            .collect()
+    }
+}
+
+fn parse_file_owned(path: &Path) -> Result<OwnedFdonValue, FdonError> {
+    from_reader_owned(File::open(path)?)
+}
+
+/// Reads a whole (not necessarily minified) FDON document from `reader` and returns
+/// it as a self-contained `OwnedFdonValue`.
+///
+/// The input is buffered in full, checked to be UTF-8 (invalid UTF-8 is an
+/// `FdonError::Io` with `InvalidData`), minified and parsed into a temporary arena
+/// that is dropped before returning. Slower than the zero-copy API, but nothing
+/// borrows from the input or an arena afterwards.
+pub fn from_reader_owned<R: Read>(mut reader: R) -> Result<OwnedFdonValue, FdonError> {
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+    let minified = minify_fdon(&content);
+
+    // Arena chỉ sống trong hàm này: giá trị được copy ra trước khi Arena bị giải phóng
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(&minified, &arena)?;
+    Ok(value.to_owned_value())
+}
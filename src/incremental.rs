@@ -0,0 +1,125 @@
+// --- Parser tăng dần cho framing trên mạng (dừng/tiếp tục giữa token) ---
+
+use bumpalo::Bump;
+
+use crate::{FdonError, FdonParser, FdonValue, ParseOptions, Validator};
+
+/// Result of feeding bytes to an `IncrementalParser`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// The first `len` buffered bytes form one complete value (see `IncrementalParser::value`).
+    Complete { len: usize },
+    /// More input is needed. `bytes_needed_hint` is a lower bound (at least 1): fewer
+    /// bytes can never complete the value, more may be needed.
+    Incomplete { bytes_needed_hint: usize },
+}
+
+/// Resumable parser for values arriving in pieces, e.g. from a non-blocking socket.
+///
+/// Bytes are buffered and checked as they arrive by the `Validator` state machine,
+/// which saves its state mid-token, so each byte is scanned once no matter how the
+/// input is split. Once a whole value is buffered, `feed` returns
+/// `Progress::Complete` and `value` parses it.
+///
+/// Values may follow each other on the stream (whitespace between them is skipped):
+/// `next_frame` drops the finished one and starts on the bytes after it. A root
+/// number (`N12`) has no terminator, so it only completes at `finish`; wrap scalars
+/// in `A[...]` when framing. Input must be minified. Error positions are offsets
+/// from the start of the current value. After an error the parser is unusable.
+pub struct IncrementalParser {
+    options: ParseOptions,
+    validator: Validator,
+    buf: Vec<u8>,
+    // Số byte của buf đã được validator quét
+    scanned: usize,
+    // Độ dài value hoàn chỉnh ở đầu buf (nếu đã có)
+    frame_len: Option<usize>,
+}
+
+impl Default for IncrementalParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IncrementalParser {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_options(ParseOptions::default())
+    }
+
+    #[inline]
+    pub fn with_options(options: ParseOptions) -> Self {
+        IncrementalParser {
+            options,
+            validator: Validator::with_options(options),
+            buf: Vec::new(),
+            scanned: 0,
+            frame_len: None,
+        }
+    }
+
+    /// Appends `bytes` and reports whether a complete value is now buffered.
+    /// Once `Complete`, further bytes are only buffered (for the next frame).
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<Progress, FdonError> {
+        self.buf.extend_from_slice(bytes);
+        self.scan()
+    }
+
+    /// Signals end of input: completes a root number, or reports what is left
+    /// unterminated (`UnexpectedEof` if nothing but whitespace was buffered).
+    pub fn finish(&mut self) -> Result<Progress, FdonError> {
+        if let Some(len) = self.frame_len {
+            return Ok(Progress::Complete { len });
+        }
+        let validator = std::mem::replace(&mut self.validator, Validator::with_options(self.options));
+        validator.finish()?;
+        self.frame_len = Some(self.scanned);
+        Ok(Progress::Complete { len: self.scanned })
+    }
+
+    /// Parses the complete value at the front of the buffer. `UnexpectedEof` if
+    /// `feed`/`finish` has not reported `Complete` yet; `InvalidUtf8` if a key or
+    /// string is not UTF-8 (the buffer is read as bytes, see `parse_fdon_bytes`).
+    pub fn value<'s, 'bump>(&'s self, arena: &'bump Bump) -> Result<FdonValue<'s, 'bump>, FdonError> {
+        let Some(len) = self.frame_len else {
+            return Err(FdonError::UnexpectedEof { pos: self.scanned });
+        };
+        FdonParser::from_bytes_with_options(&self.buf[..len], arena, self.options).parse()
+    }
+
+    /// Drops the completed value and scans the bytes buffered after it, which may
+    /// already hold the next complete value. Does nothing before `Complete`.
+    pub fn next_frame(&mut self) -> Result<Progress, FdonError> {
+        if let Some(len) = self.frame_len.take() {
+            self.buf.drain(..len);
+            self.scanned = 0;
+            self.validator = Validator::with_options(self.options);
+        }
+        self.scan()
+    }
+
+    /// Buffered bytes, starting at the current value.
+    #[inline]
+    pub fn buffered(&self) -> &[u8] {
+        &self.buf
+    }
+
+    fn scan(&mut self) -> Result<Progress, FdonError> {
+        if let Some(len) = self.frame_len {
+            return Ok(Progress::Complete { len });
+        }
+        if self.scanned == 0 {
+            // Whitespace giữa hai value trên stream
+            let ws = self.buf.iter().take_while(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r')).count();
+            self.buf.drain(..ws);
+        }
+        self.scanned += self.validator.feed_frame(&self.buf[self.scanned..])?;
+        if self.validator.root_complete() {
+            self.frame_len = Some(self.scanned);
+            Ok(Progress::Complete { len: self.scanned })
+        } else {
+            Ok(Progress::Incomplete { bytes_needed_hint: self.validator.min_bytes_needed() })
+        }
+    }
+}
@@ -0,0 +1,250 @@
+// --- JSON Output ---
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+use serde_json::ser::{CompactFormatter, Formatter, Serializer};
+use serde_json::{Map, Number, Value};
+
+use crate::{FdonNumber, FdonValue};
+
+/// Streams `value` as compact JSON into `writer` without building the whole
+/// output string first. Output is byte-identical to `serde_json::to_string(value)`.
+///
+/// Nesting deeper than `DEFAULT_MAX_DEPTH` is an `InvalidData` error (the JSON
+/// serializer recurses once per level); use `fdon_to_json_writer_with_options` to
+/// change the limit.
+///
+/// Wrap unbuffered sinks (files, sockets, stdout) in a `BufWriter`.
+#[inline]
+pub fn fdon_to_json_writer<W: Write>(value: &FdonValue<'_, '_>, writer: W) -> io::Result<()> {
+    let formatter = JsonFormatter { float_format: None, depth: 0, max_depth: DEFAULT_MAX_DEPTH };
+    let mut ser = Serializer::with_formatter(writer, formatter);
+    value.serialize(&mut ser).map_err(io::Error::from)
+}
+
+/// Default `SerializeOptions::max_depth` and `ParseOptions::max_depth`: containers
+/// nested this deep still serialize and parse.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// How floats are written by the serializers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FloatFormat {
+    /// Shortest digits that parse back to the same `f64` (`1.0`, `0.1`, `1e20`), via `ryu`.
+    /// Same digits as `fdon_to_json_writer`, but exponents carry no `+` (`1e53`, not `1e+53`).
+    #[default]
+    Shortest,
+    /// Exactly this many decimal places, rounded to nearest (`Fixed(2)`: `3.14`).
+    /// `Fixed(0)` writes no `.`, so the number reads back as an integer.
+    Fixed(u8),
+    /// Scientific notation with this many mantissa decimals (`Exponential(2)`: `3.14e0`).
+    Exponential(u8),
+}
+
+/// Output settings shared by the serializers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SerializeOptions {
+    pub float_format: FloatFormat,
+    /// Deepest container nesting the JSON writers accept (`A[]` is depth 1); deeper
+    /// values are an `InvalidData` error instead of a stack overflow. The FDON
+    /// writers do not recurse and ignore this.
+    pub max_depth: usize,
+    /// Byte the FDON writers quote strings, dates, times and quoted keys with
+    /// (default `"`), for output read back with the same
+    /// `ParseOptions::string_delimiter`. A byte failing `is_valid_string_delimiter`
+    /// is an `InvalidInput` error. The JSON writers ignore this.
+    pub string_delimiter: u8,
+}
+
+impl Default for SerializeOptions {
+    #[inline]
+    fn default() -> Self {
+        SerializeOptions { float_format: FloatFormat::default(), max_depth: DEFAULT_MAX_DEPTH, string_delimiter: b'"' }
+    }
+}
+
+/// Same as `fdon_to_json_writer`, but formats floats per `options.float_format`.
+/// NaN and infinities are still written as `null`.
+pub fn fdon_to_json_writer_with_options<W: Write>(
+    value: &FdonValue<'_, '_>,
+    writer: W,
+    options: &SerializeOptions,
+) -> io::Result<()> {
+    let formatter = JsonFormatter {
+        float_format: Some(options.float_format),
+        depth: 0,
+        max_depth: options.max_depth,
+    };
+    let mut ser = Serializer::with_formatter(writer, formatter);
+    value.serialize(&mut ser).map_err(io::Error::from)
+}
+
+// Giống CompactFormatter, chỉ thay cách ghi float (nếu có) và giới hạn độ sâu
+struct JsonFormatter {
+    float_format: Option<FloatFormat>,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl JsonFormatter {
+    #[inline]
+    fn enter(&mut self) -> io::Result<()> {
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("value nested deeper than {} levels", self.max_depth),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Formatter for JsonFormatter {
+    #[inline]
+    fn write_f64<W: ?Sized + Write>(&mut self, writer: &mut W, value: f64) -> io::Result<()> {
+        match self.float_format {
+            Some(format) => write_float(writer, value, format),
+            None => CompactFormatter.write_f64(writer, value),
+        }
+    }
+
+    #[inline]
+    fn write_f32<W: ?Sized + Write>(&mut self, writer: &mut W, value: f32) -> io::Result<()> {
+        match self.float_format {
+            Some(format) => write_float(writer, value as f64, format),
+            None => CompactFormatter.write_f32(writer, value),
+        }
+    }
+
+    #[inline]
+    fn begin_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.enter()?;
+        writer.write_all(b"[")
+    }
+
+    #[inline]
+    fn end_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.depth -= 1;
+        writer.write_all(b"]")
+    }
+
+    #[inline]
+    fn begin_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.enter()?;
+        writer.write_all(b"{")
+    }
+
+    #[inline]
+    fn end_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.depth -= 1;
+        writer.write_all(b"}")
+    }
+}
+
+/// Writes a finite `value` in `format` (non-finite values are the caller's problem).
+pub(crate) fn write_float<W: ?Sized + Write>(writer: &mut W, value: f64, format: FloatFormat) -> io::Result<()> {
+    match format {
+        FloatFormat::Shortest => writer.write_all(ryu::Buffer::new().format_finite(value).as_bytes()),
+        FloatFormat::Fixed(places) => write!(writer, "{:.*}", places as usize, value),
+        FloatFormat::Exponential(places) => write!(writer, "{:.*e}", places as usize, value),
+    }
+}
+
+/// Deep-copies `value` into an owned `serde_json::Value` that outlives the input
+/// and the arena: numbers and numeric timestamps become JSON numbers (non-finite
+/// floats `null`), strings, dates and times JSON strings. Same as
+/// `FdonValue::to_json_ordered`, and like it does not recurse.
+#[inline]
+pub fn to_json_value(value: &FdonValue<'_, '_>) -> Value {
+    value.to_json_ordered()
+}
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Deep-copies the value into a `serde_json::Value`, inserting object keys in
+    /// the object's order (document order for a parsed value).
+    ///
+    /// Enable this crate's `preserve_order` feature (which turns on serde_json's) so
+    /// `serde_json::Map` keeps that order; without it, serde_json sorts keys.
+    ///
+    /// Does not recurse, so arbitrarily deep trees cannot overflow the stack while
+    /// converting. Dropping a very deep `serde_json::Value` still recurses, though.
+    pub fn to_json_ordered(&self) -> Value {
+        let mut stack: Vec<JsonFrame<'_, 'a, 'bump>> = Vec::new();
+        let mut next = self;
+        loop {
+            // Container mở ra thành frame mới; scalar thành Value ngay
+            let mut value = match next {
+                FdonValue::Array(arr) => {
+                    stack.push(JsonFrame::Array(arr.iter(), Vec::with_capacity(arr.len())));
+                    None
+                }
+                FdonValue::Object(obj) => {
+                    stack.push(JsonFrame::Object(obj.iter(), Map::with_capacity(obj.len()), ""));
+                    None
+                }
+                scalar => Some(scalar_to_json(scalar)),
+            };
+
+            // Gắn value vào frame trên đỉnh; frame nào hết phần tử thì đóng lại thành value
+            loop {
+                let Some(frame) = stack.last_mut() else {
+                    return value.expect("closing the root frame yields a value");
+                };
+                let child = match frame {
+                    JsonFrame::Array(iter, items) => {
+                        if let Some(v) = value.take() {
+                            items.push(v);
+                        }
+                        iter.next()
+                    }
+                    JsonFrame::Object(iter, map, key) => {
+                        if let Some(v) = value.take() {
+                            map.insert((*key).to_string(), v);
+                        }
+                        iter.next().map(|(k, v)| {
+                            *key = k;
+                            v
+                        })
+                    }
+                };
+                if let Some(child) = child {
+                    next = child;
+                    break;
+                }
+                value = stack.pop().map(|frame| match frame {
+                    JsonFrame::Array(_, items) => Value::Array(items),
+                    JsonFrame::Object(_, map, _) => Value::Object(map),
+                });
+            }
+        }
+    }
+}
+
+// Một container đang chuyển dở: phần tử còn lại, kết quả đã có (và key đang chờ value)
+enum JsonFrame<'v, 'a, 'bump> {
+    Array(std::slice::Iter<'v, FdonValue<'a, 'bump>>, Vec<Value>),
+    Object(crate::object::Iter<'v, 'a, 'bump>, Map<String, Value>, &'bump str),
+}
+
+#[inline]
+fn scalar_to_json(value: &FdonValue<'_, '_>) -> Value {
+    match value {
+        FdonValue::Null => Value::Null,
+        FdonValue::Bool(b) => Value::Bool(*b),
+        FdonValue::Number(n) | FdonValue::Timestamp(n) => number_to_json(n),
+        FdonValue::RawString(s) | FdonValue::Date(s) | FdonValue::Time(s) => Value::String((*s).to_string()),
+        FdonValue::EscapedString(s) => Value::String(s.as_str().to_string()),
+        FdonValue::Array(_) | FdonValue::Object(_) => unreachable!("containers are handled by the caller"),
+    }
+}
+
+// NaN/Infinity không có trong JSON -> null (giống serde_json khi serialize f64)
+#[inline]
+fn number_to_json(n: &FdonNumber) -> Value {
+    match n {
+        FdonNumber::Integer(i) => Value::Number((*i).into()),
+        FdonNumber::UInt(u) => Value::Number((*u).into()),
+        FdonNumber::Float(f) => Number::from_f64(*f).map(Value::Number).unwrap_or(Value::Null),
+    }
+}
@@ -0,0 +1,127 @@
+// --- Object lười: chỉ quét cấu trúc, parse value khi được truy cập lần đầu ---
+
+use std::cell::OnceCell;
+use std::collections::HashMap;
+
+use ahash::RandomState as AHasher;
+use bumpalo::Bump;
+
+use crate::{check_delimiter, directive, Container, FdonError, FdonParser, FdonValue, ParseOptions};
+
+/// A root object whose values are parsed on first access (see `parse_lazy_object`).
+pub struct LazyObject<'a, 'bump> {
+    input: &'a str,
+    arena: &'bump Bump,
+    options: ParseOptions,
+    // Theo thứ tự tài liệu (lần xuất hiện đầu tiên của mỗi key)
+    entries: Vec<LazyEntry<'a, 'bump>>,
+    by_key: HashMap<&'bump str, usize, AHasher>,
+}
+
+struct LazyEntry<'a, 'bump> {
+    key: &'bump str,
+    // Offset của type tag; key trùng -> value cuối cùng thắng, như parse
+    start: usize,
+    value: OnceCell<FdonValue<'a, 'bump>>,
+}
+
+/// Scans the root object of `input` without building its values: each key is
+/// recorded with the offset of its value, which is parsed (into `arena`) and cached
+/// the first time `LazyObject::get` asks for it. Unread subtrees are only skipped.
+///
+/// The scan checks structure like `FdonParser::skip_value`, so errors it cannot
+/// see there (a malformed number, a repeated key in a nested object with
+/// `reject_duplicate_keys`) are reported by `get` for that value instead.
+/// A root that is not `O{...}` is `FdonError::Expected`.
+pub fn parse_lazy_object<'a, 'bump>(
+    input: &'a str,
+    arena: &'bump Bump,
+    options: ParseOptions,
+) -> Result<LazyObject<'a, 'bump>, FdonError> {
+    let mut parser = FdonParser::with_options(input, arena, options);
+    let mut lazy = LazyObject { input, arena, options, entries: Vec::new(), by_key: HashMap::default() };
+    scan(&mut parser, &mut lazy).map_err(|e| parser.decorate(e))?;
+    Ok(lazy)
+}
+
+fn scan<'a, 'bump>(parser: &mut FdonParser<'a, 'bump>, lazy: &mut LazyObject<'a, 'bump>) -> Result<(), FdonError> {
+    check_delimiter(lazy.options.string_delimiter)?;
+    if lazy.options.allow_version_directive {
+        parser.index = directive::check_directive(parser.data)?;
+    }
+    parser.skip_whitespace();
+    match parser.peek() {
+        Some(b'O') => {}
+        Some(b'{' | b'[' | b'"') => return Err(FdonError::LooksLikeJson { pos: parser.index }),
+        found => return Err(FdonError::Expected { expected: b'O', found, pos: parser.index }),
+    }
+    parser.enter(parser.index)?;
+    parser.advance();
+    parser.skip_whitespace();
+    parser.consume(b'{')?;
+    parser.skip_whitespace();
+    while parser.peek() != Some(b'}') {
+        let key_pos = parser.index;
+        let key = parser.parse_key()?;
+        parser.consume(b':')?;
+        if lazy.options.reject_duplicate_keys && lazy.by_key.contains_key(key) {
+            return Err(FdonError::DuplicateKey { key: key.to_string(), pos: key_pos });
+        }
+        parser.skip_whitespace();
+        let start = parser.index;
+        parser.skip_value()?;
+        match lazy.by_key.get(key) {
+            Some(&i) => lazy.entries[i].start = start,
+            None => {
+                lazy.by_key.insert(key, lazy.entries.len());
+                lazy.entries.push(LazyEntry { key, start, value: OnceCell::new() });
+            }
+        }
+        parser.skip_separator(b'}', Container::Object)?;
+    }
+    parser.advance();
+    parser.leave();
+    parser.skip_whitespace();
+    if parser.index != parser.data.len() {
+        return Err(FdonError::ExtraData { pos: parser.index });
+    }
+    Ok(())
+}
+
+impl<'a, 'bump> LazyObject<'a, 'bump> {
+    /// Value of `key`, parsed now if this is the first access (later calls return
+    /// the cached value). `Ok(None)` if the object has no such key.
+    pub fn get(&self, key: &str) -> Result<Option<&FdonValue<'a, 'bump>>, FdonError> {
+        let Some(&i) = self.by_key.get(key) else { return Ok(None) };
+        let entry = &self.entries[i];
+        if let Some(value) = entry.value.get() {
+            return Ok(Some(value));
+        }
+        let mut parser = FdonParser::with_options(self.input, self.arena, self.options);
+        parser.index = entry.start;
+        // Value nằm trong object gốc (độ sâu 1)
+        parser.depth = 1;
+        let value = parser.parse_value().map_err(|e| parser.decorate(e))?;
+        Ok(Some(entry.value.get_or_init(|| value)))
+    }
+
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.by_key.contains_key(key)
+    }
+
+    /// Keys in document order (a repeated key once, at its first position).
+    pub fn keys(&self) -> impl Iterator<Item = &'bump str> + '_ {
+        self.entries.iter().map(|e| e.key)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -1,5 +1,6 @@
 use serde::Serialize;
 use memchr::{memchr, memchr2, memchr3};
+use std::borrow::Cow;
 
 // --- TỐI ƯU HÓA "ALL-IN" ---
 use bumpalo::{
@@ -7,22 +8,91 @@ use bumpalo::{
     collections::Vec as BumpVec,
     collections::String as BumpString, // 1. Dùng String của Bumpalo (Arena)
 };
-use hashbrown::HashMap as BumpHashMap;
-use ahash::RandomState as AHasher;
 // --- KẾT THÚC KẾ HOẠCH ---
 
+mod build;
+mod de;
+mod directive;
+mod error;
+mod events;
+mod fdon_str;
+mod files;
+mod incremental;
+mod json;
+mod lazy;
+mod lint;
+mod object;
+mod owned;
+mod reader;
+mod serialize;
+mod stream;
+mod tagged;
+mod temporal;
+mod trivia;
+mod validate;
+mod value;
+mod warning;
+pub use build::{array_from_iter, build_and_serialize, object_from_iter, FdonArrayBuilder, FdonObjectBuilder};
+pub use de::{from_fdon_str, from_fdon_str_with_options};
+pub use directive::{document_version, FDON_VERSION};
+pub use error::{line_col_at, render_error, Container, ErrorVerbosity, FdonError};
+pub use events::{parse_events, parse_events_with_options, FdonVisitor};
+pub use fdon_str::FdonStr;
+pub use files::{from_reader_owned, parse_files};
+pub use incremental::{IncrementalParser, Progress};
+pub use json::{
+    fdon_to_json_writer, fdon_to_json_writer_with_options, to_json_value, FloatFormat, SerializeOptions, DEFAULT_MAX_DEPTH,
+};
+pub use lazy::{parse_lazy_object, LazyObject};
+pub use lint::{lint_duplicate_keys, DuplicateKey};
+pub use object::FdonObject;
+pub use owned::OwnedFdonValue;
+pub use reader::{CountingReader, FdonReader};
+pub use serialize::{
+    prettify_fdon, to_fdon_bytes, to_fdon_string, to_fdon_string_preserving, to_fdon_writer, to_fdon_writer_with_options,
+    PrettyOptions,
+};
+pub use stream::{parse_scalar_stream, parse_scalar_stream_with_options, ScalarStream};
+pub use tagged::{from_tagged_json, Tagged};
+pub use trivia::{collect_trivia, Trivia, TriviaKind, TriviaPlacement};
+pub use validate::{is_valid, validate, validate_reader, validate_reader_with_options, Validator};
+pub use value::{TreeMetrics, ValueKind};
+pub use warning::{parse_with_warnings, parse_with_warnings_with_options, FdonWarning, WarningKind};
+use validate::ContainerStack;
+use warning::float_loses_precision;
+
+// Tiện ích đo đạc Arena (chỉ dùng cho test/benchmark)
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
 // --- Cấu trúc dữ liệu ---
 
-/// Represents a numeric value (Integer or Float)
-#[derive(Debug, Serialize, PartialEq)]
+/// Represents a numeric value (Integer, unsigned Integer or Float)
+#[derive(Debug, Clone, Copy, Serialize)]
 #[serde(untagged)]
 pub enum FdonNumber {
     Integer(i64),
+    /// Non-negative integer above `i64::MAX` (or any integer written with the `u` suffix)
+    UInt(u64),
     Float(f64),
 }
 
+/// `Integer`/`UInt` print as plain digits; `Float` prints the shortest digits that
+/// round-trip (via `ryu`) and always keeps a `.` or exponent, so `Float(5.0)` is
+/// `5.0`, never `5`, and reads back as a float. Non-finite floats print as `NaN`,
+/// `inf` and `-inf`.
+impl std::fmt::Display for FdonNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FdonNumber::Integer(i) => write!(f, "{}", i),
+            FdonNumber::UInt(u) => write!(f, "{}", u),
+            FdonNumber::Float(x) => f.write_str(ryu::Buffer::new().format(*x)),
+        }
+    }
+}
+
 /// Represents any FDON value (Zero-Copy)
-#[derive(Debug, Serialize, PartialEq)]
+#[derive(Debug, Serialize)]
 #[serde(untagged)]
 pub enum FdonValue<'a, 'bump> {
     Null,
@@ -30,74 +100,349 @@ pub enum FdonValue<'a, 'bump> {
     Number(FdonNumber), // N...
     Timestamp(FdonNumber), // T... (dạng số)
     RawString(&'a str), // S"..."
-    EscapedString(BumpString<'bump>), // SE"..."
+    EscapedString(FdonStr<'a, 'bump>), // SE"..." (mượn input nếu không có escape)
     Date(&'a str), // D"..."
     Time(&'a str), // T"..." (dạng chuỗi)
-    Array(BumpVec<'bump, FdonValue<'a, 'bump>>),
-    Object(BumpHashMap<&'a str, FdonValue<'a, 'bump>, AHasher, &'bump Bump>),
+    Array(FdonArray<'a, 'bump>),
+    Object(FdonObject<'a, 'bump>),
 }
 
-/// Parse Error type
+/// Arena-allocated array payload of `FdonValue::Array`
+pub type FdonArray<'a, 'bump> = BumpVec<'bump, FdonValue<'a, 'bump>>;
+
+/// The pre-`FdonError` `(message, position)` error tuple, kept so code that
+/// destructures it still compiles: `FdonError` converts into it (`?` or `.into()`),
+/// with the same messages as before.
+#[deprecated(note = "use `FdonError`; `FdonParseError::from(err)` gives the old (message, position) tuple")]
 pub type FdonParseError = (String, usize);
-pub type ParseResult<'a, 'bump, T> = Result<T, FdonParseError>;
+pub type ParseResult<'a, 'bump, T> = Result<T, FdonError>;
 
-// --- Minify Function (Giữ nguyên) ---
+// --- Tùy chọn Parser ---
+
+/// Parser configuration. `ParseOptions::default()` is the classic strict FDON parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Byte that opens and closes `S`, `SE`, `D` and `T` strings (default `"`).
+    /// Use e.g. `` b'`' `` when embedding FDON in a `"`-heavy host format.
+    /// Must pass `is_valid_string_delimiter`; any other byte makes every parse fail
+    /// with `FdonError::InvalidDelimiter`.
+    pub string_delimiter: u8,
+    /// Accept a trailing type marker on numbers: `N5i` (integer), `N5u` (unsigned,
+    /// `UInt(5)`) and `N5f` (float, so `N5f` is `Float(5.0)`). A marker that contradicts
+    /// the literal (`N1.5i`, `N-1u`) is an error. Off by default.
+    pub allow_number_suffixes: bool,
+    /// How much detail errors carry (see `ErrorVerbosity`). `Verbose` only applies to
+    /// `FdonParser::parse`; `skip_value` and the streaming `Validator` never annotate.
+    pub error_verbosity: ErrorVerbosity,
+    /// Let `minify_fdon_with_options` strip comments outside strings: `# ...` and
+    /// `// ...` to end of line, and `/* ... */` blocks (which do not nest: the first
+    /// `*/` closes). Off by default, since `#` and `/` are legal in unquoted keys.
+    /// The parser itself never sees comments; always minify first.
+    pub allow_comments: bool,
+    /// Reject zero-length object keys (`O{:N1}`) with `FdonError::EmptyKey`.
+    /// Off by default, where such a key parses as `""`.
+    pub forbid_empty_keys: bool,
+    /// Also accept the JSON keywords `null`, `true` and `false` at value positions,
+    /// as `U`, `Btrue` and `Bfalse`. Type tags are all uppercase, so a lowercase
+    /// `n`/`t`/`f` can only start a keyword; anything else there is still
+    /// `UnknownTypeSpecifier`. Off by default.
+    pub allow_json_keywords: bool,
+    /// Report `WarningKind::BackslashInRawString` for `S"..."` strings containing `\`
+    /// (stored literally, unlike in `SE"..."`). Only has an effect through
+    /// `parse_with_warnings_with_options`; parsing itself is unchanged. Off by default.
+    pub warn_backslash_in_raw_string: bool,
+    /// Accept a leading `%fdon <N>` version directive (see `document_version`) and
+    /// skip it; a version above `FDON_VERSION` is `FdonError::UnsupportedVersion`.
+    /// Documents without a directive parse as usual. Off by default.
+    pub allow_version_directive: bool,
+    /// Reject escapes other than `\n`, `\t`, `\r`, `\"`, `\\`, `\u` and `\<delimiter>` in
+    /// `SE"..."` with `FdonError::InvalidEscape`, instead of keeping the escaped
+    /// character (`\q` -> `q`). Off by default.
+    pub strict_escapes: bool,
+    /// Longest key, number or string body (in bytes, escapes counted as written) the
+    /// parser scans for its terminator; a longer token is `FdonError::TokenTooLong`
+    /// as soon as the limit is passed, instead of after searching the rest of the
+    /// input. `None` (the default) means unlimited.
+    pub max_token_scan: Option<usize>,
+    /// Skip spaces, tabs and newlines between tokens: around the document, after
+    /// `O`/`A`, inside `{}`/`[]` around keys, values, `:` and `,`. Lets the parser read
+    /// pretty-printed FDON without `minify_fdon` first (see `parse_fdon_lenient`).
+    /// Strings are untouched, and whitespace inside a token (`N1 2`, `S "x"`) is still
+    /// an error. A key keeps inner spaces (`O{a b:N1}` has key `"a b"`, where minify
+    /// would give `"ab"`). The streaming `Validator` ignores this option. Off by default.
+    pub allow_whitespace: bool,
+    /// Reject an unescaped line feed inside `S`, `SE`, `D` and `T` strings with
+    /// `FdonError::LiteralNewline`, so every document fits on one line (write `\n`
+    /// in `SE"..."` instead). A `\r\n` break is caught by its `\n`. Off by default.
+    pub forbid_literal_newlines_in_strings: bool,
+    /// Deepest container nesting accepted (the root `O`/`A` is depth 1); one level
+    /// deeper is `FdonError::DepthLimitExceeded`. Default `DEFAULT_MAX_DEPTH` (128).
+    ///
+    /// `parse`, `skip_value` and `lint_duplicate_keys` keep open containers on the
+    /// heap, so `usize::MAX` (no limit) is safe there, and so are the FDON writers and
+    /// `to_json_value`.
+    /// `from_fdon_str` still recurses once per level, as do the serde-based JSON
+    /// writers (which stop at `SerializeOptions::max_depth`). Dropping a `FdonValue`
+    /// also recurses: release trees nested many thousands deep with `std::mem::forget`
+    /// (the arena owns their memory, nothing leaks).
+    pub max_depth: usize,
+    /// Read integers too large for `i64`/`u64` as (rounded) `Float`s instead of
+    /// failing with `FdonError::IntegerOverflow`, and floats beyond `f64` (`N1e999`)
+    /// as infinity instead of failing with `FdonError::InvalidFloat` (note that the
+    /// writers emit a non-finite float as `U`). Integers with a `u` suffix always
+    /// fail. Off by default.
+    pub overflow_to_float: bool,
+    /// Accept `_` between digits of `N`/`T` numbers as a visual separator
+    /// (`N1_000_000`, `N3.141_592`). Each `_` must sit between two digits; anywhere
+    /// else (leading, trailing, doubled, next to `.`/`e`/a sign) is
+    /// `FdonError::InvalidDigitSeparator`. Off by default, where `_` is invalid.
+    pub allow_digit_separators: bool,
+    /// Require `D"..."` to be a real `YYYY-MM-DD` date (leap years included) and
+    /// `T"..."` a real `HH:MM:SS` time, optionally with a 1-9 digit fraction
+    /// (`T"08:30:00.25"`); anything else is `FdonError::InvalidDate` /
+    /// `FdonError::InvalidTime`. The check is plain byte matching. Off by default,
+    /// where any string is accepted.
+    pub strict_date_time: bool,
+    /// Make the streaming `Validator` (and `validate_reader`, `IncrementalParser`,
+    /// `FdonReader`) check that keys and strings are UTF-8, failing with
+    /// `FdonError::InvalidUtf8` at the same byte as `parse_fdon_bytes`. Only those
+    /// bytes are checked, not the whole document. The parser needs no option: it
+    /// always checks input given as bytes, and `&str` input is UTF-8 already. Off by
+    /// default.
+    pub validate_utf8: bool,
+    /// Fail with `FdonError::DuplicateKey` when a key repeats within one object,
+    /// instead of keeping the last value. Applies to the tree parser,
+    /// `parse_lazy_object` and `from_fdon_str_with_options`; the streaming `Validator` and `parse_events` ignore it
+    /// (see `lint_duplicate_keys` to list every repeat). Off by default.
+    pub reject_duplicate_keys: bool,
+}
+
+impl Default for ParseOptions {
+    #[inline]
+    fn default() -> Self {
+        ParseOptions {
+            string_delimiter: b'"',
+            allow_number_suffixes: false,
+            error_verbosity: ErrorVerbosity::Normal,
+            allow_comments: false,
+            forbid_empty_keys: false,
+            allow_json_keywords: false,
+            warn_backslash_in_raw_string: false,
+            allow_version_directive: false,
+            strict_escapes: false,
+            max_token_scan: None,
+            allow_whitespace: false,
+            forbid_literal_newlines_in_strings: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+            overflow_to_float: false,
+            allow_digit_separators: false,
+            strict_date_time: false,
+            validate_utf8: false,
+            reject_duplicate_keys: false,
+        }
+    }
+}
+
+/// Whether `byte` can quote FDON strings (`ParseOptions::string_delimiter`,
+/// `SerializeOptions::string_delimiter`): ASCII punctuation other than the
+/// structural `{ } [ ] : ,`, the escape `\`, the number characters `+ - . _`
+/// and the comment and directive markers `# / %`. Letters, digits and whitespace
+/// would be read as tags, keys or numbers.
+pub fn is_valid_string_delimiter(byte: u8) -> bool {
+    byte.is_ascii_punctuation() && !b"{}[]:,\\+-._#/%".contains(&byte)
+}
+
+#[inline]
+pub(crate) fn check_delimiter(delim: u8) -> Result<(), FdonError> {
+    if is_valid_string_delimiter(delim) {
+        Ok(())
+    } else {
+        Err(FdonError::InvalidDelimiter { byte: delim })
+    }
+}
+
+// --- Minify Function ---
 
 #[inline(always)]
 pub fn minify_fdon(input: &str) -> String {
-    let input_bytes = input.as_bytes();
+    minify_fdon_with_options(input, &ParseOptions::default())
+}
+
+/// Same as `minify_fdon`, but honors `options.string_delimiter` when tracking strings
+/// and strips comments when `options.allow_comments` is set.
+pub fn minify_fdon_with_options(input: &str, options: &ParseOptions) -> String {
+    rewrite_outside_strings(input, options.string_delimiter, false, options.allow_comments)
+}
+
+/// Removes `#`, `//` and `/* */` comments that sit outside strings, leaving all
+/// other bytes (including whitespace) untouched. Returns `Cow::Borrowed` when the
+/// input has no comments.
+#[inline]
+pub fn strip_comments(input: &str) -> Cow<'_, str> {
+    strip_comments_with_options(input, &ParseOptions::default())
+}
+
+/// Same as `strip_comments`, but honors `options.string_delimiter` when tracking strings.
+pub fn strip_comments_with_options<'a>(input: &'a str, options: &ParseOptions) -> Cow<'a, str> {
+    // Không có '#' hay '/' thì chắc chắn không có comment
+    if memchr2(b'#', b'/', input.as_bytes()).is_none() {
+        return Cow::Borrowed(input);
+    }
+    let stripped = rewrite_outside_strings(input, options.string_delimiter, true, true);
+    // Giữ nguyên whitespace nên độ dài không đổi <=> không có comment nào bị xoá
+    if stripped.len() == input.len() {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(stripped)
+    }
+}
+
+/// Maps a byte offset in `minify_fdon_with_options(input, options)` (e.g. an error
+/// position from parsing the minified text) back to the offset of the same byte in
+/// `input`. Offsets at or past the end of the minified text map to `input.len()`.
+pub fn source_offset(input: &str, options: &ParseOptions, minified_pos: usize) -> usize {
+    let mut offsets = SourceOffsets(Vec::with_capacity(input.len()));
+    scan_outside_strings(input, options.string_delimiter, false, options.allow_comments, &mut offsets);
+    offsets.0.get(minified_pos).copied().unwrap_or(input.len())
+}
+
+// Dùng chung cho minify và strip_comments để hai bên theo đúng một quy tắc chuỗi/comment
+fn rewrite_outside_strings(input: &str, delim: u8, keep_whitespace: bool, strip_comments: bool) -> String {
     let mut minified = Vec::with_capacity(input.len());
-    let mut in_string_s = false; // Dùng cho S"..."
-    let mut in_string_se = false; // Dùng cho SE"..."
+    scan_outside_strings(input, delim, keep_whitespace, strip_comments, &mut minified);
+    unsafe { String::from_utf8_unchecked(minified) }
+}
+
+// Nơi nhận các byte được giữ lại (kèm vị trí trong input gốc)
+trait KeptBytes {
+    fn keep(&mut self, at: usize, byte: u8);
+}
+
+impl KeptBytes for Vec<u8> {
+    #[inline(always)]
+    fn keep(&mut self, _at: usize, byte: u8) {
+        self.push(byte);
+    }
+}
+
+// Vị trí trong input gốc của từng byte được giữ lại
+struct SourceOffsets(Vec<usize>);
+
+impl KeptBytes for SourceOffsets {
+    #[inline(always)]
+    fn keep(&mut self, at: usize, _byte: u8) {
+        self.0.push(at);
+    }
+}
+
+// Nơi ghi nội dung SE"..." đã unescape (Arena khi dựng cây, String dùng lại khi duyệt sự kiện,
+// Discard khi chỉ kiểm tra cú pháp)
+trait UnescapeBuf {
+    fn push(&mut self, ch: char);
+    fn push_str(&mut self, s: &str);
+}
+
+impl UnescapeBuf for BumpString<'_> {
+    #[inline(always)]
+    fn push(&mut self, ch: char) {
+        BumpString::push(self, ch);
+    }
+
+    #[inline(always)]
+    fn push_str(&mut self, s: &str) {
+        BumpString::push_str(self, s);
+    }
+}
+
+impl UnescapeBuf for String {
+    #[inline(always)]
+    fn push(&mut self, ch: char) {
+        String::push(self, ch);
+    }
+
+    #[inline(always)]
+    fn push_str(&mut self, s: &str) {
+        String::push_str(self, s);
+    }
+}
+
+// Bỏ nội dung đã unescape (key SE"..." trong skip_value)
+struct Discard;
+
+impl UnescapeBuf for Discard {
+    #[inline(always)]
+    fn push(&mut self, _ch: char) {}
+
+    #[inline(always)]
+    fn push_str(&mut self, _s: &str) {}
+}
+
+fn scan_outside_strings<K: KeptBytes>(input: &str, delim: u8, keep_whitespace: bool, strip_comments: bool, minified: &mut K) {
+    let input_bytes = input.as_bytes();
+    // Trong S"..." / D"..." / T"...: mọi byte được giữ nguyên cho tới delimiter đóng
+    let mut in_raw_string = false;
+    // Vừa gặp tag S/D/T của một value: delimiter kế tiếp (bỏ qua whitespace) mở chuỗi raw
+    let mut after_raw_tag = false;
+    // Đang ở vị trí của một value hoặc key (đầu tài liệu, sau ':' '[' ',' '{'): chỉ ở
+    // đây tag (hay dấu nháy của key "a:b") mới có thể mở chuỗi, còn lại (key USER,
+    // chuỗi S"SE ...") chỉ là byte thường
+    let mut at_value = true;
+    // Chỉ thị `%fdon N` ở đầu không phải value: value gốc vẫn bắt đầu ngay sau nó
+    let directive_len = directive::read_directive(input_bytes).ok().flatten().map_or(0, |d| d.len);
 
     let mut i = 0;
     while i < input_bytes.len() {
         let byte = input_bytes[i];
-        
-        // Logic cho S"..." (Raw String)
-        if byte == b'"' && !in_string_se {
-             // Kiểm tra xem có phải là S" (start) hay D" hoặc T"
-            if !in_string_s && i > 0 {
-                let prefix = input_bytes[i-1];
-                if prefix == b'S' || prefix == b'D' || prefix == b'T' {
-                     in_string_s = true;
-                }
-            } else {
-                 in_string_s = false;
-            }
-            minified.push(byte);
+
+        // Nội dung S"..." (Raw String): copy y hệt, chỉ delimiter mới kết thúc
+        if in_raw_string {
+            minified.keep(i, byte);
+            in_raw_string = byte != delim;
+            i += 1;
+            continue;
+        }
+
+        // Mở S"..." / D"..." / T"..." hoặc key "..."
+        if byte == delim && (after_raw_tag || at_value) {
+            after_raw_tag = false;
+            at_value = false;
+            in_raw_string = true;
+            minified.keep(i, byte);
             i += 1;
             continue;
         }
 
         // Logic cho SE"..." (Escaped String)
-        if byte == b'S' && i + 1 < input_bytes.len() && input_bytes[i+1] == b'E' {
-             minified.push(b'S');
-             minified.push(b'E');
+        if at_value && byte == b'S' && i + 1 < input_bytes.len() && input_bytes[i+1] == b'E' {
+             at_value = false;
+             after_raw_tag = false;
+             minified.keep(i, b'S');
+             minified.keep(i + 1, b'E');
              i += 2;
              
              // Tìm " mở đầu
              while i < input_bytes.len() && (input_bytes[i] == b' ' || input_bytes[i] == b'\t' || input_bytes[i] == b'\n' || input_bytes[i] == b'\r') {
+                 if keep_whitespace {
+                     minified.keep(i, input_bytes[i]);
+                 }
                  i += 1;
              }
-             if i < input_bytes.len() && input_bytes[i] == b'"' {
-                 minified.push(b'"');
+             if i < input_bytes.len() && input_bytes[i] == delim {
+                 minified.keep(i, delim);
                  i += 1;
-                 in_string_se = true;
                  
                  // Copy y hệt cho đến khi gặp " đóng (không bị escape)
                  while i < input_bytes.len() {
                      let se_byte = input_bytes[i];
-                     minified.push(se_byte);
+                     minified.keep(i, se_byte);
                      i += 1;
                      
                      if se_byte == b'\\' && i < input_bytes.len() {
                          // Nếu là escape (\\ hoặc \") thì copy cả ký tự sau
-                         minified.push(input_bytes[i]);
+                         minified.keep(i, input_bytes[i]);
                          i += 1;
-                     } else if se_byte == b'"' {
+                     } else if se_byte == delim {
                          // Dấu " không bị escape -> kết thúc SE
-                         in_string_se = false;
                          break;
                      }
                  }
@@ -105,40 +450,122 @@ pub fn minify_fdon(input: &str) -> String {
              continue;
         }
 
-        // Bỏ qua whitespace nếu không ở trong chuỗi nào cả
-        if (byte == b' ' || byte == b'\n' || byte == b'\r' || byte == b'\t') && !in_string_s && !in_string_se {
+        // Bỏ qua comment (nếu được bật); chuỗi đã được xử lý trọn ở các nhánh trên
+        if strip_comments
+            && let Some(len) = comment_len(&input_bytes[i..])
+        {
+            i += len;
+            continue;
+        }
+
+        let is_whitespace = matches!(byte, b' ' | b'\n' | b'\r' | b'\t');
+
+        // Bỏ qua whitespace ngoài chuỗi
+        if !keep_whitespace && is_whitespace {
             i += 1;
             continue;
         }
 
         // Giữ lại các ký tự khác
-        minified.push(byte);
+        if !is_whitespace && i >= directive_len {
+            after_raw_tag = at_value && matches!(byte, b'S' | b'D' | b'T');
+            at_value = matches!(byte, b':' | b'[' | b',' | b'{');
+        }
+        minified.keep(i, byte);
         i += 1;
     }
-    
-    unsafe { String::from_utf8_unchecked(minified) }
 }
 
+/// Length of the comment starting at `bytes[0]` (`#`, `//` or `/*`), or `None` if
+/// none starts there. Line comments stop before the `\n`; an unterminated block
+/// comment runs to the end of the input.
+#[inline]
+pub(crate) fn comment_len(bytes: &[u8]) -> Option<usize> {
+    match bytes {
+        [b'#', ..] | [b'/', b'/', ..] => Some(memchr(b'\n', bytes).unwrap_or(bytes.len())),
+        [b'/', b'*', rest @ ..] => Some(
+            memchr::memmem::find(rest, b"*/").map_or(bytes.len(), |end| end + 4),
+        ),
+        _ => None,
+    }
+}
 
 // --- Parser ---
 
+// Số byte đầu của key được quét tay trước khi chuyển sang memchr
+const SHORT_KEY_SCAN: usize = 16;
+// Tương tự cho số (N.../T...) trước khi chuyển sang memchr3
+const SHORT_NUMBER_SCAN: usize = 8;
+
+// Container đang dựng dở trong parse_value
+enum Frame<'a, 'bump> {
+    // `key` (tại `key_pos`) đang chờ value
+    Object { obj: FdonObject<'a, 'bump>, key: &'bump str, key_pos: usize },
+    Array(FdonArray<'a, 'bump>),
+}
+
 pub struct FdonParser<'a, 'bump> {
     data: &'a [u8],
     index: usize,
     arena: &'bump Bump, 
+    options: ParseOptions,
+    // Chỉ Some(..) khi gọi qua parse_with_warnings
+    warnings: Option<Vec<FdonWarning>>,
+    // Số container đang mở (so với options.max_depth)
+    depth: usize,
+    // Input từ from_bytes: key/chuỗi phải được kiểm tra UTF-8 trước khi thành &str
+    check_utf8: bool,
 }
 
 impl<'a, 'bump> FdonParser<'a, 'bump> {
     #[inline(always)]
     pub fn new(input: &'a str, arena: &'bump Bump) -> Self {
+        Self::with_options(input, arena, ParseOptions::default())
+    }
+
+    #[inline(always)]
+    pub fn with_options(input: &'a str, arena: &'bump Bump, options: ParseOptions) -> Self {
         FdonParser {
             data: input.as_bytes(),
             index: 0,
             arena,
+            options,
+            warnings: None,
+            depth: 0,
+            check_utf8: false,
         }
     }
 
+    /// Parser over raw bytes, with no up-front UTF-8 check of the whole buffer:
+    /// only the bytes of keys and strings are checked, as they are read, and
+    /// invalid ones are `FdonError::InvalidUtf8`.
+    #[inline(always)]
+    pub fn from_bytes(input: &'a [u8], arena: &'bump Bump) -> Self {
+        Self::from_bytes_with_options(input, arena, ParseOptions::default())
+    }
+
+    #[inline(always)]
+    pub fn from_bytes_with_options(input: &'a [u8], arena: &'bump Bump, options: ParseOptions) -> Self {
+        FdonParser { data: input, check_utf8: true, ..Self::with_options("", arena, options) }
+    }
+
+    /// Default options with `max_depth` set to `max_depth`.
+    #[inline(always)]
+    pub fn with_max_depth(input: &'a str, arena: &'bump Bump, max_depth: usize) -> Self {
+        Self::with_options(input, arena, ParseOptions { max_depth, ..ParseOptions::default() })
+    }
+
     // --- Helpers (Không đổi) ---
+
+    // Byte của key/chuỗi (bắt đầu tại `pos`) thành &str; chỉ kiểm tra khi input là &[u8]
+    #[inline(always)]
+    fn to_str(&self, bytes: &'a [u8], pos: usize) -> ParseResult<'a, 'bump, &'a str> {
+        if self.check_utf8 {
+            std::str::from_utf8(bytes).map_err(|e| FdonError::InvalidUtf8 { pos: pos + e.valid_up_to() })
+        } else {
+            Ok(unsafe { std::str::from_utf8_unchecked(bytes) })
+        }
+    }
     #[inline(always)]
     fn peek(&self) -> Option<u8> {
         self.data.get(self.index).copied()
@@ -155,37 +582,168 @@ impl<'a, 'bump> FdonParser<'a, 'bump> {
             self.advance();
             Ok(())
         } else {
-            let found = self.peek().map(|c| c as char).map(|c| c.to_string()).unwrap_or_else(|| "EOF".to_string());
-            Err((
-                format!("Expected '{}' but found '{}'", char as char, found),
-                self.index,
-            ))
+            Err(FdonError::Expected {
+                expected: char,
+                found: self.peek(),
+                pos: self.index,
+            })
+        }
+    }
+
+    // Mở một container có tag tại `pos`; lỗi nếu vượt max_depth
+    #[inline(always)]
+    fn enter(&mut self, pos: usize) -> ParseResult<'a, 'bump, ()> {
+        self.depth += 1;
+        if self.depth > self.options.max_depth {
+            return Err(FdonError::DepthLimitExceeded { pos });
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    // Bỏ qua whitespace giữa các token (chỉ khi allow_whitespace)
+    #[inline(always)]
+    fn skip_whitespace(&mut self) {
+        if self.options.allow_whitespace {
+            while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+                self.advance();
+            }
         }
     }
 
     // --- Parse Logic ---
     #[inline(always)]
     pub fn parse(&mut self) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
-        let value = self.parse_value()?;
+        check_delimiter(self.options.string_delimiter)?;
+        if self.options.allow_version_directive && self.index == 0 {
+            self.index = directive::check_directive(self.data).map_err(|e| self.decorate(e))?;
+        }
+        self.skip_whitespace();
+        // Người dùng quen JSON hay thử {"a":1} -> báo lỗi dễ hiểu thay vì "Unknown data type"
+        if matches!(self.peek(), Some(b'{' | b'[' | b'"')) {
+            return Err(self.decorate(FdonError::LooksLikeJson { pos: self.index }));
+        }
+        let value = self.parse_value().map_err(|e| self.decorate(e))?;
+        self.skip_whitespace();
         if self.index != self.data.len() {
-            Err((
-                "Extra data detected at end of file".to_string(),
-                self.index,
-            ))
+            Err(self.decorate(FdonError::ExtraData { pos: self.index }))
         } else {
             Ok(value)
         }
     }
 
+    // Áp dụng ErrorVerbosity::Verbose cho lỗi trả ra ngoài
     #[inline(always)]
+    fn decorate(&self, err: FdonError) -> FdonError {
+        if self.options.error_verbosity == ErrorVerbosity::Verbose {
+            err.annotate(self.data, self.options.string_delimiter)
+        } else {
+            err
+        }
+    }
+
+    // --- Parse Value (không đệ quy: container đang dựng nằm trên `stack`) ---
+    // Độ sâu chỉ bị giới hạn bởi heap (và max_depth), không phải call stack
     fn parse_value(&mut self) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
-        let type_char = self.peek().ok_or(("Unexpected EOF".to_string(), self.index))?;
-        self.advance(); 
+        let mut stack: Vec<Frame<'a, 'bump>> = Vec::new();
+        let result = self.parse_value_on(&mut stack);
+        // Khi lỗi: phần đã dựng thuộc về Arena, bỏ qua Drop (đệ quy theo độ sâu)
+        stack.drain(..).for_each(std::mem::forget);
+        result
+    }
 
+    #[inline(always)]
+    fn parse_value_on(&mut self, stack: &mut Vec<Frame<'a, 'bump>>) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
+        'value: loop {
+            self.skip_whitespace();
+            let type_char = self.peek().ok_or(FdonError::UnexpectedEof { pos: self.index })?;
+            self.advance();
+
+            let mut value = match type_char {
+                b'O' => {
+                    self.enter(self.index - 1)?;
+                    let obj = FdonObject::new_in(self.arena);
+                    self.skip_whitespace();
+                    self.consume(b'{')?;
+                    self.skip_whitespace();
+                    if self.peek() != Some(b'}') {
+                        let key_pos = self.index;
+                        let key = self.parse_key()?;
+                        self.consume(b':')?;
+                        stack.push(Frame::Object { obj, key, key_pos });
+                        continue 'value;
+                    }
+                    self.advance();
+                    self.leave();
+                    FdonValue::Object(obj)
+                }
+                b'A' => {
+                    self.enter(self.index - 1)?;
+                    let arr = BumpVec::new_in(self.arena);
+                    self.skip_whitespace();
+                    self.consume(b'[')?;
+                    self.skip_whitespace();
+                    if self.peek() != Some(b']') {
+                        stack.push(Frame::Array(arr));
+                        continue 'value;
+                    }
+                    self.advance();
+                    self.leave();
+                    FdonValue::Array(arr)
+                }
+                _ => self.parse_scalar(type_char)?,
+            };
+
+            // Gắn value vào container trên đỉnh stack; container nào đóng xong
+            // lại thành value cho tầng bên dưới
+            loop {
+                match stack.last_mut() {
+                    None => return Ok(value),
+                    Some(Frame::Object { obj, key, key_pos }) => {
+                        if obj.insert(key, value).is_some()
+                            && let Some(warnings) = &mut self.warnings
+                        {
+                            warnings.push(FdonWarning { kind: WarningKind::DuplicateKey, pos: *key_pos });
+                        }
+                        self.skip_separator(b'}', Container::Object)?;
+                        if self.peek() != Some(b'}') {
+                            *key_pos = self.index;
+                            *key = self.parse_key()?;
+                            self.consume(b':')?;
+                            if self.options.reject_duplicate_keys && obj.contains_key(key) {
+                                return Err(FdonError::DuplicateKey { key: (*key).to_string(), pos: *key_pos });
+                            }
+                            continue 'value;
+                        }
+                    }
+                    Some(Frame::Array(arr)) => {
+                        arr.push(value);
+                        self.skip_separator(b']', Container::Array)?;
+                        if self.peek() != Some(b']') {
+                            continue 'value;
+                        }
+                    }
+                }
+                // Dấu đóng của container trên đỉnh
+                self.advance();
+                self.leave();
+                value = match stack.pop() {
+                    Some(Frame::Object { obj, .. }) => FdonValue::Object(obj),
+                    Some(Frame::Array(arr)) => FdonValue::Array(arr),
+                    None => unreachable!("container closed with an empty stack"),
+                };
+            }
+        }
+    }
+
+    // Mọi value không phải container (type tag đã được đọc)
+    #[inline(always)]
+    fn parse_scalar(&mut self, type_char: u8) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
         match type_char {
-            b'O' => self.parse_object(),
-            b'A' => self.parse_array(),
-            
             b'S' => {
                 // Check for SE"..." (Escaped String)
                 if self.peek() == Some(b'E') {
@@ -193,17 +751,45 @@ impl<'a, 'bump> FdonParser<'a, 'bump> {
                     self.parse_escaped_string()
                 } else {
                     // S"..." (Raw String)
-                    self.parse_raw_string(FdonValue::RawString)
+                    let value = self.parse_raw_string(FdonValue::RawString)?;
+                    if self.options.warn_backslash_in_raw_string
+                        && let (Some(warnings), FdonValue::RawString(raw)) = (&mut self.warnings, &value)
+                        && memchr(b'\\', raw.as_bytes()).is_some()
+                    {
+                        // vị trí của 'S'
+                        let pos = raw.as_ptr() as usize - self.data.as_ptr() as usize - 2;
+                        warnings.push(FdonWarning { kind: WarningKind::BackslashInRawString, pos });
+                    }
+                    Ok(value)
                 }
             }
             
-            b'D' => self.parse_raw_string(FdonValue::Date), // D"..."
+            b'D' => {
+                // D"..."
+                let tag_pos = self.index - 1;
+                let value = self.parse_raw_string(FdonValue::Date)?;
+                if self.options.strict_date_time
+                    && let FdonValue::Date(s) = value
+                    && !temporal::is_valid_date(s.as_bytes())
+                {
+                    return Err(FdonError::InvalidDate { pos: tag_pos });
+                }
+                Ok(value)
+            }
             
             b'T' => {
                 // T (Đa hình): Có thể là T"..." (String) hoặc T... (Number)
-                if self.peek() == Some(b'"') {
+                if self.peek() == Some(self.options.string_delimiter) {
                     // T"..." -> String path
-                    self.parse_raw_string(FdonValue::Time)
+                    let tag_pos = self.index - 1;
+                    let value = self.parse_raw_string(FdonValue::Time)?;
+                    if self.options.strict_date_time
+                        && let FdonValue::Time(s) = value
+                        && !temporal::is_valid_time(s.as_bytes())
+                    {
+                        return Err(FdonError::InvalidTime { pos: tag_pos });
+                    }
+                    Ok(value)
                 } else {
                     // T... -> Number path
                     self.parse_number_internal()
@@ -219,80 +805,109 @@ impl<'a, 'bump> FdonParser<'a, 'bump> {
 
             b'B' => self.parse_boolean(),
             b'U' => Ok(FdonValue::Null),
+
+            b'n' | b't' | b'f' if self.options.allow_json_keywords => {
+                Ok(self.parse_json_keyword(type_char)?.map_or(FdonValue::Null, FdonValue::Bool))
+            }
             
-            _ => Err((
-                format!("Unknown data type specifier '{}'", type_char as char),
-                self.index - 1,
-            )),
+            _ => Err(FdonError::UnknownTypeSpecifier {
+                byte: type_char,
+                pos: self.index - 1,
+            }),
         }
     }
 
-    // --- Parse Object (TỐI ƯU HÓA "ALL-IN") ---
-    fn parse_object(&mut self) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
-        let hasher = AHasher::new();
-        let mut obj = BumpHashMap::with_hasher_in(hasher, self.arena);
-        
-        self.consume(b'{')?;
-
-        while self.peek() != Some(b'}') {
-            let key = self.parse_key()?;
-            self.consume(b':')?;
-            let value = self.parse_value()?;
-            obj.insert(key, value);
+    // --- Parse Key ---
+    // Key của cây: key SE"..." có escape được unescape vào Arena, key còn lại mượn
+    // từ input (sống lâu hơn Arena, vì vậy 'a: 'bump)
+    #[inline(always)]
+    fn parse_key(&mut self) -> ParseResult<'a, 'bump, &'bump str>
+    where
+        'a: 'bump,
+    {
+        let mut unescaped = BumpString::new_in(self.arena);
+        Ok(match self.parse_key_into(&mut unescaped)? {
+            Some(key) => key,
+            None => unescaped.into_bump_str(),
+        })
+    }
 
-            if self.peek() == Some(b',') {
+    // Key mượn từ input -> Some; key SE"..." có escape -> None, nội dung nằm trong `unescaped`
+    #[inline(always)]
+    fn parse_key_into<B: UnescapeBuf>(&mut self, unescaped: &mut B) -> ParseResult<'a, 'bump, Option<&'a str>> {
+        let start = self.index;
+        // Key trong dấu nháy ("a:b", S"a:b" hoặc SE"a\"b:c"): kết thúc ở delimiter, không ở ':'
+        let delim = self.options.string_delimiter;
+        match self.peek() {
+            Some(b) if b == delim => return self.parse_quoted_key(start).map(Some),
+            Some(b'S') if self.data.get(start + 1) == Some(&delim) => {
                 self.advance();
-                if self.peek() == Some(b'}') {
-                    return Err(("Trailing comma detected in object".to_string(), self.index));
-                }
-            } else if self.peek() != Some(b'}') {
-                return Err(("Missing comma or '}' in object".to_string(), self.index));
+                return self.parse_quoted_key(start).map(Some);
+            }
+            Some(b'S') if self.data.get(start + 1) == Some(&b'E') && self.data.get(start + 2) == Some(&delim) => {
+                self.index += 2;
+                return self.parse_escaped_key(start, unescaped);
             }
+            _ => {}
         }
-        self.consume(b'}')?;
-        Ok(FdonValue::Object(obj))
-    }
+        let remaining_data = self.scan_window(start, start);
 
-    // --- Parse Key (Không đổi) ---
-    #[inline(always)]
-    fn parse_key(&mut self) -> ParseResult<'a, 'bump, &'a str> {
-        let start = self.index;
-        let remaining_data = &self.data[self.index..];
+        // Fast path: key thường rất ngắn -> quét tay vài byte đầu, tránh chi phí
+        // khởi tạo memchr; chỉ dùng memchr (SIMD) khi key dài hơn SHORT_KEY_SCAN.
+        let short = &remaining_data[..remaining_data.len().min(SHORT_KEY_SCAN)];
+        let colon = match short.iter().position(|&b| b == b':') {
+            Some(pos) => Some(pos),
+            None if remaining_data.len() > SHORT_KEY_SCAN => {
+                memchr(b':', &remaining_data[SHORT_KEY_SCAN..]).map(|pos| pos + SHORT_KEY_SCAN)
+            }
+            None => None,
+        };
 
-        match memchr(b':', remaining_data) {
+        match colon {
+            Some(0) if self.options.forbid_empty_keys => Err(FdonError::EmptyKey { pos: start }),
             Some(pos) => {
                 let end = self.index + pos;
-                let key_slice = &self.data[start..end];
+                let mut key_slice = &self.data[start..end];
                 self.index = end; 
-
-                unsafe {
-                    Ok(std::str::from_utf8_unchecked(key_slice))
+                // Whitespace trước ':' không thuộc về key
+                if self.options.allow_whitespace {
+                    key_slice = key_slice.trim_ascii_end();
                 }
+
+                self.to_str(key_slice, start).map(Some)
             }
-            None => Err(("EOF while reading key (':' not found)".to_string(), self.index)),
+            None if self.too_long(start) => Err(FdonError::TokenTooLong { pos: start }),
+            None => Err(FdonError::UnterminatedKey { pos: self.index }),
         }
     }
 
-    // --- Parse Array (Đã tối ưu với BumpVec) ---
-    fn parse_array(&mut self) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
-        let mut arr = BumpVec::new_in(self.arena);
-        
-        self.consume(b'[')?;
-
-        while self.peek() != Some(b']') {
-            arr.push(self.parse_value()?);
+    // Key `"..."` / `S"..."` bắt đầu tại `start` (con trỏ đang ở delimiter mở);
+    // nội dung giữ nguyên như S"...", dừng trước ':'
+    #[inline(never)]
+    fn parse_quoted_key(&mut self, start: usize) -> ParseResult<'a, 'bump, &'a str> {
+        let key = self.raw_string()?;
+        if key.is_empty() && self.options.forbid_empty_keys {
+            return Err(FdonError::EmptyKey { pos: start });
+        }
+        self.skip_whitespace();
+        Ok(key)
+    }
 
-            if self.peek() == Some(b',') {
-                self.advance();
-                if self.peek() == Some(b']') {
-                    return Err(("Trailing comma detected in array".to_string(), self.index));
-                }
-            } else if self.peek() != Some(b']') {
-                return Err(("Missing comma or ']' in array".to_string(), self.index));
-            }
+    // Key `SE"..."` bắt đầu tại `start` (con trỏ đang ở delimiter mở): escape như
+    // value SE"...", dừng trước ':'
+    #[inline(never)]
+    fn parse_escaped_key<B: UnescapeBuf>(
+        &mut self,
+        start: usize,
+        unescaped: &mut B,
+    ) -> ParseResult<'a, 'bump, Option<&'a str>> {
+        let key = self.unescape_string(unescaped)?;
+        // Có escape thì nội dung không thể rỗng
+        if key == Some("") && self.options.forbid_empty_keys {
+            return Err(FdonError::EmptyKey { pos: start });
         }
-        self.consume(b']')?;
-        Ok(FdonValue::Array(arr))
+        self.skip_whitespace();
+        Ok(key)
     }
 
     // --- Parse Raw String (S"...", D"...", T"...") ---
@@ -301,52 +916,81 @@ impl<'a, 'bump> FdonParser<'a, 'bump> {
         &mut self, 
         constructor: fn(&'a str) -> FdonValue<'a, 'bump>
     ) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
-        self.consume(b'"')?;
+        self.raw_string().map(constructor)
+    }
+
+    // Nội dung chuỗi raw (con trỏ đang ở delimiter mở), mượn thẳng từ input
+    #[inline(always)]
+    fn raw_string(&mut self) -> ParseResult<'a, 'bump, &'a str> {
+        let delim = self.options.string_delimiter;
+        self.consume(delim)?;
         let start = self.index;
-        let remaining_data = &self.data[self.index..];
+        let remaining_data = self.scan_window(start, start);
 
-        match memchr(b'"', remaining_data) {
+        match self.find_raw_end(remaining_data) {
+            Some(pos) if self.data[start + pos] != delim => Err(FdonError::LiteralNewline { pos: start + pos }),
             Some(pos) => {
                 let end = self.index + pos;
                 let val_slice = &self.data[start..end];
                 
                 self.index = end + 1; 
 
-                // SỬA LỖI: std.str:: -> std::str::
-                let val_str = unsafe { std::str::from_utf8_unchecked(val_slice) };
-                
-                Ok(constructor(val_str))
+                self.to_str(val_slice, start)
             }
-            None => Err(("EOF while reading string ('\"' not found)".to_string(), start)),
+            None if self.too_long(start) => Err(FdonError::TokenTooLong { pos: start }),
+            None => Err(FdonError::UnterminatedString { escaped: false, pos: start }),
         }
     }
     
     // --- Parse Escaped String (SE"...") ---
     fn parse_escaped_string(&mut self) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
-        self.consume(b'"')?;
-        
-        // Dùng String của Bumpalo để chứa kết quả unescape
+        // Dùng String của Bumpalo để chứa kết quả unescape (chưa cấp phát cho tới lần push đầu)
         let mut unescaped_str = BumpString::new_in(self.arena);
+        Ok(FdonValue::EscapedString(match self.unescape_string(&mut unescaped_str)? {
+            Some(borrowed) => FdonStr::Borrowed(borrowed),
+            None => FdonStr::Owned(unescaped_str),
+        }))
+    }
+
+    // Đọc SE"..." (sau 'SE'): không có escape -> Some(mượn từ input), ngược lại
+    // nội dung đã unescape được ghi vào `unescaped_str` và trả None
+    #[inline(always)]
+    fn unescape_string<B: UnescapeBuf>(&mut self, unescaped_str: &mut B) -> ParseResult<'a, 'bump, Option<&'a str>> {
+        let delim = self.options.string_delimiter;
+        // 'SE' phải đi liền với dấu mở chuỗi (ví dụ: SEfoo là lỗi, không phải key/giá trị khác)
+        if self.peek() != Some(delim) {
+            return Err(FdonError::ExpectedDelimiterAfterSE {
+                delimiter: delim,
+                pos: self.index,
+            });
+        }
+        self.advance();
         
+        let content_start = self.index;
         let mut start_chunk = self.index;
 
-        // Tối ưu: Dùng memchr2 để tìm \ hoặc " (kết thúc)
-        while let Some(pos) = memchr2(b'\\', b'"', &self.data[self.index..]) {
+        // Tối ưu: Dùng memchr2 để tìm \ hoặc delimiter (kết thúc)
+        while let Some(pos) = self.find_escape_or_end(self.scan_window(content_start, self.index)) {
             
             let found_char = self.data[self.index + pos];
             
-            if found_char == b'"' {
+            if found_char == delim {
                 // --- KẾT THÚC CHUỖI ---
                 let end = self.index + pos;
                 let chunk_slice = &self.data[start_chunk..end];
+                self.index = end + 1; // Bỏ qua "
+
+                // Không gặp escape nào -> mượn thẳng từ input, không copy
+                if start_chunk == content_start {
+                    return self.to_str(chunk_slice, start_chunk).map(Some);
+                }
                 
                 // Thêm chunk cuối cùng (nếu có)
                 if !chunk_slice.is_empty() {
-                    unescaped_str.push_str(unsafe { std::str::from_utf8_unchecked(chunk_slice) });
+                    unescaped_str.push_str(self.to_str(chunk_slice, start_chunk)?);
                 }
                 
-                self.index = end + 1; // Bỏ qua "
-                return Ok(FdonValue::EscapedString(unescaped_str));
+                return Ok(None);
             }
 
             if found_char == b'\\' {
@@ -356,7 +1000,7 @@ impl<'a, 'bump> FdonParser<'a, 'bump> {
                 let end_chunk = self.index + pos;
                 let chunk_slice = &self.data[start_chunk..end_chunk];
                 if !chunk_slice.is_empty() {
-                    unescaped_str.push_str(unsafe { std::str::from_utf8_unchecked(chunk_slice) });
+                    unescaped_str.push_str(self.to_str(chunk_slice, start_chunk)?);
                 }
                 
                 // 2. Bỏ qua dấu \
@@ -364,27 +1008,58 @@ impl<'a, 'bump> FdonParser<'a, 'bump> {
                 
                 // 3. Xử lý ký tự được escape
                 match self.peek() {
+                    Some(b'\n') if self.options.forbid_literal_newlines_in_strings => {
+                        return Err(FdonError::LiteralNewline { pos: self.index });
+                    }
                     Some(b'n') => unescaped_str.push('\n'),
                     Some(b't') => unescaped_str.push('\t'),
                     Some(b'r') => unescaped_str.push('\r'),
                     Some(b'"') => unescaped_str.push('\"'),
                     Some(b'\\') => unescaped_str.push('\\'),
+                    Some(other) if other == delim => unescaped_str.push(delim as char),
+                    Some(b'u') => {
+                        let (ch, len) = decode_unicode_escape(&self.data[end_chunk..])
+                            .map_err(|_| FdonError::InvalidUnicodeEscape { pos: end_chunk })?;
+                        unescaped_str.push(ch);
+                        self.index = end_chunk + len;
+                        start_chunk = self.index;
+                        continue;
+                    }
+                    Some(other) if self.options.strict_escapes => {
+                        return Err(FdonError::InvalidEscape { ch: other, pos: end_chunk });
+                    }
                     Some(other) => {
                         // Ký tự escape không hợp lệ, chỉ giữ lại ký tự đó
-                        // (ví dụ: \a -> a)
-                         unescaped_str.push(other as char);
+                        // (ví dụ: \a -> a, \é -> é: lấy trọn ký tự nhiều byte)
+                        let width = match other {
+                            0x00..=0x7F => 1,
+                            0xC0..=0xDF => 2,
+                            0xE0..=0xEF => 3,
+                            _ => 4,
+                        };
+                        let ch_end = (self.index + width).min(self.data.len());
+                        unescaped_str.push_str(self.to_str(&self.data[self.index..ch_end], self.index)?);
+                        self.index = ch_end;
+                        start_chunk = self.index;
+                        continue;
                     }
-                    None => return Err(("EOF after escape character '\\'".to_string(), self.index)),
+                    None => return Err(FdonError::EofAfterEscape { pos: self.index }),
                 }
                 
                 // 4. Advance và reset chunk
                 self.advance();
                 start_chunk = self.index;
+            } else {
+                // '\n' trần (chỉ khi forbid_literal_newlines_in_strings)
+                return Err(FdonError::LiteralNewline { pos: self.index + pos });
             }
         }
 
         // Nếu không tìm thấy " (lỗi EOF)
-        Err(("EOF while reading escaped string ('\"' not found)".to_string(), self.index))
+        if self.too_long(content_start) {
+            return Err(FdonError::TokenTooLong { pos: content_start });
+        }
+        Err(FdonError::UnterminatedString { escaped: true, pos: self.index })
     }
 
 
@@ -392,36 +1067,35 @@ impl<'a, 'bump> FdonParser<'a, 'bump> {
     #[inline(always)]
     fn parse_number_internal(&mut self) -> ParseResult<'a, 'bump, FdonNumber> {
         let start = self.index;
-        let remaining_data = &self.data[self.index..];
+        let remaining_data = self.scan_window(start, start);
 
-        let end;
-        match memchr3(b',', b'}', b']', remaining_data) {
-            Some(pos) => {
-                end = self.index + pos;
-                self.index = end; 
-            }
-            None => {
-                end = self.data.len();
-                self.index = end;
+        // Số thường rất ngắn (N1, N42) -> quét tay vài byte đầu; chỉ gọi memchr3
+        // (SIMD, có chi phí khởi tạo) khi số dài hơn SHORT_NUMBER_SCAN.
+        let short = &remaining_data[..remaining_data.len().min(SHORT_NUMBER_SCAN)];
+        let delim = match short.iter().position(|&b| matches!(b, b',' | b'}' | b']')) {
+            Some(pos) => Some(pos),
+            None if remaining_data.len() > SHORT_NUMBER_SCAN => {
+                memchr3(b',', b'}', b']', &remaining_data[SHORT_NUMBER_SCAN..])
+                    .map(|pos| pos + SHORT_NUMBER_SCAN)
             }
-        }
+            None => None,
+        };
+        let end = match delim {
+            Some(pos) => self.index + pos,
+            None if self.too_long(start) => return Err(FdonError::TokenTooLong { pos: start }),
+            None => self.data.len(),
+        };
+        let end = self.trim_number_end(start, end);
+        self.index = end;
 
-        let num_slice = &self.data[start..end];
-        if num_slice.is_empty() {
-            return Err(("Empty number value".to_string(), self.index));
-        }
-        
-        let is_float = memchr(b'.', num_slice).is_some();
-
-        if is_float {
-            let val: f64 = fast_float::parse(num_slice)
-                .map_err(|e| (format!("Invalid float format: {}", e), start))?;
-            Ok(FdonNumber::Float(val))
-        } else {
-            let val: i64 = atoi::atoi(num_slice)
-                .ok_or(("Invalid integer format or out of range".to_string(), start))?;
-            Ok(FdonNumber::Integer(val))
+        let num = parse_number_slice(&self.data[start..end], start, &self.options)?;
+        if let Some(warnings) = &mut self.warnings
+            && let FdonNumber::Float(f) = num
+            && float_loses_precision(&self.data[start..end], f)
+        {
+            warnings.push(FdonWarning { kind: WarningKind::LossyFloat, pos: start });
         }
+        Ok(num)
     }
 
     // --- Parse Boolean (Không đổi) ---
@@ -434,9 +1108,468 @@ impl<'a, 'bump> FdonParser<'a, 'bump> {
             self.index += 5;
             Ok(FdonValue::Bool(false))
         } else {
-            Err(("Invalid boolean value".to_string(), self.index))
+            Err(FdonError::InvalidBool { pos: self.index })
+        }
+    }
+
+    // --- JSON keyword (null / true / false), chỉ khi allow_json_keywords ---
+    // `first` đã được consume; trả về None cho null, Some(b) cho true/false
+    #[inline]
+    fn parse_json_keyword(&mut self, first: u8) -> Result<Option<bool>, FdonError> {
+        let (rest, value): (&[u8], _) = match first {
+            b'n' => (b"ull", None),
+            b't' => (b"rue", Some(true)),
+            _ => (b"alse", Some(false)),
+        };
+        if self.data[self.index..].starts_with(rest) {
+            self.index += rest.len();
+            Ok(value)
+        } else {
+            Err(FdonError::UnknownTypeSpecifier { byte: first, pos: self.index - 1 })
+        }
+    }
+
+    // --- Skip Value (bỏ qua một value mà không dựng nó) ---
+
+    /// Current byte offset of the parser in its input.
+    #[inline(always)]
+    pub fn position(&self) -> usize {
+        self.index
+    }
+
+    /// Advances past exactly one value (nested containers included) without building it
+    /// or touching the arena. Does not recurse, so nesting is only bounded by `max_depth`.
+    ///
+    /// Only structure is checked, with the same errors as `parse`: type tags,
+    /// `{}`/`[]` balance, commas, keys, string termination (escapes included),
+    /// booleans and `max_depth`. Value contents are not: number tokens are only
+    /// delimited, not converted (`N1x` is skipped silently where parsing it would
+    /// fail), and `strict_date_time`, `reject_duplicate_keys` and the UTF-8 check of
+    /// string values in input given as bytes are not applied.
+    pub fn skip_value(&mut self) -> ParseResult<'a, 'bump, ()> {
+        // Container đang mở trong lần skip này (không cấp phát tới 128 cấp)
+        let mut stack = ContainerStack::default();
+        loop {
+            self.skip_whitespace();
+            let type_char = self.peek().ok_or(FdonError::UnexpectedEof { pos: self.index })?;
+            self.advance();
+
+            if let b'O' | b'A' = type_char {
+                let (container, open, close) = match type_char {
+                    b'O' => (Container::Object, b'{', b'}'),
+                    _ => (Container::Array, b'[', b']'),
+                };
+                self.enter(self.index - 1)?;
+                self.skip_whitespace();
+                self.consume(open)?;
+                self.skip_whitespace();
+                if self.peek() != Some(close) {
+                    stack.push(container);
+                    if container == Container::Object {
+                        self.parse_key_into(&mut Discard)?;
+                        self.consume(b':')?;
+                    }
+                    continue;
+                }
+                self.advance();
+                self.leave();
+            } else {
+                self.skip_scalar(type_char)?;
+            }
+
+            // Một value đã xong: đóng các container vừa hết phần tử
+            loop {
+                let Some(container) = stack.last() else { return Ok(()) };
+                let close = match container {
+                    Container::Object => b'}',
+                    Container::Array => b']',
+                };
+                self.skip_separator(close, container)?;
+                if self.peek() != Some(close) {
+                    if container == Container::Object {
+                        self.parse_key_into(&mut Discard)?;
+                        self.consume(b':')?;
+                    }
+                    break;
+                }
+                self.advance();
+                self.leave();
+                stack.pop();
+            }
+        }
+    }
+
+    // Một value không phải container (tag `type_char` đã được đọc)
+    #[inline(always)]
+    fn skip_scalar(&mut self, type_char: u8) -> ParseResult<'a, 'bump, ()> {
+        match type_char {
+            b'S' if self.peek() == Some(b'E') => {
+                self.advance();
+                self.skip_escaped_string()
+            }
+            b'S' | b'D' => self.skip_raw_string(),
+            b'T' if self.peek() == Some(self.options.string_delimiter) => self.skip_raw_string(),
+            b'T' | b'N' => {
+                let start = self.index;
+                let len = match memchr3(b',', b'}', b']', self.scan_window(start, start)) {
+                    Some(len) => len,
+                    None if self.too_long(start) => return Err(FdonError::TokenTooLong { pos: start }),
+                    None => self.data.len() - start,
+                };
+                let len = self.trim_number_end(start, start + len) - start;
+                if len == 0 {
+                    return Err(FdonError::EmptyNumber { pos: self.index });
+                }
+                self.index += len;
+                Ok(())
+            }
+            b'B' => {
+                if self.data.get(self.index..self.index + 4) == Some(b"true") {
+                    self.index += 4;
+                } else if self.data.get(self.index..self.index + 5) == Some(b"false") {
+                    self.index += 5;
+                } else {
+                    return Err(FdonError::InvalidBool { pos: self.index });
+                }
+                Ok(())
+            }
+            b'U' => Ok(()),
+            b'n' | b't' | b'f' if self.options.allow_json_keywords => {
+                self.parse_json_keyword(type_char).map(|_| ())
+            }
+            _ => Err(FdonError::UnknownTypeSpecifier {
+                byte: type_char,
+                pos: self.index - 1,
+            }),
+        }
+    }
+
+    // Dấu ',' hoặc dấu đóng sau một phần tử (cùng luật với parse_value)
+    #[inline(always)]
+    fn skip_separator(&mut self, close: u8, container: Container) -> ParseResult<'a, 'bump, ()> {
+        self.skip_whitespace();
+        if self.peek() == Some(b',') {
+            self.advance();
+            self.skip_whitespace();
+            if self.peek() == Some(close) {
+                return Err(FdonError::TrailingComma { container, pos: self.index });
+            }
+        } else if self.peek() != Some(close) {
+            return Err(FdonError::MissingComma { container, pos: self.index });
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn skip_raw_string(&mut self) -> ParseResult<'a, 'bump, ()> {
+        let delim = self.options.string_delimiter;
+        self.consume(delim)?;
+        match self.find_raw_end(self.scan_window(self.index, self.index)) {
+            Some(pos) if self.data[self.index + pos] != delim => {
+                Err(FdonError::LiteralNewline { pos: self.index + pos })
+            }
+            Some(pos) => {
+                self.index += pos + 1;
+                Ok(())
+            }
+            None if self.too_long(self.index) => Err(FdonError::TokenTooLong { pos: self.index }),
+            None => Err(FdonError::UnterminatedString { escaped: false, pos: self.index }),
+        }
+    }
+
+    fn skip_escaped_string(&mut self) -> ParseResult<'a, 'bump, ()> {
+        let delim = self.options.string_delimiter;
+        if self.peek() != Some(delim) {
+            return Err(FdonError::ExpectedDelimiterAfterSE {
+                delimiter: delim,
+                pos: self.index,
+            });
+        }
+        self.advance();
+
+        let content_start = self.index;
+        while let Some(pos) = self.find_escape_or_end(self.scan_window(content_start, self.index)) {
+            let found = self.index + pos;
+            if self.data[found] == delim {
+                self.index = found + 1;
+                return Ok(());
+            }
+            if self.data[found] == b'\n' {
+                return Err(FdonError::LiteralNewline { pos: found });
+            }
+            // Bỏ qua '\' và ký tự được escape
+            if found + 1 >= self.data.len() {
+                return Err(FdonError::EofAfterEscape { pos: found + 1 });
+            }
+            let ch = self.data[found + 1];
+            if ch == b'\n' && self.options.forbid_literal_newlines_in_strings {
+                return Err(FdonError::LiteralNewline { pos: found + 1 });
+            }
+            if ch == delim {
+                self.index = found + 2;
+                continue;
+            }
+            if ch == b'u' {
+                let (_, len) = decode_unicode_escape(&self.data[found..])
+                    .map_err(|_| FdonError::InvalidUnicodeEscape { pos: found })?;
+                self.index = found + len;
+                continue;
+            }
+            if self.options.strict_escapes && !is_known_escape(ch, delim) {
+                return Err(FdonError::InvalidEscape { ch, pos: found });
+            }
+            self.index = found + 2;
+        }
+        if self.too_long(content_start) {
+            return Err(FdonError::TokenTooLong { pos: content_start });
+        }
+        Err(FdonError::UnterminatedString { escaped: true, pos: self.index })
+    }
+
+    // Dấu đóng của S"..."/D"..."/T"..." (hoặc '\n' trần khi forbid_literal_newlines_in_strings)
+    #[inline(always)]
+    fn find_raw_end(&self, haystack: &[u8]) -> Option<usize> {
+        let delim = self.options.string_delimiter;
+        if self.options.forbid_literal_newlines_in_strings {
+            memchr2(delim, b'\n', haystack)
+        } else {
+            memchr(delim, haystack)
         }
     }
+
+    // '\' hoặc dấu đóng trong SE"..." (hoặc '\n' trần khi forbid_literal_newlines_in_strings)
+    #[inline(always)]
+    fn find_escape_or_end(&self, haystack: &[u8]) -> Option<usize> {
+        let delim = self.options.string_delimiter;
+        if self.options.forbid_literal_newlines_in_strings {
+            memchr3(b'\\', delim, b'\n', haystack)
+        } else {
+            memchr2(b'\\', delim, haystack)
+        }
+    }
+
+    // Phần input còn được quét (từ `from`) của token bắt đầu tại `start`,
+    // bị cắt ở `max_token_scan` + 1 byte để thấy được dấu kết thúc ngay tại giới hạn
+    #[inline(always)]
+    fn scan_window(&self, start: usize, from: usize) -> &'a [u8] {
+        let data = self.data;
+        let end = match self.options.max_token_scan {
+            Some(max) => data.len().min(start.saturating_add(max).saturating_add(1)),
+            None => data.len(),
+        };
+        &data[from.min(end)..end]
+    }
+
+    // Số kết thúc ở whitespace đầu tiên (allow_whitespace), để `N1 2` báo lỗi thiếu ','
+    // thay vì atoi lặng lẽ đọc mỗi phần đầu
+    #[inline(always)]
+    fn trim_number_end(&self, start: usize, end: usize) -> usize {
+        if !self.options.allow_whitespace {
+            return end;
+        }
+        let token = &self.data[start..end];
+        token.iter().position(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r')).map_or(end, |ws| start + ws)
+    }
+
+    // Không thấy dấu kết thúc trong scan_window: token đã vượt max_token_scan?
+    #[inline(always)]
+    fn too_long(&self, start: usize) -> bool {
+        self.options.max_token_scan.is_some_and(|max| self.data.len() - start > max)
+    }
+}
+
+
+/// Escapes `SE"..."` understands (the byte after `\`); used by `strict_escapes`.
+#[inline]
+pub(crate) fn is_known_escape(ch: u8, delim: u8) -> bool {
+    matches!(ch, b'n' | b't' | b'r' | b'"' | b'\\' | b'u') || ch == delim
+}
+
+/// Why `decode_unicode_escape` rejected its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnicodeEscapeError {
+    /// Every byte so far is valid, but the escape is not finished.
+    Incomplete,
+    Invalid,
+}
+
+/// Decodes the `\uXXXX` (a high surrogate must be followed by `\uXXXX` with the low
+/// one) or `\u{X...}` (1 to 6 hex digits) escape at the start of `bytes`, which begins
+/// with `\u`. Returns the character and the escape's length in bytes.
+pub(crate) fn decode_unicode_escape(bytes: &[u8]) -> Result<(char, usize), UnicodeEscapeError> {
+    use UnicodeEscapeError::{Incomplete, Invalid};
+
+    // 4 chữ số hex tại `at`
+    let hex4 = |at: usize| -> Result<u32, UnicodeEscapeError> {
+        let mut value = 0;
+        for k in at..at + 4 {
+            let digit = (*bytes.get(k).ok_or(Incomplete)? as char).to_digit(16).ok_or(Invalid)?;
+            value = value * 16 + digit;
+        }
+        Ok(value)
+    };
+
+    if *bytes.get(2).ok_or(Incomplete)? == b'{' {
+        let mut value = 0u32;
+        for k in 3..=9 {
+            match *bytes.get(k).ok_or(Incomplete)? {
+                b'}' if k > 3 => return char::from_u32(value).map(|ch| (ch, k + 1)).ok_or(Invalid),
+                b if k < 9 => value = value * 16 + (b as char).to_digit(16).ok_or(Invalid)?,
+                _ => return Err(Invalid),
+            }
+        }
+        return Err(Invalid);
+    }
+
+    let high = hex4(2)?;
+    match high {
+        0xD800..=0xDBFF => {
+            // Cặp surrogate: phải có ngay \uDC00..\uDFFF phía sau
+            for (k, expected) in [(6, b'\\'), (7, b'u')] {
+                if *bytes.get(k).ok_or(Incomplete)? != expected {
+                    return Err(Invalid);
+                }
+            }
+            let low = hex4(8)?;
+            if !(0xDC00..=0xDFFF).contains(&low) {
+                return Err(Invalid);
+            }
+            let ch = char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)).ok_or(Invalid)?;
+            Ok((ch, 12))
+        }
+        _ => char::from_u32(high).map(|ch| (ch, 6)).ok_or(Invalid),
+    }
+}
+
+// --- Chuyển slice số -> FdonNumber (dùng chung cho Parser và Validator) ---
+
+/// Converts the bytes of a number token (without its `N`/`T` tag) into a `FdonNumber`.
+/// `start` is the token's offset, used for error positions.
+#[inline(always)]
+pub(crate) fn parse_number_slice(
+    num_slice: &[u8],
+    start: usize,
+    options: &ParseOptions,
+) -> Result<FdonNumber, FdonError> {
+    let end = start + num_slice.len();
+    let mut num_slice = num_slice;
+    if num_slice.is_empty() {
+        return Err(FdonError::EmptyNumber { pos: start });
+    }
+
+    // Hậu tố ép kiểu (N5i / N5u / N5f), chỉ khi được bật
+    let mut suffix = None;
+    if options.allow_number_suffixes
+        && let Some(&last @ (b'i' | b'u' | b'f')) = num_slice.last()
+    {
+        suffix = Some(last);
+        num_slice = &num_slice[..num_slice.len() - 1];
+        if num_slice.is_empty() {
+            return Err(FdonError::EmptyNumber { pos: start });
+        }
+    }
+    
+    // Có '.' hoặc số mũ (N1e10, N1.5E-3) -> float; fast_float nhận cả dấu '+' đầu
+    let is_float = memchr3(b'.', b'e', b'E', num_slice).is_some();
+
+    // 'i'/'u' ép kiểu số nguyên -> xung đột với literal dạng float; 'u' không nhận số âm
+    if let Some(s @ (b'i' | b'u')) = suffix
+        && (is_float || (s == b'u' && num_slice[0] == b'-'))
+    {
+        return Err(FdonError::InvalidNumberSuffix { suffix: s, pos: end - 1 });
+    }
+
+    // Dấu '_' phân cách chữ số (N1_000): kiểm tra vị trí rồi bỏ đi trước khi parse;
+    // `at` đổi offset trong slice đã bỏ '_' về vị trí trong input
+    let digits = num_slice;
+    let stripped: Vec<u8>;
+    if options.allow_digit_separators && memchr(b'_', num_slice).is_some() {
+        check_digit_separators(num_slice, start)?;
+        stripped = num_slice.iter().copied().filter(|&b| b != b'_').collect();
+        num_slice = &stripped;
+    }
+    let at = |k: usize| {
+        if num_slice.len() == digits.len() {
+            start + k
+        } else {
+            // Byte thứ k (không tính '_') nằm ở đâu trong slice gốc
+            let kept = digits.iter().enumerate().filter(|&(_, &b)| b != b'_');
+            start + kept.map(|(i, _)| i).nth(k).unwrap_or(digits.len())
+        }
+    };
+
+    // Terse: không cấp phát chuỗi mô tả
+    let detail = |msg: &dyn std::fmt::Display| {
+        if options.error_verbosity == ErrorVerbosity::Terse { String::new() } else { msg.to_string() }
+    };
+
+    if is_float || suffix == Some(b'f') {
+        let (val, used): (f64, usize) = fast_float::parse_partial(num_slice)
+            .map_err(|e| FdonError::InvalidFloat { detail: detail(&e), pos: start })?;
+        // Phải đọc hết slice: N1.2.3 là lỗi tại dấu '.' thứ hai, không phải 1.2
+        if used < num_slice.len() {
+            let byte = num_slice[used] as char;
+            return Err(FdonError::InvalidFloat {
+                detail: detail(&format_args!("unexpected `{}`", byte.escape_default())),
+                pos: at(used),
+            });
+        }
+        // N1e999 -> inf: serializer sẽ ghi thành U, nên là lỗi trừ khi overflow_to_float.
+        // inf/nan viết bằng chữ (N-inff với hậu tố f) luôn bị từ chối
+        let overflowed = val.is_infinite() && num_slice.iter().any(u8::is_ascii_digit);
+        if !(val.is_finite() || overflowed && options.overflow_to_float) {
+            return Err(FdonError::InvalidFloat { detail: detail(&"not a finite number"), pos: start });
+        }
+        Ok(FdonNumber::Float(val))
+    } else if suffix == Some(b'u') {
+        match parse_int_exact::<u64>(num_slice, at)? {
+            Some(val) => Ok(FdonNumber::UInt(val)),
+            None => Err(FdonError::IntegerOverflow { pos: start }),
+        }
+    } else if let Some(val) = parse_int_exact::<i64>(num_slice, at)? {
+        Ok(FdonNumber::Integer(val))
+    } else if num_slice[0] != b'-' && let Some(val) = parse_int_exact::<u64>(num_slice, at)? {
+        // Lớn hơn i64::MAX nhưng vẫn vừa u64 -> giữ chính xác, không rơi về float
+        Ok(FdonNumber::UInt(val))
+    } else if options.overflow_to_float {
+        // Chỉ gồm dấu và chữ số (đã kiểm tra) -> fast_float không thể lỗi
+        fast_float::parse(num_slice)
+            .map(FdonNumber::Float)
+            .map_err(|_| FdonError::IntegerOverflow { pos: start })
+    } else {
+        Err(FdonError::IntegerOverflow { pos: start })
+    }
+}
+
+// Số nguyên phải chiếm trọn `num_slice` (atoi chỉ đọc phần đầu hợp lệ, N12abc -> 12).
+// Ok(None) = đúng định dạng nhưng tràn; lỗi tại byte lạ đầu tiên (`at`: offset -> vị trí)
+#[inline]
+fn parse_int_exact<I: atoi::FromRadix10SignedChecked>(
+    num_slice: &[u8],
+    at: impl Fn(usize) -> usize,
+) -> Result<Option<I>, FdonError> {
+    let (val, used) = I::from_radix_10_signed_checked(num_slice);
+    if used < num_slice.len() {
+        return Err(FdonError::InvalidInteger { pos: at(used) });
+    }
+    // Chỉ có dấu, không có chữ số nào (N-, N+)
+    if used == usize::from(matches!(num_slice[0], b'+' | b'-')) {
+        return Err(FdonError::InvalidInteger { pos: at(0) });
+    }
+    Ok(val)
+}
+
+// Mỗi '_' phải nằm giữa hai chữ số: không ở đầu/cuối, không cạnh '.', 'e', dấu hay '_' khác
+fn check_digit_separators(num_slice: &[u8], start: usize) -> Result<(), FdonError> {
+    for i in memchr::memchr_iter(b'_', num_slice) {
+        let between_digits = i > 0
+            && num_slice[i - 1].is_ascii_digit()
+            && num_slice.get(i + 1).is_some_and(u8::is_ascii_digit);
+        if !between_digits {
+            return Err(FdonError::InvalidDigitSeparator { pos: start + i });
+        }
+    }
+    Ok(())
 }
 
 
@@ -449,4 +1582,88 @@ pub fn parse_fdon_zero_copy_arena<'a, 'bump>(
 ) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
     let mut parser = FdonParser::new(minified_data, arena);
     parser.parse()
-}
\ No newline at end of file
+}
+
+/// Like `parse_fdon_zero_copy_arena`, for input that is not known to be UTF-8
+/// (e.g. file bytes). Keys and strings are checked as they are read; the rest of
+/// the buffer never needs to be (see `FdonParser::from_bytes`).
+#[inline]
+pub fn parse_fdon_bytes<'a, 'bump>(bytes: &'a [u8], arena: &'bump Bump) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
+    FdonParser::from_bytes(bytes, arena).parse()
+}
+
+/// Same as `parse_fdon_bytes`, with explicit `ParseOptions`.
+#[inline]
+pub fn parse_fdon_bytes_with_options<'a, 'bump>(
+    bytes: &'a [u8],
+    arena: &'bump Bump,
+    options: ParseOptions,
+) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
+    FdonParser::from_bytes_with_options(bytes, arena, options).parse()
+}
+
+/// Same as `parse_fdon_zero_copy_arena`, with explicit `ParseOptions`.
+#[inline]
+pub fn parse_fdon_zero_copy_arena_with_options<'a, 'bump>(
+    minified_data: &'a str,
+    arena: &'bump Bump,
+    options: ParseOptions,
+) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
+    let mut parser = FdonParser::with_options(minified_data, arena, options);
+    parser.parse()
+}
+/// Parses FDON that may contain whitespace between tokens (e.g. pretty-printed
+/// with `prettify_fdon`) in one pass, without `minify_fdon` first. Same as
+/// parsing with `ParseOptions { allow_whitespace: true, .. }`; error positions are
+/// offsets into `input` itself.
+#[inline]
+pub fn parse_fdon_lenient<'a, 'bump>(
+    input: &'a str,
+    arena: &'bump Bump,
+) -> ParseResult<'a, 'bump, FdonValue<'a, 'bump>> {
+    let options = ParseOptions { allow_whitespace: true, ..ParseOptions::default() };
+    FdonParser::with_options(input, arena, options).parse()
+}
+
+/// Parses a copy of `minified_data` made inside `arena`, so keys and strings are
+/// `&'bump str` and the value no longer borrows the caller's buffer: the input can
+/// be dropped or reused right away, and keys can be replaced (`rename_key`) with
+/// other arena strings.
+///
+/// Memory cost: the whole input is copied into the arena once, on top of the usual
+/// tree, i.e. roughly `minified_data.len()` extra arena bytes for the value's lifetime.
+pub fn parse_fdon_detached<'bump>(
+    minified_data: &str,
+    arena: &'bump Bump,
+    options: ParseOptions,
+) -> ParseResult<'bump, 'bump, FdonValue<'bump, 'bump>> {
+    let copy: &'bump str = arena.alloc_str(minified_data);
+    let mut parser = FdonParser::with_options(copy, arena, options);
+    parser.parse()
+}
+
+/// Parses the single value that starts at byte `offset` of `minified_data`, and
+/// returns it with the offset just past its end. Lets tooling that indexed value
+/// offsets earlier materialize one subtree without re-parsing the document.
+///
+/// `offset` must be a value boundary: the start of input, or right after `[`, `:`
+/// or `,`, on a type tag. Otherwise the result is `FdonError::InvalidOffset`.
+/// (After `,` inside an object a key starts, so a key beginning with a type-tag
+/// letter cannot be told apart from a value here.)
+pub fn parse_value_at<'a, 'bump>(
+    minified_data: &'a str,
+    offset: usize,
+    arena: &'bump Bump,
+) -> ParseResult<'a, 'bump, (FdonValue<'a, 'bump>, usize)> {
+    let data = minified_data.as_bytes();
+    let on_boundary = offset < data.len()
+        && (offset == 0 || matches!(data[offset - 1], b'[' | b':' | b','))
+        && matches!(data[offset], b'O' | b'A' | b'S' | b'D' | b'T' | b'N' | b'B' | b'U');
+    if !on_boundary {
+        return Err(FdonError::InvalidOffset { pos: offset });
+    }
+    let mut parser = FdonParser::new(minified_data, arena);
+    parser.index = offset;
+    let value = parser.parse_value()?;
+    Ok((value, parser.index))
+}
@@ -0,0 +1,140 @@
+// --- Lint: liệt kê mọi key trùng (không dừng ở key trùng đầu tiên) ---
+
+use std::borrow::Cow;
+use std::collections::hash_map::{Entry, HashMap};
+
+use bumpalo::Bump;
+
+use crate::validate::ContainerStack;
+use crate::{check_delimiter, directive, Container, FdonError, FdonParser, ParseOptions};
+
+/// A key that appears more than once in the same object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DuplicateKey<'a> {
+    /// The key as written: for an `SE"..."` key with escapes, the text between the
+    /// quotes with the escapes still in place (keys are compared unescaped).
+    pub key: &'a str,
+    /// Offset of the first occurrence of `key` in that object.
+    pub first_pos: usize,
+    /// Offset of this (later) occurrence.
+    pub dup_pos: usize,
+}
+
+/// Reports every duplicate key in `input`, across all objects, in document order.
+/// A key written three times gives two entries, both pointing back at the first.
+///
+/// Walks the document without building it (nothing is allocated per value), with
+/// the same syntax checks and errors as `FdonParser::skip_value`; a syntax error
+/// aborts the lint. Use `options.allow_whitespace` to lint pretty-printed files.
+pub fn lint_duplicate_keys(input: &str, options: ParseOptions) -> Result<Vec<DuplicateKey<'_>>, FdonError> {
+    // skip_value không cấp phát gì trong Arena
+    let arena = Bump::new();
+    check_delimiter(options.string_delimiter)?;
+    let mut parser = FdonParser::with_options(input, &arena, options);
+    if options.allow_version_directive {
+        parser.index = directive::check_directive(parser.data).map_err(|e| parser.decorate(e))?;
+    }
+    parser.skip_whitespace();
+    if matches!(parser.peek(), Some(b'{' | b'[' | b'"')) {
+        return Err(parser.decorate(FdonError::LooksLikeJson { pos: parser.index }));
+    }
+
+    let mut duplicates = Vec::new();
+    walk(&mut parser, &mut duplicates).map_err(|e| parser.decorate(e))?;
+    parser.skip_whitespace();
+    if parser.index != parser.data.len() {
+        return Err(parser.decorate(FdonError::ExtraData { pos: parser.index }));
+    }
+    Ok(duplicates)
+}
+
+// Đi qua một value như skip_value: container đang mở nằm trên heap (độ sâu chỉ bị
+// giới hạn bởi max_depth), mỗi object đang mở có map key -> vị trí của riêng nó
+fn walk<'a>(parser: &mut FdonParser<'a, '_>, duplicates: &mut Vec<DuplicateKey<'a>>) -> Result<(), FdonError> {
+    let mut stack = ContainerStack::default();
+    let mut seen: Vec<HashMap<Cow<'a, str>, usize>> = Vec::new();
+    // Dùng lại cho mọi key SE"..." có escape
+    let mut unescaped = String::new();
+    loop {
+        parser.skip_whitespace();
+        let type_char = parser.peek().ok_or(FdonError::UnexpectedEof { pos: parser.index })?;
+        parser.advance();
+
+        if let b'O' | b'A' = type_char {
+            let (container, open, close) = match type_char {
+                b'O' => (Container::Object, b'{', b'}'),
+                _ => (Container::Array, b'[', b']'),
+            };
+            parser.enter(parser.index - 1)?;
+            parser.skip_whitespace();
+            parser.consume(open)?;
+            parser.skip_whitespace();
+            if parser.peek() != Some(close) {
+                stack.push(container);
+                if container == Container::Object {
+                    let mut keys = HashMap::new();
+                    check_key(parser, &mut keys, &mut unescaped, duplicates)?;
+                    seen.push(keys);
+                }
+                continue;
+            }
+            parser.advance();
+            parser.leave();
+        } else {
+            parser.skip_scalar(type_char)?;
+        }
+
+        // Một value đã xong: đóng các container vừa hết phần tử
+        loop {
+            let Some(container) = stack.last() else { return Ok(()) };
+            let close = match container {
+                Container::Object => b'}',
+                Container::Array => b']',
+            };
+            parser.skip_separator(close, container)?;
+            if parser.peek() != Some(close) {
+                if container == Container::Object
+                    && let Some(keys) = seen.last_mut()
+                {
+                    check_key(parser, keys, &mut unescaped, duplicates)?;
+                }
+                break;
+            }
+            parser.advance();
+            parser.leave();
+            stack.pop();
+            if container == Container::Object {
+                seen.pop();
+            }
+        }
+    }
+}
+
+// Đọc key (và dấu ':') của object đang mở; key đã có trong `seen` thì ghi lại là trùng
+fn check_key<'a>(
+    parser: &mut FdonParser<'a, '_>,
+    seen: &mut HashMap<Cow<'a, str>, usize>,
+    unescaped: &mut String,
+    duplicates: &mut Vec<DuplicateKey<'a>>,
+) -> Result<(), FdonError> {
+    let pos = parser.index;
+    unescaped.clear();
+    let (key, written) = match parser.parse_key_into(unescaped)? {
+        Some(key) => (Cow::Borrowed(key), key),
+        None => {
+            // SE"..." tại `pos`: phần giữa hai dấu nháy (trước whitespace sau dấu đóng)
+            let quoted = parser.data[pos + 3..parser.index].trim_ascii_end();
+            (Cow::Owned(unescaped.clone()), parser.to_str(&quoted[..quoted.len() - 1], pos + 3)?)
+        }
+    };
+    parser.consume(b':')?;
+    match seen.entry(key) {
+        Entry::Occupied(first) => {
+            duplicates.push(DuplicateKey { key: written, first_pos: *first.get(), dup_pos: pos });
+        }
+        Entry::Vacant(slot) => {
+            slot.insert(pos);
+        }
+    }
+    Ok(())
+}
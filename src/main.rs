@@ -1,81 +1,113 @@
 use std::env;
 use std::fs;
+use std::io::{self, Write};
 use std::process;
 use std::time::Instant;
 
 // --- SỬA LỖI API ---
 // Import API mới (chỉ dùng Arena) và các struct liên quan
-use fdon_rs::{minify_fdon, FdonParseError, FdonValue, parse_fdon_zero_copy_arena};
+use fdon_rs::{fdon_to_json_writer, line_col_at, lint_duplicate_keys, render_error, FdonError, FdonValue, ParseOptions, parse_fdon_lenient};
 // Import Bumpalo
 use bumpalo::Bump;
 // --- KẾT THÚC SỬA LỖI ---
 
 
-// Hàm trợ giúp in lỗi (Giờ sẽ in lỗi trên file thô)
-fn print_error((msg, pos): FdonParseError, raw_content: &str) -> ! {
-    eprintln!("FDON Syntax Error: {} at position {}", msg, pos);
-    
-    // Chỉ in một phần của nội dung nếu nó quá dài
-    const MAX_LEN: usize = 100;
-    if raw_content.len() > MAX_LEN {
-         let start = if pos > MAX_LEN / 2 { pos - MAX_LEN / 2 } else { 0 };
-         let end = std::cmp::min(raw_content.len(), start + MAX_LEN);
-         eprintln!("...{}...", &raw_content[start..end]);
-         // Tính toán vị trí ^
-         if pos >= start {
-            eprintln!("{}^", " ".repeat(pos - start));
-         } else {
-            eprintln!("^ (Error at start)");
-         }
-    } else {
-        eprintln!("{}", raw_content);
-        eprintln!("{}^", " ".repeat(pos));
+// Writer chỉ giữ lại 100 byte đầu (để in mẫu) và đếm tổng số byte JSON,
+// tránh phải giữ toàn bộ chuỗi JSON trong bộ nhớ.
+struct SampleWriter {
+    sample: Vec<u8>,
+    total: usize,
+}
+
+impl Write for SampleWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        const SAMPLE_LEN: usize = 100;
+        if self.sample.len() < SAMPLE_LEN {
+            let take = std::cmp::min(SAMPLE_LEN - self.sample.len(), buf.len());
+            self.sample.extend_from_slice(&buf[..take]);
+        }
+        self.total += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Hàm trợ giúp in lỗi (dùng chung cách hiển thị với thư viện: render_error)
+// Parse thẳng trên file gốc nên vị trí lỗi cũng là vị trí trong `content`
+fn print_error(err: FdonError, content: &str, filename: &str) -> ! {
+    eprintln!("FDON Syntax Error: {}", render_error(content, &err));
+    if let Some(pos) = err.pos() {
+        let (line, col) = line_col_at(content, pos);
+        eprintln!("  --> {}:{}:{}", filename, line, col);
     }
-    
     process::exit(1);
 }
 
+// Đọc file hoặc thoát với thông báo lỗi
+fn read_file(filename: &str) -> String {
+    match fs::read_to_string(filename) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error: File not found or read error for '{}': {}", filename, e);
+            process::exit(1);
+        }
+    }
+}
+
+// `lint <file>`: in mọi key trùng (file:line:col), exit code 1 nếu có
+fn lint(filename: &str) -> ! {
+    let content = read_file(filename);
+    let options = ParseOptions { allow_whitespace: true, ..ParseOptions::default() };
+    let duplicates = match lint_duplicate_keys(&content, options) {
+        Ok(d) => d,
+        Err(e) => print_error(e, &content, filename),
+    };
+    for dup in &duplicates {
+        let (line, col) = line_col_at(&content, dup.dup_pos);
+        let (first_line, first_col) = line_col_at(&content, dup.first_pos);
+        println!(
+            "{}:{}:{}: duplicate key `{}` (first defined at {}:{})",
+            filename, line, col, dup.key, first_line, first_col
+        );
+    }
+    process::exit(if duplicates.is_empty() { 0 } else { 1 });
+}
+
 fn main() {
     // --- Argument handling ---
     let args: Vec<String> = env::args().collect();
+    if args.len() == 3 && args[1] == "lint" {
+        lint(&args[2]);
+    }
     if args.len() != 2 {
         eprintln!("Usage: {} <filename>.fdon", args[0]);
+        eprintln!("       {} lint <filename>.fdon", args[0]);
         process::exit(1);
     }
     let filename = &args[1];
 
     // --- Read file ---
-    let content = match fs::read_to_string(filename) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("Error: File not found or read error for '{}': {}", filename, e);
-            process::exit(1);
-        }
-    };
+    let content = read_file(filename);
 
-    // --- Bước 1: Minify (Đo thời gian riêng) ---
-    let start_time_minify = Instant::now();
-    let minified_content = minify_fdon(&content);
-    let duration_minify = start_time_minify.elapsed();
-    
     println!("--- FDON Process Timing ---");
-    println!("Minified Data Size: {} bytes", minified_content.len());
-    println!("Minify Time: {:.6} ms", duration_minify.as_secs_f64() * 1000.0);
+    println!("Input Data Size: {} bytes", content.len());
     println!("{}", "-".repeat(30));
 
 
-    // --- Bước 2: Parse (Sử dụng Arena) ---
+    // --- Parse (Sử dụng Arena, bỏ qua whitespace ngay khi parse, không cần minify) ---
     
     // TẠO ARENA
     let arena = Bump::new();
     
     let start_time_parse = Instant::now();
     
-    // 'value' giờ đây mượn 'minified_content' (cho 'a) VÀ 'arena' (cho 'bump)
-    let value: FdonValue<'_, '_> = match parse_fdon_zero_copy_arena(&minified_content, &arena) {
+    // 'value' giờ đây mượn 'content' (cho 'a) VÀ 'arena' (cho 'bump)
+    let value: FdonValue<'_, '_> = match parse_fdon_lenient(&content, &arena) {
         Ok(v) => v,
-        // In lỗi trên nội dung ĐÃ MINIFY (vì index lỗi là trên file đó)
-        Err(e) => print_error(e, &minified_content),
+        Err(e) => print_error(e, &content, filename),
     };
 
     let duration_parse = start_time_parse.elapsed(); 
@@ -83,16 +115,23 @@ fn main() {
     // --- Serialization và In kết quả ---
     let start_time_serialize = Instant::now();
 
-    let json_output = serde_json::to_string(&value)
-        .unwrap_or_else(|e| format!("Error serializing to JSON: {}", e));
+    // Stream JSON thay vì dựng toàn bộ chuỗi (giảm bộ nhớ đỉnh)
+    let mut json_output = SampleWriter { sample: Vec::new(), total: 0 };
+    if let Err(e) = fdon_to_json_writer(&value, &mut json_output) {
+        eprintln!("Error serializing to JSON: {}", e);
+    }
 
     let duration_serialize = start_time_serialize.elapsed();
 
     // --- Print Results ---
     println!("--- Result (JSON) ---");
-    let sample = json_output.chars().take(100).collect::<String>();
-    println!("Sample (first 100 chars): {}", sample);
-    println!("Total JSON size: {} bytes", json_output.len());
+    // 100 byte đầu có thể cắt giữa một ký tự nhiều byte: bỏ ký tự dở dang ở cuối
+    let sample = match std::str::from_utf8(&json_output.sample).map_err(|e| e.valid_up_to()) {
+        Ok(sample) => sample,
+        Err(valid) => std::str::from_utf8(&json_output.sample[..valid]).unwrap_or_default(),
+    };
+    println!("Sample (first 100 bytes): {}", sample);
+    println!("Total JSON size: {} bytes", json_output.total);
     println!("{}", "-".repeat(30));
     
     // Tính toán và in tốc độ
@@ -100,7 +139,6 @@ fn main() {
     let duration_serialize_ms = duration_serialize.as_secs_f64() * 1000.0;
     
     println!("--- FDON Process Timing (Summary) ---");
-    // (Lưu ý: Thời gian Parse này KHÔNG bao gồm Minify)
     println!("🚀 Parse Time (Arena, Zero-Copy): {:.6} ms", duration_parse_ms);
     println!("⚡ Serialize Time (minified): {:.6} ms", duration_serialize_ms);
     println!("Total Time (Parse + Serialize): {:.6} ms", duration_parse_ms + duration_serialize_ms);
@@ -0,0 +1,304 @@
+// --- Object giữ thứ tự chèn (payload của FdonValue::Object) ---
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use ahash::RandomState as AHasher;
+use bumpalo::{Bump, collections::Vec as BumpVec};
+use hashbrown::HashTable;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::FdonValue;
+
+// Object nhỏ thì quét tuyến tính; vượt ngưỡng này mới dựng bảng băm
+const INDEX_THRESHOLD: usize = 8;
+
+/// Arena-allocated object payload of `FdonValue::Object`. Keys are `&'bump str`:
+/// borrowed from the input (which outlives the arena) or, for `SE"..."` keys with
+/// escapes, unescaped into the arena.
+///
+/// Entries keep insertion order, which for a parsed document is document order:
+/// iteration, serialization and JSON conversion all list keys in that order.
+/// Inserting an existing key replaces its value in place; removing a key shifts
+/// the entries after it. Small objects are searched linearly, larger ones through
+/// a hash index allocated in the same arena.
+pub struct FdonObject<'a, 'bump> {
+    entries: BumpVec<'bump, (&'bump str, FdonValue<'a, 'bump>)>,
+    index: Option<HashTable<usize, &'bump Bump>>,
+}
+
+// Một seed ngẫu nhiên cho cả process: index không cần lưu hasher riêng cho từng object
+fn hash_key(key: &str) -> u64 {
+    static HASHER: OnceLock<AHasher> = OnceLock::new();
+    HASHER.get_or_init(AHasher::new).hash_one(key)
+}
+
+#[inline]
+fn index_find<V>(table: &HashTable<usize, &Bump>, entries: &[(&str, V)], key: &str) -> Option<usize> {
+    table.find(hash_key(key), |&i| entries[i].0 == key).copied()
+}
+
+#[inline]
+fn index_push<V>(table: &mut HashTable<usize, &Bump>, entries: &[(&str, V)], i: usize) {
+    table.insert_unique(hash_key(entries[i].0), i, |&j| hash_key(entries[j].0));
+}
+
+impl<'a, 'bump> FdonObject<'a, 'bump> {
+    #[inline]
+    pub fn new_in(arena: &'bump Bump) -> Self {
+        FdonObject { entries: BumpVec::new_in(arena), index: None }
+    }
+
+    /// Pre-sizes the entries (and, for more than a handful, the hash index) for
+    /// `capacity` entries.
+    pub fn with_capacity_in(capacity: usize, arena: &'bump Bump) -> Self {
+        let mut obj = FdonObject::new_in(arena);
+        obj.reserve(capacity);
+        obj
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        match &self.index {
+            Some(table) => index_find(table, &self.entries, key),
+            None => self.entries.iter().position(|(k, _)| *k == key),
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&FdonValue<'a, 'bump>> {
+        self.position(key).map(|i| &self.entries[i].1)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut FdonValue<'a, 'bump>> {
+        self.position(key).map(|i| &mut self.entries[i].1)
+    }
+
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Inserts `value` under `key`. A new key goes at the end; an existing one keeps
+    /// its position and the old value is returned.
+    pub fn insert(&mut self, key: &'bump str, value: FdonValue<'a, 'bump>) -> Option<FdonValue<'a, 'bump>> {
+        if let Some(i) = self.position(key) {
+            return Some(std::mem::replace(&mut self.entries[i].1, value));
+        }
+        self.entries.push((key, value));
+        let i = self.entries.len() - 1;
+        match &mut self.index {
+            Some(table) => index_push(table, &self.entries, i),
+            None if self.entries.len() > INDEX_THRESHOLD => self.rebuild_index(),
+            None => {}
+        }
+        None
+    }
+
+    /// Removes `key` and returns its value; later entries move up one place.
+    pub fn remove(&mut self, key: &str) -> Option<FdonValue<'a, 'bump>> {
+        let i = self.position(key)?;
+        let (_, value) = self.entries.remove(i);
+        self.rebuild_index();
+        Some(value)
+    }
+
+    /// Renames `from` to `to` in place, keeping the entry's position; an existing
+    /// `to` entry is dropped. Returns `false` if there is no key `from`.
+    pub(crate) fn rename(&mut self, from: &str, to: &'bump str) -> bool {
+        let Some(i) = self.position(from) else { return false };
+        if from == to {
+            return true;
+        }
+        self.entries[i].0 = to;
+        if let Some(j) = self.entries.iter().enumerate().position(|(j, (k, _))| j != i && *k == to) {
+            self.entries.remove(j);
+        }
+        self.rebuild_index();
+        true
+    }
+
+    /// Keeps the entries for which `f` returns `true`, in their order.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&&'bump str, &mut FdonValue<'a, 'bump>) -> bool,
+    {
+        let len = self.entries.len();
+        self.entries.retain_mut(|(k, v)| f(k, v));
+        if self.entries.len() != len {
+            self.rebuild_index();
+        }
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.entries.reserve(additional);
+        let total = self.entries.len() + additional;
+        if total <= INDEX_THRESHOLD {
+            return;
+        }
+        match &mut self.index {
+            Some(table) => {
+                let entries = &self.entries;
+                table.reserve(additional, |&j| hash_key(entries[j].0));
+            }
+            None => {
+                let table = self.index.insert(HashTable::with_capacity_in(total, self.entries.bump()));
+                for i in 0..self.entries.len() {
+                    index_push(table, &self.entries, i);
+                }
+            }
+        }
+    }
+
+    /// Entries in insertion order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, 'a, 'bump> {
+        Iter(self.entries.iter())
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, 'a, 'bump> {
+        IterMut(self.entries.iter_mut())
+    }
+
+    #[inline]
+    pub fn keys(&self) -> impl Iterator<Item = &'bump str> + '_ {
+        self.entries.iter().map(|(k, _)| *k)
+    }
+
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = &FdonValue<'a, 'bump>> + '_ {
+        self.entries.iter().map(|(_, v)| v)
+    }
+
+    #[inline]
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut FdonValue<'a, 'bump>> + '_ {
+        self.entries.iter_mut().map(|(_, v)| v)
+    }
+
+    // Dựng lại index sau khi vị trí các entry thay đổi (remove/retain/rename)
+    fn rebuild_index(&mut self) {
+        if self.index.is_none() && self.entries.len() <= INDEX_THRESHOLD {
+            return;
+        }
+        let table = match &mut self.index {
+            Some(table) => {
+                table.clear();
+                table
+            }
+            None => self.index.insert(HashTable::with_capacity_in(self.entries.len(), self.entries.bump())),
+        };
+        for i in 0..self.entries.len() {
+            index_push(table, &self.entries, i);
+        }
+    }
+}
+
+impl<'a, 'bump> Extend<(&'bump str, FdonValue<'a, 'bump>)> for FdonObject<'a, 'bump> {
+    fn extend<I: IntoIterator<Item = (&'bump str, FdonValue<'a, 'bump>)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+/// Borrowing iterator over an object's entries, in insertion order.
+pub struct Iter<'o, 'a, 'bump>(std::slice::Iter<'o, (&'bump str, FdonValue<'a, 'bump>)>);
+
+impl<'o, 'a, 'bump> Iterator for Iter<'o, 'a, 'bump> {
+    type Item = (&'o &'bump str, &'o FdonValue<'a, 'bump>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (k, v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Iter<'_, '_, '_> {}
+
+/// Mutable iterator over an object's entries, in insertion order.
+pub struct IterMut<'o, 'a, 'bump>(std::slice::IterMut<'o, (&'bump str, FdonValue<'a, 'bump>)>);
+
+impl<'o, 'a, 'bump> Iterator for IterMut<'o, 'a, 'bump> {
+    type Item = (&'o &'bump str, &'o mut FdonValue<'a, 'bump>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (&*k, v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for IterMut<'_, '_, '_> {}
+
+impl<'o, 'a, 'bump> IntoIterator for &'o FdonObject<'a, 'bump> {
+    type Item = (&'o &'bump str, &'o FdonValue<'a, 'bump>);
+    type IntoIter = Iter<'o, 'a, 'bump>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'o, 'a, 'bump> IntoIterator for &'o mut FdonObject<'a, 'bump> {
+    type Item = (&'o &'bump str, &'o mut FdonValue<'a, 'bump>);
+    type IntoIter = IterMut<'o, 'a, 'bump>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, 'bump> IntoIterator for FdonObject<'a, 'bump> {
+    type Item = (&'bump str, FdonValue<'a, 'bump>);
+    type IntoIter = bumpalo::collections::vec::IntoIter<'bump, (&'bump str, FdonValue<'a, 'bump>)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl fmt::Debug for FdonObject<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Same entries in any order, compared with `FdonValue`'s `==`.
+impl PartialEq for FdonObject<'_, '_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k).is_some_and(|w| v == w))
+    }
+}
+
+impl Serialize for FdonObject<'_, '_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (k, v) in self.iter() {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
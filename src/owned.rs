@@ -0,0 +1,94 @@
+// --- Owned Value (không phụ thuộc input/Arena) ---
+
+use serde::Serialize;
+use hashbrown::HashMap;
+use ahash::RandomState as AHasher;
+
+use crate::{FdonNumber, FdonValue};
+
+/// A self-contained copy of a `FdonValue` that borrows neither the input nor the arena.
+///
+/// Use it to return parsed data out of the scope that owns the arena. Variants mirror
+/// `FdonValue` one-to-one, so the FDON type of every value is preserved.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum OwnedFdonValue {
+    Null,
+    Bool(bool),
+    Number(FdonNumber),
+    Timestamp(FdonNumber),
+    RawString(String),
+    EscapedString(String),
+    Date(String),
+    Time(String),
+    Array(Vec<OwnedFdonValue>),
+    Object(HashMap<String, OwnedFdonValue, AHasher>),
+}
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Deep-copies the value into plain heap types.
+    pub fn to_owned_value(&self) -> OwnedFdonValue {
+        match self {
+            FdonValue::Null => OwnedFdonValue::Null,
+            FdonValue::Bool(b) => OwnedFdonValue::Bool(*b),
+            FdonValue::Number(n) => OwnedFdonValue::Number(*n),
+            FdonValue::Timestamp(n) => OwnedFdonValue::Timestamp(*n),
+            FdonValue::RawString(s) => OwnedFdonValue::RawString((*s).to_string()),
+            FdonValue::EscapedString(s) => OwnedFdonValue::EscapedString(s.as_str().to_string()),
+            FdonValue::Date(s) => OwnedFdonValue::Date((*s).to_string()),
+            FdonValue::Time(s) => OwnedFdonValue::Time((*s).to_string()),
+            FdonValue::Array(arr) => {
+                OwnedFdonValue::Array(arr.iter().map(FdonValue::to_owned_value).collect())
+            }
+            FdonValue::Object(obj) => {
+                let mut map = HashMap::with_capacity_and_hasher(obj.len(), AHasher::new());
+                for (k, v) in obj.iter() {
+                    map.insert((*k).to_string(), v.to_owned_value());
+                }
+                OwnedFdonValue::Object(map)
+            }
+        }
+    }
+}
+
+impl From<&FdonValue<'_, '_>> for OwnedFdonValue {
+    #[inline]
+    fn from(value: &FdonValue<'_, '_>) -> Self {
+        value.to_owned_value()
+    }
+}
+
+// --- So sánh FdonValue <-> OwnedFdonValue (không cần chuyển đổi) ---
+
+/// Numeric-aware (`N1` == `N1.0`) and raw/escaped-insensitive (`S"a"` == `SE"a"`)
+/// equality, the same notion as `==` between two `FdonValue`s.
+impl PartialEq<OwnedFdonValue> for FdonValue<'_, '_> {
+    fn eq(&self, other: &OwnedFdonValue) -> bool {
+        match (self, other) {
+            (FdonValue::Null, OwnedFdonValue::Null) => true,
+            (FdonValue::Bool(a), OwnedFdonValue::Bool(b)) => a == b,
+            (FdonValue::Number(a), OwnedFdonValue::Number(b))
+            | (FdonValue::Timestamp(a), OwnedFdonValue::Timestamp(b)) => a == b,
+            (
+                FdonValue::RawString(_) | FdonValue::EscapedString(_),
+                OwnedFdonValue::RawString(b) | OwnedFdonValue::EscapedString(b),
+            ) => self.string_content() == Some(b.as_str()),
+            (FdonValue::Date(a), OwnedFdonValue::Date(b))
+            | (FdonValue::Time(a), OwnedFdonValue::Time(b)) => a == b,
+            (FdonValue::Array(a), OwnedFdonValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x == y)
+            }
+            (FdonValue::Object(a), OwnedFdonValue::Object(b)) => {
+                a.len() == b.len() && a.iter().all(|(k, v)| b.get(*k).is_some_and(|w| v == w))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq<FdonValue<'_, '_>> for OwnedFdonValue {
+    #[inline]
+    fn eq(&self, other: &FdonValue<'_, '_>) -> bool {
+        other == self
+    }
+}
@@ -0,0 +1,172 @@
+// --- Reader tiện ích cho các API streaming ---
+
+use std::io::{self, BufRead, Read};
+
+use bumpalo::Bump;
+
+use crate::validate::READ_CHUNK;
+use crate::{FdonError, IncrementalParser, OwnedFdonValue, ParseOptions, Progress};
+
+/// Wraps a reader and counts every byte read through it, so positions local to a
+/// buffer can be turned into absolute stream offsets (the numbers `grep -b` and
+/// editors show).
+///
+/// `validate_reader` already reports absolute offsets; this is for callers that
+/// drive their own buffers, e.g. feeding a `Validator` from a socket after some
+/// header bytes were consumed.
+#[derive(Debug)]
+pub struct CountingReader<R> {
+    inner: R,
+    offset: u64,
+}
+
+impl<R> CountingReader<R> {
+    #[inline]
+    pub fn new(inner: R) -> Self {
+        CountingReader { inner, offset: 0 }
+    }
+
+    /// Total bytes read (or consumed, for `BufRead`) so far.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.offset += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<R> {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.offset += amt as u64;
+    }
+}
+
+// --- Đọc lần lượt các value từ một Reader ---
+
+/// Iterator over the values of a stream of (minified) FDON documents read from
+/// `R`, e.g. a multi-gigabyte file of one value per line.
+///
+/// Bytes are pulled in chunks and checked by an `IncrementalParser` as they
+/// arrive, so tokens may be split anywhere. Only the value being read is kept in
+/// memory: each is parsed into a scratch arena, yielded as an `OwnedFdonValue`,
+/// and its bytes are dropped. A single huge root value is still buffered whole.
+///
+/// Errors carry absolute byte offsets into the stream. After the first error (or
+/// the end of input) the iterator returns `None`. Like `IncrementalParser`, a root
+/// number only completes at end of input.
+pub struct FdonReader<R> {
+    reader: R,
+    parser: IncrementalParser,
+    arena: Bump,
+    chunk: Vec<u8>,
+    // Tổng số byte đã đọc từ reader
+    read: usize,
+    done: bool,
+}
+
+impl<R: Read> FdonReader<R> {
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ParseOptions::default())
+    }
+
+    #[inline]
+    pub fn with_options(reader: R, options: ParseOptions) -> Self {
+        Self::with_chunk_size(reader, options, READ_CHUNK)
+    }
+
+    /// Like `with_options`, reading at most `chunk_size` (at least 1) bytes per call
+    /// to `Read::read`.
+    pub fn with_chunk_size(reader: R, options: ParseOptions, chunk_size: usize) -> Self {
+        FdonReader {
+            reader,
+            parser: IncrementalParser::with_options(options),
+            arena: Bump::new(),
+            chunk: vec![0; chunk_size.max(1)],
+            read: 0,
+            done: false,
+        }
+    }
+
+    /// Total bytes read from the underlying reader so far.
+    #[inline]
+    pub fn bytes_read(&self) -> usize {
+        self.read
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn next_value(&mut self) -> Result<Option<OwnedFdonValue>, FdonError> {
+        let mut progress = self.parser.next_frame()?;
+        while let Progress::Incomplete { .. } = progress {
+            let n = match self.reader.read(&mut self.chunk) {
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(FdonError::Io(e)),
+            };
+            if n == 0 {
+                // Chỉ còn khoảng trắng (đã bị bỏ) -> hết stream
+                if self.parser.buffered().is_empty() {
+                    return Ok(None);
+                }
+                progress = self.parser.finish()?;
+            } else {
+                self.read += n;
+                progress = self.parser.feed(&self.chunk[..n])?;
+            }
+        }
+        self.arena.reset();
+        let value = self.parser.value(&self.arena)?.to_owned_value();
+        Ok(Some(value))
+    }
+}
+
+impl<R: Read> Iterator for FdonReader<R> {
+    type Item = Result<OwnedFdonValue, FdonError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_value() {
+            Ok(Some(value)) => Some(Ok(value)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                // buffer của parser bắt đầu tại value hiện tại
+                let base = self.read - self.parser.buffered().len();
+                Some(Err(e.offset_by(base)))
+            }
+        }
+    }
+}
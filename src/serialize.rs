@@ -0,0 +1,407 @@
+// --- FDON Output ---
+
+use std::fmt::{self, Write as _};
+use std::io::{self, Write};
+
+use crate::json::{write_float, FloatFormat, SerializeOptions};
+use crate::trivia::{attach_comments, Comments};
+use crate::value::push_pointer_token;
+use crate::{check_delimiter, FdonError, FdonNumber, FdonValue, ParseOptions};
+
+/// Serializes `value` back into minified FDON text (`O{a:N1,b:A[Btrue,U]}`).
+///
+/// Object entries are written in insertion order (document order for parsed input).
+/// Raw strings stay `S"..."` unless they contain a `"`, in which case they become
+/// `SE"..."`; escaped strings are
+/// re-escaped (`\n`, `\t`, `\r`, `\"`, `\\`). Parsing the output gives back an equal
+/// value, except that non-finite floats are written as `U` (FDON has no literal for them).
+/// Keys are written bare, or quoted (`"a:b":`) when they contain `:` or whitespace,
+/// or start with `}` or like a quoted key; a key that needs quoting and also
+/// contains `"` is written escaped (`SE"a\"b:c":`).
+///
+/// The writer does not recurse, so arbitrarily deep trees (e.g. built with
+/// `FdonArrayBuilder`) cannot overflow the stack.
+pub fn to_fdon_string(value: &FdonValue<'_, '_>) -> String {
+    // Output chỉ gồm ASCII và các &str của value -> luôn là UTF-8
+    unsafe { String::from_utf8_unchecked(to_fdon_bytes(value)) }
+}
+
+/// Same output as `to_fdon_string`, byte for byte, as a `Vec<u8>`: for callers that
+/// write it straight to a file or socket and have no use for a `String`.
+pub fn to_fdon_bytes(value: &FdonValue<'_, '_>) -> Vec<u8> {
+    let mut out = Vec::new();
+    // Ghi vào Vec<u8> không bao giờ lỗi
+    let _ = to_fdon_writer(value, &mut out);
+    out
+}
+
+/// Writes the value as FDON text: `{}` gives `to_fdon_string`'s minified form,
+/// `{:#}` gives `prettify_fdon` with default `PrettyOptions`.
+impl fmt::Display for FdonValue<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(&prettify_fdon(self, PrettyOptions::default()))
+        } else {
+            f.write_str(&to_fdon_string(self))
+        }
+    }
+}
+
+/// Streaming version of `to_fdon_string`.
+///
+/// Wrap unbuffered sinks (files, sockets, stdout) in a `BufWriter`.
+#[inline]
+pub fn to_fdon_writer<W: Write>(value: &FdonValue<'_, '_>, writer: W) -> io::Result<()> {
+    to_fdon_writer_with_options(value, writer, &SerializeOptions::default())
+}
+
+/// Same as `to_fdon_writer`, but formats floats per `options.float_format` and
+/// quotes strings with `options.string_delimiter` (raw strings containing it become
+/// `SE` with the delimiter escaped). Floats always keep a `.` so they read back as
+/// floats, except with `Fixed(0)`.
+pub fn to_fdon_writer_with_options<W: Write>(
+    value: &FdonValue<'_, '_>,
+    mut writer: W,
+    options: &SerializeOptions,
+) -> io::Result<()> {
+    check_delimiter(options.string_delimiter).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let printer = Printer {
+        float_format: options.float_format,
+        delim: options.string_delimiter,
+        pretty: None,
+        comments: None,
+    };
+    printer.write_value(&mut writer, value)
+}
+
+/// Layout settings for `prettify_fdon`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyOptions {
+    /// Indent characters per nesting level (default 2).
+    pub indent_width: usize,
+    /// Indent with tabs instead of spaces.
+    pub use_tabs: bool,
+    /// Write `key: value` instead of `key:value` in objects (default on).
+    pub space_after_colon: bool,
+}
+
+impl Default for PrettyOptions {
+    #[inline]
+    fn default() -> Self {
+        PrettyOptions { indent_width: 2, use_tabs: false, space_after_colon: true }
+    }
+}
+
+/// Serializes `value` as indented, human-readable FDON: one entry or item per line,
+/// one indent level per nesting depth, keys in object order. Empty containers stay
+/// on one line (`O{}`, `A[]`). `minify_fdon` of the output equals `to_fdon_string(value)`.
+pub fn prettify_fdon(value: &FdonValue<'_, '_>, opts: PrettyOptions) -> String {
+    let mut out = Vec::new();
+    let printer = Printer { float_format: FloatFormat::default(), delim: b'"', pretty: Some(opts), comments: None };
+    // Ghi vào Vec<u8> không bao giờ lỗi
+    let _ = printer.write_value(&mut out, value);
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+/// Pretty-prints `value` like `prettify_fdon`, putting back the comments of `source`:
+/// the commented, un-minified text `value` was originally parsed from.
+///
+/// Values carry no trivia (the parser keeps no comments or spans), so this cannot be
+/// a plain `(value) -> String`: the comments are recovered from `source` instead,
+/// which is why it takes `source` and `options` and can fail on them.
+///
+/// Each comment is tied to the JSON Pointer path (as in `FdonValue::paths`) of the
+/// value it precedes or trails in `source` (see `collect_trivia`), so changing a
+/// value keeps its comments: leading comments are written on their own lines before
+/// the entry, trailing ones after it on the same line, and comments before a closing
+/// bracket stay there. Comments of paths no longer in `value` are dropped. Strings are
+/// quoted with `options.string_delimiter`, like `source`. Errors are those of reading
+/// `source` with `options` plus `allow_whitespace`.
+pub fn to_fdon_string_preserving(
+    value: &FdonValue<'_, '_>,
+    source: &str,
+    options: &ParseOptions,
+) -> Result<String, FdonError> {
+    let comments = attach_comments(source, options)?;
+    let mut out = Vec::new();
+    let printer = Printer {
+        float_format: FloatFormat::default(),
+        delim: options.string_delimiter,
+        pretty: Some(PrettyOptions::default()),
+        comments: Some(&comments),
+    };
+    // Ghi vào Vec<u8> không bao giờ lỗi
+    let _ = printer.write_value(&mut out, value);
+    Ok(unsafe { String::from_utf8_unchecked(out) })
+}
+
+// Dùng chung cho dạng minified (pretty = None) và dạng có thụt lề
+struct Printer<'c> {
+    float_format: FloatFormat,
+    // Delimiter của S/SE/D/T và key trong dấu nháy (đã được kiểm tra)
+    delim: u8,
+    pretty: Option<PrettyOptions>,
+    // Chỉ Some(..) cho to_fdon_string_preserving
+    comments: Option<&'c Comments<'c>>,
+}
+
+// Một container đang ghi dở: các phần tử/entry còn lại và dấu đóng
+struct Frame<'v, 'a, 'bump> {
+    items: Items<'v, 'a, 'bump>,
+    first: bool,
+    // Số phần tử đã ghi và độ dài path của container (khi giữ comment)
+    index: usize,
+    path_len: usize,
+}
+
+enum Items<'v, 'a, 'bump> {
+    Array(std::slice::Iter<'v, FdonValue<'a, 'bump>>),
+    Object(crate::object::Iter<'v, 'a, 'bump>),
+}
+
+impl<'c> Printer<'c> {
+    // Không đệ quy: dùng stack tường minh để cây sâu bao nhiêu cũng không tràn stack
+    fn write_value<W: Write>(&self, w: &mut W, root: &FdonValue<'_, '_>) -> io::Result<()> {
+        let colon: &[u8] = match self.pretty {
+            Some(p) if p.space_after_colon => b": ",
+            _ => b":",
+        };
+        let mut stack: Vec<Frame<'_, '_, '_>> = Vec::new();
+        let mut next = Some(root);
+        // JSON Pointer path của value đang ghi (chỉ cập nhật khi giữ comment)
+        let mut path = String::new();
+        // Comment cùng dòng của value vừa ghi xong, chờ sau dấu ','
+        let mut trailing: &[&str] = &[];
+        self.write_leading(w, &path, 0)?;
+
+        loop {
+            match next.take() {
+                Some(FdonValue::Array(arr)) if arr.is_empty() && self.before_close(&path).is_empty() => {
+                    w.write_all(b"A[]")?;
+                    trailing = self.after(&path);
+                }
+                Some(FdonValue::Object(obj)) if obj.is_empty() && self.before_close(&path).is_empty() => {
+                    w.write_all(b"O{}")?;
+                    trailing = self.after(&path);
+                }
+                Some(FdonValue::Array(arr)) => {
+                    w.write_all(b"A[")?;
+                    let items = Items::Array(arr.iter());
+                    stack.push(Frame { items, first: true, index: 0, path_len: path.len() });
+                }
+                Some(FdonValue::Object(obj)) => {
+                    w.write_all(b"O{")?;
+                    let items = Items::Object(obj.iter());
+                    stack.push(Frame { items, first: true, index: 0, path_len: path.len() });
+                }
+                Some(scalar) => {
+                    self.write_scalar(w, scalar)?;
+                    trailing = self.after(&path);
+                }
+                None => {}
+            }
+
+            let depth = stack.len();
+            let Some(frame) = stack.last_mut() else {
+                self.write_trailing(w, trailing)?;
+                for comment in self.comments.map_or(&[][..], |c| &c.end) {
+                    self.newline(w, 0)?;
+                    w.write_all(comment.as_bytes())?;
+                }
+                return Ok(());
+            };
+            let (key, item) = match &mut frame.items {
+                Items::Array(iter) => (None, iter.next()),
+                Items::Object(iter) => match iter.next() {
+                    Some((key, item)) => (Some(*key), Some(item)),
+                    None => (None, None),
+                },
+            };
+            match item {
+                Some(item) => {
+                    if !frame.first {
+                        w.write_all(b",")?;
+                    }
+                    frame.first = false;
+                    self.write_trailing(w, trailing)?;
+                    trailing = &[];
+                    if self.comments.is_some() {
+                        path.truncate(frame.path_len);
+                        match key {
+                            Some(key) => {
+                                path.push('/');
+                                push_pointer_token(&mut path, key);
+                            }
+                            None => {
+                                let _ = write!(path, "/{}", frame.index);
+                            }
+                        }
+                        frame.index += 1;
+                    }
+                    self.newline(w, depth)?;
+                    self.write_leading(w, &path, depth)?;
+                    if let Some(key) = key {
+                        write_key(w, key, self.delim)?;
+                        w.write_all(colon)?;
+                    }
+                    next = Some(item);
+                }
+                None => {
+                    let close: &[u8] = match frame.items {
+                        Items::Array(_) => b"]",
+                        Items::Object(_) => b"}",
+                    };
+                    self.write_trailing(w, trailing)?;
+                    path.truncate(frame.path_len);
+                    for comment in self.before_close(&path) {
+                        self.newline(w, depth)?;
+                        w.write_all(comment.as_bytes())?;
+                    }
+                    stack.pop();
+                    self.newline(w, depth - 1)?;
+                    w.write_all(close)?;
+                    trailing = self.after(&path);
+                }
+            }
+        }
+    }
+
+    // --- Comment (to_fdon_string_preserving) ---
+
+    #[inline]
+    fn after(&self, path: &str) -> &'c [&'c str] {
+        match self.comments.and_then(|c| c.by_path.get(path)) {
+            Some(attached) => &attached.after,
+            None => &[],
+        }
+    }
+
+    #[inline]
+    fn before_close(&self, path: &str) -> &'c [&'c str] {
+        match self.comments.and_then(|c| c.by_path.get(path)) {
+            Some(attached) => &attached.before_close,
+            None => &[],
+        }
+    }
+
+    // Comment leading: mỗi cái một dòng, cùng thụt lề với entry theo sau
+    fn write_leading<W: Write>(&self, w: &mut W, path: &str, depth: usize) -> io::Result<()> {
+        let Some(attached) = self.comments.and_then(|c| c.by_path.get(path)) else { return Ok(()) };
+        for comment in &attached.before {
+            w.write_all(comment.as_bytes())?;
+            self.newline(w, depth)?;
+        }
+        Ok(())
+    }
+
+    // Comment trailing: cùng dòng (dấu xuống dòng sau đó luôn do newline ghi)
+    #[inline]
+    fn write_trailing<W: Write>(&self, w: &mut W, comments: &[&str]) -> io::Result<()> {
+        for comment in comments {
+            w.write_all(b" ")?;
+            w.write_all(comment.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn write_scalar<W: Write>(&self, w: &mut W, value: &FdonValue<'_, '_>) -> io::Result<()> {
+        let format = self.float_format;
+        let delim = self.delim;
+        match value {
+            FdonValue::Null => w.write_all(b"U"),
+            FdonValue::Bool(true) => w.write_all(b"Btrue"),
+            FdonValue::Bool(false) => w.write_all(b"Bfalse"),
+            FdonValue::Number(n) => write_number(w, b'N', n, format),
+            FdonValue::Timestamp(n) => write_number(w, b'T', n, format),
+            FdonValue::RawString(s) if !s.as_bytes().contains(&delim) => write_quoted(w, b"S", s, delim),
+            FdonValue::RawString(s) => write_escaped(w, s, delim),
+            FdonValue::EscapedString(s) => write_escaped(w, s, delim),
+            FdonValue::Date(s) => write_quoted(w, b"D", s, delim),
+            FdonValue::Time(s) => write_quoted(w, b"T", s, delim),
+            FdonValue::Array(_) | FdonValue::Object(_) => unreachable!("containers are written by write_value"),
+        }
+    }
+
+    // Xuống dòng + thụt lề `depth` cấp (không làm gì ở dạng minified)
+    #[inline]
+    fn newline<W: Write>(&self, w: &mut W, depth: usize) -> io::Result<()> {
+        let Some(p) = self.pretty else { return Ok(()) };
+        w.write_all(b"\n")?;
+        let (ch, width) = if p.use_tabs { (b'\t', 1) } else { (b' ', p.indent_width) };
+        for _ in 0..depth * width {
+            w.write_all(&[ch])?;
+        }
+        Ok(())
+    }
+}
+
+fn write_number<W: Write>(w: &mut W, tag: u8, n: &FdonNumber, format: FloatFormat) -> io::Result<()> {
+    match n {
+        FdonNumber::Integer(i) => write!(w, "{}{}", tag as char, i),
+        FdonNumber::UInt(u) => write!(w, "{}{}", tag as char, u),
+        // Không có literal cho NaN/inf -> U (giống JSON ghi null)
+        FdonNumber::Float(f) if !f.is_finite() => w.write_all(b"U"),
+        FdonNumber::Float(f) => {
+            let mut buf = Vec::with_capacity(24);
+            write_float(&mut buf, *f, format)?;
+            // Luôn giữ dấu '.' (1e20 -> 1.0e20) cho dễ nhận ra là float
+            if format != FloatFormat::Fixed(0) && memchr::memchr(b'.', &buf).is_none() {
+                let at = memchr::memchr(b'e', &buf).unwrap_or(buf.len());
+                buf.splice(at..at, *b".0");
+            }
+            w.write_all(&[tag])?;
+            w.write_all(&buf)
+        }
+    }
+}
+
+// Key thường ghi trần; key mà parse_key sẽ đọc sai (hoặc minify sẽ xoá khoảng trắng
+// bên trong) thì đặt trong dấu nháy, hoặc SE"..." nếu chính nó chứa delimiter
+fn write_key<W: Write>(w: &mut W, key: &str, delim: u8) -> io::Result<()> {
+    let bytes = key.as_bytes();
+    let needs_quotes = key.contains(':')
+        || bytes.first() == Some(&delim)
+        // '}' ở đầu bị đọc thành dấu đóng object
+        || bytes.first() == Some(&b'}')
+        || bytes.starts_with(&[b'S', delim])
+        || bytes.starts_with(&[b'S', b'E', delim])
+        || bytes.iter().any(u8::is_ascii_whitespace);
+    if !needs_quotes {
+        w.write_all(key.as_bytes())
+    } else if bytes.contains(&delim) {
+        write_escaped(w, key, delim)
+    } else {
+        write_quoted(w, b"", key, delim)
+    }
+}
+
+#[inline]
+fn write_quoted<W: Write>(w: &mut W, tag: &[u8], s: &str, delim: u8) -> io::Result<()> {
+    w.write_all(tag)?;
+    w.write_all(&[delim])?;
+    w.write_all(s.as_bytes())?;
+    w.write_all(&[delim])
+}
+
+fn write_escaped<W: Write>(w: &mut W, s: &str, delim: u8) -> io::Result<()> {
+    w.write_all(&[b'S', b'E', delim])?;
+    let bytes = s.as_bytes();
+    // Ghi từng đoạn không cần escape một lần
+    let mut start = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        let escaped: &[u8] = match b {
+            b'\n' => b"\\n",
+            b'\t' => b"\\t",
+            b'\r' => b"\\r",
+            b'\\' => b"\\\\",
+            _ if b == delim => &[b'\\', delim],
+            _ => continue,
+        };
+        w.write_all(&bytes[start..i])?;
+        w.write_all(escaped)?;
+        start = i + 1;
+    }
+    w.write_all(&bytes[start..])?;
+    w.write_all(&[delim])
+}
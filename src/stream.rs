@@ -0,0 +1,101 @@
+// --- Luồng scalar ở top-level (N1 N2 S"x" ...) ---
+
+use bumpalo::Bump;
+
+use crate::{check_delimiter, parse_number_slice, FdonError, FdonNumber, FdonParser, FdonValue, ParseOptions};
+
+/// Iterator returned by `parse_scalar_stream`.
+pub struct ScalarStream<'a, 'bump> {
+    parser: FdonParser<'a, 'bump>,
+    done: bool,
+}
+
+/// Parses a top-level sequence of scalars (`N1 N2 T"12:00" Btrue U ...`) without a
+/// wrapping array or newline framing, yielding each value in order.
+///
+/// Separation rules:
+/// - `S`, `SE`, `D` and `T"..."` end at their closing delimiter, and `B`/`U` have a
+///   fixed length, so the next value may follow directly (`S"a"S"b"`, `UN1`).
+/// - Numbers (`N` and numeric `T`) have no terminator of their own and run to the
+///   next whitespace, so they must be followed by whitespace or end of input.
+///   `N1N2` is `FdonError::UnseparatedValues` at the second `N`.
+///
+/// `O`/`A` containers are rejected with `FdonError::NotAScalar`. The input is not
+/// minified: whitespace between values is skipped, whitespace inside strings is kept.
+/// The iterator stops after the first error.
+#[inline]
+pub fn parse_scalar_stream<'a, 'bump>(input: &'a str, arena: &'bump Bump) -> ScalarStream<'a, 'bump> {
+    parse_scalar_stream_with_options(input, arena, ParseOptions::default())
+}
+
+/// Same as `parse_scalar_stream`, with explicit `ParseOptions`.
+pub fn parse_scalar_stream_with_options<'a, 'bump>(
+    input: &'a str,
+    arena: &'bump Bump,
+    options: ParseOptions,
+) -> ScalarStream<'a, 'bump> {
+    ScalarStream {
+        parser: FdonParser::with_options(input, arena, options),
+        done: false,
+    }
+}
+
+#[inline(always)]
+fn is_whitespace(b: u8) -> bool {
+    matches!(b, b' ' | b'\n' | b'\r' | b'\t')
+}
+
+impl<'a, 'bump> ScalarStream<'a, 'bump> {
+    fn next_value(&mut self) -> Result<FdonValue<'a, 'bump>, FdonError> {
+        let p = &mut self.parser;
+        let start = p.index;
+        match p.data[start] {
+            b'O' | b'A' => Err(FdonError::NotAScalar { pos: start }),
+            b'N' => self.parse_number(start + 1).map(FdonValue::Number),
+            b'T' if p.data.get(start + 1) != Some(&p.options.string_delimiter) => {
+                self.parse_number(start + 1).map(FdonValue::Timestamp)
+            }
+            _ => p.parse_value(),
+        }
+    }
+
+    // Số chạy tới khoảng trắng kế tiếp; gặp type tag giữa chừng -> hai value dính nhau
+    fn parse_number(&mut self, start: usize) -> Result<FdonNumber, FdonError> {
+        let p = &mut self.parser;
+        let rest = &p.data[start..];
+        let len = rest.iter().position(|&b| is_whitespace(b)).unwrap_or(rest.len());
+        let token = &rest[..len];
+        if let Some(i) = token.iter().position(|b| b"OASDTNBU".contains(b)) {
+            return Err(FdonError::UnseparatedValues { pos: start + i });
+        }
+        p.index = start + len;
+        parse_number_slice(token, start, &p.options)
+    }
+}
+
+impl<'a: 'bump, 'bump> Iterator for ScalarStream<'a, 'bump> {
+    type Item = Result<FdonValue<'a, 'bump>, FdonError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Err(e) = check_delimiter(self.parser.options.string_delimiter) {
+            self.done = true;
+            return Some(Err(e));
+        }
+        let p = &mut self.parser;
+        while p.index < p.data.len() && is_whitespace(p.data[p.index]) {
+            p.index += 1;
+        }
+        if p.index == p.data.len() {
+            self.done = true;
+            return None;
+        }
+        let result = self.next_value();
+        if result.is_err() {
+            self.done = true;
+        }
+        Some(result)
+    }
+}
@@ -0,0 +1,118 @@
+// --- Serialize có tag kiểu (giữ phân biệt Date / Time / SE ... khi qua JSON) ---
+
+use bumpalo::{
+    Bump,
+    collections::Vec as BumpVec,
+};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::Value;
+
+use crate::{FdonNumber, FdonObject, FdonStr, FdonValue};
+
+/// Serializes a value with its FDON type spelled out, so it survives a trip through
+/// JSON: every value becomes `{"type": ..., "value": ...}`, e.g.
+/// `{"type":"date","value":"2023-01-01"}`.
+///
+/// Types: `null` (no `value`), `bool`, `number`, `timestamp` (numeric `T`),
+/// `string` (`S`), `escaped_string` (`SE`), `date`, `time` (`T"..."`), `array`
+/// (value: array of tagged values) and `object` (value: map of tagged values).
+/// Numbers keep their JSON form, so `N5` and `N5.0` stay distinct; NaN and
+/// infinities become `null` and do not round-trip. Read back with `from_tagged_json`.
+///
+/// Plain `Serialize` on `FdonValue` stays untagged.
+#[derive(Debug, Clone, Copy)]
+pub struct Tagged<'r, 'a, 'bump>(pub &'r FdonValue<'a, 'bump>);
+
+impl Serialize for Tagged<'_, '_, '_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (tag, len) = match self.0 {
+            FdonValue::Null => ("null", 1),
+            FdonValue::Bool(_) => ("bool", 2),
+            FdonValue::Number(_) => ("number", 2),
+            FdonValue::Timestamp(_) => ("timestamp", 2),
+            FdonValue::RawString(_) => ("string", 2),
+            FdonValue::EscapedString(_) => ("escaped_string", 2),
+            FdonValue::Date(_) => ("date", 2),
+            FdonValue::Time(_) => ("time", 2),
+            FdonValue::Array(_) => ("array", 2),
+            FdonValue::Object(_) => ("object", 2),
+        };
+        let mut map = serializer.serialize_map(Some(len))?;
+        map.serialize_entry("type", tag)?;
+        match self.0 {
+            FdonValue::Null => {}
+            FdonValue::Bool(b) => map.serialize_entry("value", b)?,
+            FdonValue::Number(n) | FdonValue::Timestamp(n) => map.serialize_entry("value", n)?,
+            FdonValue::RawString(s) | FdonValue::Date(s) | FdonValue::Time(s) => {
+                map.serialize_entry("value", s)?
+            }
+            FdonValue::EscapedString(s) => map.serialize_entry("value", s.as_str())?,
+            FdonValue::Array(arr) => map.serialize_entry("value", &TaggedArray(arr))?,
+            FdonValue::Object(obj) => map.serialize_entry("value", &TaggedObject(obj))?,
+        }
+        map.end()
+    }
+}
+
+struct TaggedArray<'r, 'a, 'bump>(&'r [FdonValue<'a, 'bump>]);
+
+impl Serialize for TaggedArray<'_, '_, '_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(Tagged))
+    }
+}
+
+struct TaggedObject<'r, 'a, 'bump>(&'r FdonObject<'a, 'bump>);
+
+impl Serialize for TaggedObject<'_, '_, '_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k, Tagged(v))))
+    }
+}
+
+/// Rebuilds a `FdonValue` from the JSON produced by `Tagged`. Strings and keys
+/// borrow from `json`; containers go into `arena`.
+/// Returns `None` if `json` is not in the tagged form.
+pub fn from_tagged_json<'a, 'bump>(json: &'a Value, arena: &'bump Bump) -> Option<FdonValue<'a, 'bump>> {
+    let obj = json.as_object()?;
+    let value = obj.get("value");
+    Some(match obj.get("type")?.as_str()? {
+        "null" => FdonValue::Null,
+        "bool" => FdonValue::Bool(value?.as_bool()?),
+        "number" => FdonValue::Number(number_from_json(value?)?),
+        "timestamp" => FdonValue::Timestamp(number_from_json(value?)?),
+        "string" => FdonValue::RawString(value?.as_str()?),
+        "escaped_string" => FdonValue::EscapedString(FdonStr::Borrowed(value?.as_str()?)),
+        "date" => FdonValue::Date(value?.as_str()?),
+        "time" => FdonValue::Time(value?.as_str()?),
+        "array" => {
+            let items = value?.as_array()?;
+            let mut out = BumpVec::with_capacity_in(items.len(), arena);
+            for item in items {
+                out.push(from_tagged_json(item, arena)?);
+            }
+            FdonValue::Array(out)
+        }
+        "object" => {
+            let entries = value?.as_object()?;
+            let mut out = FdonObject::with_capacity_in(entries.len(), arena);
+            for (k, v) in entries {
+                out.insert(k.as_str(), from_tagged_json(v, arena)?);
+            }
+            FdonValue::Object(out)
+        }
+        _ => return None,
+    })
+}
+
+// Giữ đúng dạng số của JSON: 5 -> Integer, 5.0 -> Float, > i64::MAX -> UInt
+fn number_from_json(v: &Value) -> Option<FdonNumber> {
+    let n = v.as_number()?;
+    if let Some(i) = n.as_i64() {
+        Some(FdonNumber::Integer(i))
+    } else if let Some(u) = n.as_u64() {
+        Some(FdonNumber::UInt(u))
+    } else {
+        n.as_f64().map(FdonNumber::Float)
+    }
+}
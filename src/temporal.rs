@@ -0,0 +1,97 @@
+// --- Date/Time: tách thành phần từ D"YYYY-MM-DD" / T"HH:MM:SS[.fff]" (không cấp phát) ---
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+
+#[cfg(feature = "chrono")]
+use crate::{FdonNumber, FdonValue};
+
+// Nội dung T"..." dài nhất hợp lệ: `HH:MM:SS.fffffffff`
+pub(crate) const MAX_TIME_LEN: usize = 18;
+
+// `YYYY-MM-DD` -> (năm, tháng, ngày); chỉ kiểm tra hình dạng, không kiểm tra khoảng
+fn date_parts(b: &[u8]) -> Option<(i32, u32, u32)> {
+    if b.len() != 10 || b[4] != b'-' || b[7] != b'-' {
+        return None;
+    }
+    Some((digits(&b[..4])? as i32, digits(&b[5..7])?, digits(&b[8..])?))
+}
+
+// `HH:MM:SS` với phần thập phân tùy chọn (1-9 chữ số) -> (giờ, phút, giây, nano giây)
+fn time_parts(b: &[u8]) -> Option<(u32, u32, u32, u32)> {
+    if b.len() < 8 || b[2] != b':' || b[5] != b':' {
+        return None;
+    }
+    let nanos = match &b[8..] {
+        [] => 0,
+        [b'.', frac @ ..] if (1..=9).contains(&frac.len()) => digits(frac)? * 10u32.pow(9 - frac.len() as u32),
+        _ => return None,
+    };
+    Some((digits(&b[..2])?, digits(&b[3..5])?, digits(&b[6..8])?, nanos))
+}
+
+// Tối đa 9 chữ số ASCII -> u32 (không tràn)
+fn digits(b: &[u8]) -> Option<u32> {
+    b.iter().try_fold(0u32, |n, &c| c.is_ascii_digit().then(|| n * 10 + u32::from(c - b'0')))
+}
+
+/// `YYYY-MM-DD` naming a day that exists (proleptic Gregorian, so `2024-02-29`
+/// passes and `2023-02-29` does not).
+pub(crate) fn is_valid_date(b: &[u8]) -> bool {
+    let Some((y, m, d)) = date_parts(b) else { return false };
+    let leap = y % 4 == 0 && (y % 100 != 0 || y % 400 == 0);
+    let days = match m {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&d)
+}
+
+/// `HH:MM:SS` (24-hour, no leap second) with an optional 1-9 digit fraction.
+pub(crate) fn is_valid_time(b: &[u8]) -> bool {
+    matches!(time_parts(b), Some((h, m, s, _)) if h < 24 && m < 60 && s < 60)
+}
+
+// --- Đọc thành kiểu chrono (feature "chrono") ---
+
+#[cfg(feature = "chrono")]
+impl FdonValue<'_, '_> {
+    /// A `D"YYYY-MM-DD"` value as a calendar date; `None` for other variants, other
+    /// layouts or impossible dates (`D"2023-02-29"`, month 13, day 32).
+    pub fn as_naive_date(&self) -> Option<NaiveDate> {
+        let FdonValue::Date(s) = self else { return None };
+        let (y, m, d) = date_parts(s.as_bytes())?;
+        NaiveDate::from_ymd_opt(y, m, d)
+    }
+
+    /// A `T"HH:MM:SS"` value (optionally with up to 9 fractional digits, `T"08:30:00.25"`)
+    /// as a time of day; `None` for other variants, other layouts or out-of-range fields.
+    pub fn as_naive_time(&self) -> Option<NaiveTime> {
+        let FdonValue::Time(s) = self else { return None };
+        let (h, m, sec, nanos) = time_parts(s.as_bytes())?;
+        NaiveTime::from_hms_nano_opt(h, m, sec, nanos)
+    }
+
+    /// A numeric `T` value read as Unix seconds (a float keeps its fraction, rounded
+    /// to nanoseconds); `None` for other variants or instants chrono cannot represent.
+    pub fn as_datetime_utc(&self) -> Option<DateTime<Utc>> {
+        let FdonValue::Timestamp(n) = self else { return None };
+        match *n {
+            FdonNumber::Integer(secs) => DateTime::from_timestamp(secs, 0),
+            FdonNumber::UInt(secs) => DateTime::from_timestamp(i64::try_from(secs).ok()?, 0),
+            FdonNumber::Float(f) => {
+                if !f.is_finite() || f.abs() >= i64::MAX as f64 {
+                    return None;
+                }
+                let secs = f.floor();
+                // Làm tròn có thể đẩy phần lẻ lên đúng 1 giây
+                let nanos = ((f - secs) * 1e9).round() as u32;
+                let (secs, nanos) = if nanos >= 1_000_000_000 { (secs + 1.0, 0) } else { (secs, nanos) };
+                DateTime::from_timestamp(secs as i64, nanos)
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Arena instrumentation for tests and benchmarks (enabled by the `test-util` feature).
+//!
+//! `bumpalo` does not expose a per-allocation hook, so the arena numbers reported here
+//! are measured from its chunks: how many bytes were bumped and how many chunks were
+//! requested from the global allocator. Heap allocations made outside the arena (work
+//! stacks, owned results) are counted by `CountingAllocator`. For a fixed input and a
+//! fixed initial capacity these numbers are fully deterministic, which is what tests need.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::ops::Deref;
+
+use bumpalo::Bump;
+
+/// Snapshot of how much memory an arena has handed out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ArenaStats {
+    /// Bytes actually bumped (values, containers, unescaped strings, padding).
+    pub used_bytes: usize,
+    /// Bytes reserved from the global allocator across all chunks.
+    pub reserved_bytes: usize,
+    /// Number of chunks the arena allocated. Every chunk after the first is a
+    /// real call into the global allocator.
+    pub chunks: usize,
+}
+
+impl ArenaStats {
+    /// Difference between two snapshots (`self` taken after `earlier`).
+    #[inline]
+    pub fn since(&self, earlier: &ArenaStats) -> ArenaStats {
+        ArenaStats {
+            used_bytes: self.used_bytes.saturating_sub(earlier.used_bytes),
+            reserved_bytes: self.reserved_bytes.saturating_sub(earlier.reserved_bytes),
+            chunks: self.chunks.saturating_sub(earlier.chunks),
+        }
+    }
+}
+
+/// A `Bump` wrapper that can be queried for allocation statistics after a parse.
+///
+/// Derefs to `Bump`, so it can be passed anywhere the parser expects `&Bump`:
+///
+/// ```
+/// use fdon_rs::parse_fdon_zero_copy_arena;
+/// use fdon_rs::test_util::TrackingArena;
+///
+/// let arena = TrackingArena::with_capacity(4096);
+/// let value = parse_fdon_zero_copy_arena("A[SE\"a\\nb\"]", &arena)?;
+/// assert_eq!(value.as_array().map(|a| a.len()), Some(1));
+/// assert_eq!(arena.stats().chunks, 1);
+/// # Ok::<(), fdon_rs::FdonError>(())
+/// ```
+#[derive(Default)]
+pub struct TrackingArena {
+    bump: Bump,
+}
+
+impl TrackingArena {
+    #[inline]
+    pub fn new() -> Self {
+        TrackingArena { bump: Bump::new() }
+    }
+
+    /// Pre-sizes the first chunk so small documents never hit the global allocator
+    /// mid-parse, keeping `chunks` stable across runs.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        TrackingArena { bump: Bump::with_capacity(capacity) }
+    }
+
+    #[inline]
+    pub fn bump(&self) -> &Bump {
+        &self.bump
+    }
+
+    /// Current statistics for the whole lifetime of the arena (or since the last `reset`).
+    pub fn stats(&self) -> ArenaStats {
+        let mut used_bytes = 0;
+        let mut chunks = 0;
+        // SAFETY: we only read the (ptr, len) pairs to sum lengths; no chunk memory is
+        // dereferenced and no allocation happens while the iterator is alive.
+        unsafe {
+            for (_, len) in self.bump.iter_allocated_chunks_raw() {
+                used_bytes += len;
+                chunks += 1;
+            }
+        }
+        ArenaStats {
+            used_bytes,
+            reserved_bytes: self.bump.allocated_bytes(),
+            chunks,
+        }
+    }
+
+    /// Frees everything allocated so far and keeps the largest chunk for reuse.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.bump.reset();
+    }
+}
+
+impl Deref for TrackingArena {
+    type Target = Bump;
+
+    #[inline]
+    fn deref(&self) -> &Bump {
+        &self.bump
+    }
+}
+
+// --- Đếm số lần cấp phát heap (global allocator) ---
+
+thread_local! {
+    // Theo từng thread để các test chạy song song không đếm lẫn của nhau
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static ALLOCATED_BYTES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A global allocator that forwards to `System` and counts, per thread, every
+/// allocation (and reallocation) and the bytes requested.
+///
+/// Install it in a test or benchmark binary, then wrap the code to measure in
+/// `count_allocations`:
+///
+/// ```
+/// use fdon_rs::parse_fdon_zero_copy_arena;
+/// use fdon_rs::test_util::{count_allocations, CountingAllocator, TrackingArena};
+///
+/// #[global_allocator]
+/// static ALLOC: CountingAllocator = CountingAllocator;
+///
+/// let arena = TrackingArena::with_capacity(4096);
+/// let (value, allocs) = count_allocations(|| parse_fdon_zero_copy_arena("S\"x\"", &arena));
+/// assert!(value.is_ok());
+/// // Zero-copy scalar: nothing allocated, not even in the arena
+/// assert_eq!(allocs.count, 0);
+/// ```
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        unsafe { System.alloc(layout) }
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[inline]
+fn record(size: usize) {
+    // try_with: thread đang bị hủy thì bỏ qua thay vì panic trong allocator
+    let _ = ALLOCATIONS.try_with(|c| c.set(c.get() + 1));
+    let _ = ALLOCATED_BYTES.try_with(|c| c.set(c.get() + size));
+}
+
+/// Heap allocations made by the current thread, as counted by `CountingAllocator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocCount {
+    /// Calls to `alloc`, `alloc_zeroed` and `realloc`.
+    pub count: usize,
+    /// Bytes requested by those calls (the new size for `realloc`).
+    pub bytes: usize,
+}
+
+/// Runs `f` and returns its result with the heap allocations it made on this thread.
+///
+/// Counts are only recorded while `CountingAllocator` is the global allocator;
+/// otherwise they are always zero.
+pub fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, AllocCount) {
+    let before = (ALLOCATIONS.with(Cell::get), ALLOCATED_BYTES.with(Cell::get));
+    let result = f();
+    let count = AllocCount {
+        count: ALLOCATIONS.with(Cell::get) - before.0,
+        bytes: ALLOCATED_BYTES.with(Cell::get) - before.1,
+    };
+    (result, count)
+}
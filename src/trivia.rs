@@ -0,0 +1,244 @@
+// --- Trivia: vị trí comment trong input gốc (cho formatter giữ comment) ---
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use bumpalo::Bump;
+use memchr::memchr;
+
+use crate::value::push_pointer_token;
+use crate::{check_delimiter, comment_len, directive, Container, FdonError, FdonParser, ParseOptions};
+
+/// Which comment syntax a `Trivia` uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    /// `# ...` or `// ...` (the range stops before the newline)
+    Line,
+    /// `/* ... */`
+    Block,
+}
+
+/// Where a comment sits relative to the surrounding tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaPlacement {
+    /// On the same line as, and after, the preceding token (`N1, // note`):
+    /// belongs to the value before it.
+    Trailing,
+    /// On a line of its own, or before any token: belongs to what follows.
+    Leading,
+}
+
+/// One comment in an un-minified document; `start..end` is its byte range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub placement: TriviaPlacement,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Comments (`#`, `//`, `/* */`) in `input`, in order, with byte ranges into
+/// `input` itself (the un-minified text, not the output of `minify_fdon`).
+///
+/// A formatter can minify and parse with `allow_comments`, then reinsert each
+/// comment next to the token it was attached to. Strings (using
+/// `options.string_delimiter`) are skipped, so comment markers inside them are not
+/// reported. The parser has no span mode; `to_fdon_string_preserving` maps each
+/// comment to the path of its value, otherwise compare offsets.
+pub fn collect_trivia(input: &str, options: &ParseOptions) -> Vec<Trivia> {
+    let delim = options.string_delimiter;
+    let bytes = input.as_bytes();
+    let mut out = Vec::new();
+    // Vị trí ngay sau token cuối cùng (không tính whitespace / comment)
+    let mut last_token_end: Option<usize> = None;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if matches!(byte, b' ' | b'\n' | b'\r' | b'\t') {
+            i += 1;
+            continue;
+        }
+
+        // Chuỗi S"..", D"..", T"..", SE".." và key "..": nhảy qua nguyên chuỗi
+        let quoted_key = last_token_end.is_some_and(|end| matches!(bytes[end - 1], b'{' | b','));
+        if byte == delim && (quoted_key || i > 0 && matches!(bytes[i - 1], b'S' | b'D' | b'T' | b'E')) {
+            let escaped = bytes[i - 1] == b'E' && i > 1 && bytes[i - 2] == b'S';
+            i = if escaped {
+                skip_escaped(bytes, i + 1, delim)
+            } else {
+                memchr(delim, &bytes[i + 1..]).map_or(bytes.len(), |p| i + 1 + p + 1)
+            };
+            last_token_end = Some(i);
+            continue;
+        }
+
+        if let Some(len) = comment_len(&bytes[i..]) {
+            let kind = if bytes[i..].starts_with(b"/*") { TriviaKind::Block } else { TriviaKind::Line };
+            let placement = match last_token_end {
+                Some(end) if memchr(b'\n', &bytes[end..i]).is_none() => TriviaPlacement::Trailing,
+                _ => TriviaPlacement::Leading,
+            };
+            out.push(Trivia { kind, placement, start: i, end: i + len });
+            i += len;
+            continue;
+        }
+
+        i += 1;
+        last_token_end = Some(i);
+    }
+    out
+}
+
+// --- Gắn comment vào value (cho to_fdon_string_preserving) ---
+
+// Comment của một value, theo JSON Pointer path của nó trong tài liệu gốc
+#[derive(Debug, Default)]
+pub(crate) struct Attached<'s> {
+    // Trên các dòng riêng, ngay trước entry (trước key nếu là object entry)
+    pub(crate) before: Vec<&'s str>,
+    // Cùng dòng, sau value (và sau dấu ',')
+    pub(crate) after: Vec<&'s str>,
+    // Trước dấu đóng của container này
+    pub(crate) before_close: Vec<&'s str>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Comments<'s> {
+    pub(crate) by_path: HashMap<String, Attached<'s>>,
+    // Trên các dòng riêng sau value gốc
+    pub(crate) end: Vec<&'s str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Before,
+    After,
+    BeforeClose,
+}
+
+/// Ties every comment of `source` to the path of the value it precedes or trails.
+pub(crate) fn attach_comments<'s>(source: &'s str, options: &ParseOptions) -> Result<Comments<'s>, FdonError> {
+    let trivia = collect_trivia(source, options);
+
+    // Thay comment bằng khoảng trắng (giữ nguyên offset) rồi đi qua cấu trúc
+    let mut blanked = source.as_bytes().to_vec();
+    for t in &trivia {
+        blanked[t.start..t.end].fill(b' ');
+    }
+    let blanked = String::from_utf8(blanked).expect("comments replaced by ASCII spaces");
+    let anchors = collect_anchors(&blanked, ParseOptions { allow_whitespace: true, ..*options })?;
+
+    let mut comments = Comments::default();
+    for t in &trivia {
+        let text = &source[t.start..t.end];
+        // Trailing: gắn vào value vừa kết thúc ngay trước nó (nếu token trước đó là dấu
+        // mở hay key thì coi như leading)
+        let before = anchors.partition_point(|(pos, ..)| *pos <= t.start);
+        if t.placement == TriviaPlacement::Trailing
+            && let Some((_, Slot::After, path)) = before.checked_sub(1).map(|i| &anchors[i])
+        {
+            comments.by_path.entry(path.clone()).or_default().after.push(text);
+            continue;
+        }
+        // Leading: gắn vào token kế tiếp (entry hoặc dấu đóng)
+        match anchors[before..].iter().find(|(_, slot, _)| *slot != Slot::After) {
+            Some((_, Slot::BeforeClose, path)) => {
+                comments.by_path.entry(path.clone()).or_default().before_close.push(text)
+            }
+            Some((_, _, path)) => comments.by_path.entry(path.clone()).or_default().before.push(text),
+            None => comments.end.push(text),
+        }
+    }
+    Ok(comments)
+}
+
+// (offset, slot, path) của mọi token có thể mang comment, theo thứ tự trong input
+fn collect_anchors(input: &str, options: ParseOptions) -> Result<Vec<(usize, Slot, String)>, FdonError> {
+    // Chỉ skip_value / parse_key_into: không cấp phát gì trong Arena
+    let arena = Bump::new();
+    check_delimiter(options.string_delimiter)?;
+    let mut parser = FdonParser::with_options(input, &arena, options);
+    if options.allow_version_directive {
+        parser.index = directive::check_directive(parser.data).map_err(|e| parser.decorate(e))?;
+    }
+    parser.skip_whitespace();
+    if matches!(parser.peek(), Some(b'{' | b'[' | b'"')) {
+        return Err(parser.decorate(FdonError::LooksLikeJson { pos: parser.index }));
+    }
+
+    let mut anchors = Vec::new();
+    walk(&mut parser, &mut String::new(), &mut anchors).map_err(|e| parser.decorate(e))?;
+    parser.skip_whitespace();
+    if parser.index != parser.data.len() {
+        return Err(parser.decorate(FdonError::ExtraData { pos: parser.index }));
+    }
+    Ok(anchors)
+}
+
+fn walk(
+    parser: &mut FdonParser<'_, '_>,
+    path: &mut String,
+    anchors: &mut Vec<(usize, Slot, String)>,
+) -> Result<(), FdonError> {
+    parser.skip_whitespace();
+    anchors.push((parser.index, Slot::Before, path.clone()));
+    let len = path.len();
+    match parser.peek() {
+        Some(b'O') => {
+            parser.enter(parser.index)?;
+            parser.advance();
+            parser.skip_whitespace();
+            parser.consume(b'{')?;
+            parser.skip_whitespace();
+            while parser.peek() != Some(b'}') {
+                let key_pos = parser.index;
+                let mut unescaped = String::new();
+                let key = parser.parse_key_into(&mut unescaped)?;
+                parser.consume(b':')?;
+                path.push('/');
+                push_pointer_token(path, key.unwrap_or(&unescaped));
+                anchors.push((key_pos, Slot::Before, path.clone()));
+                walk(parser, path, anchors)?;
+                path.truncate(len);
+                parser.skip_separator(b'}', Container::Object)?;
+            }
+            anchors.push((parser.index, Slot::BeforeClose, path.clone()));
+            parser.advance();
+            parser.leave();
+        }
+        Some(b'A') => {
+            parser.enter(parser.index)?;
+            parser.advance();
+            parser.skip_whitespace();
+            parser.consume(b'[')?;
+            parser.skip_whitespace();
+            let mut i = 0;
+            while parser.peek() != Some(b']') {
+                let _ = write!(path, "/{}", i);
+                walk(parser, path, anchors)?;
+                path.truncate(len);
+                parser.skip_separator(b']', Container::Array)?;
+                i += 1;
+            }
+            anchors.push((parser.index, Slot::BeforeClose, path.clone()));
+            parser.advance();
+            parser.leave();
+        }
+        _ => parser.skip_value()?,
+    }
+    anchors.push((parser.index, Slot::After, path.clone()));
+    Ok(())
+}
+
+// Vị trí ngay sau delimiter đóng của SE"..." (bắt đầu quét tại `i`)
+fn skip_escaped(bytes: &[u8], mut i: usize, delim: u8) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == delim => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
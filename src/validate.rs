@@ -0,0 +1,946 @@
+// --- Validator (máy trạng thái theo byte, không dựng cây) ---
+
+use std::io::{self, Read};
+
+use memchr::{memchr, memchr2, memchr3};
+
+use crate::directive::check_directive;
+use crate::temporal::{is_valid_date, is_valid_time, MAX_TIME_LEN};
+use crate::{
+    check_delimiter, decode_unicode_escape, is_known_escape, parse_number_slice, Container, FdonError, ParseOptions,
+    UnicodeEscapeError,
+};
+
+// Kích thước buffer khi đọc từ Reader
+pub(crate) const READ_CHUNK: usize = 64 * 1024;
+
+// Escape dài nhất: cặp surrogate `\uD83D\uDE00`
+const MAX_UNICODE_ESCAPE: usize = 12;
+
+// Cách feed_inner xử lý cuối chunk / cuối value gốc
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeedMode {
+    /// Còn chunk sau (feed)
+    Chunk,
+    /// Dừng ngay sau value gốc thay vì báo ExtraData (feed_frame)
+    Frame,
+    /// Chunk cuối cùng: số ở cuối được kiểm tra tại chỗ, không copy vào `number_buf`
+    Last,
+}
+
+// Stack container dạng bit (1 = Object): 128 tầng đầu nằm trong `inline`, chỉ sâu
+// hơn mới cấp phát (với max_depth mặc định thì không bao giờ)
+#[derive(Default)]
+pub(crate) struct ContainerStack {
+    len: usize,
+    inline: u128,
+    spill: Vec<Container>,
+}
+
+impl ContainerStack {
+    const INLINE: usize = u128::BITS as usize;
+
+    #[inline]
+    pub(crate) fn push(&mut self, container: Container) {
+        if self.len < Self::INLINE {
+            let bit = 1u128 << self.len;
+            if container == Container::Object {
+                self.inline |= bit;
+            } else {
+                self.inline &= !bit;
+            }
+        } else {
+            self.spill.push(container);
+        }
+        self.len += 1;
+    }
+
+    #[inline]
+    pub(crate) fn pop(&mut self) {
+        if self.len > Self::INLINE {
+            self.spill.pop();
+        }
+        self.len = self.len.saturating_sub(1);
+    }
+
+    #[inline]
+    pub(crate) fn last(&self) -> Option<Container> {
+        match self.len {
+            0 => None,
+            n if n > Self::INLINE => self.spill.last().copied(),
+            n if self.inline >> (n - 1) & 1 == 1 => Some(Container::Object),
+            _ => Some(Container::Array),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+// Kiểm tra UTF-8 theo luồng cho nội dung key/chuỗi (validate_utf8)
+#[derive(Default)]
+struct Utf8Check {
+    /// Ký tự nhiều byte bị cắt ngang giữa hai chunk (bắt đầu tại `pending_pos`)
+    pending: [u8; 4],
+    pending_len: usize,
+    pending_pos: usize,
+    /// Lỗi đầu tiên trong đoạn hiện tại; như parser, chỉ báo khi đoạn kết thúc
+    error: Option<usize>,
+}
+
+impl Utf8Check {
+    // `piece` bắt đầu tại offset tuyệt đối `pos`
+    fn feed(&mut self, mut piece: &[u8], mut pos: usize) {
+        if self.error.is_some() {
+            return;
+        }
+        if self.pending_len > 0 {
+            let width = utf8_width(self.pending[0]);
+            let take = (width - self.pending_len).min(piece.len());
+            self.pending[self.pending_len..self.pending_len + take].copy_from_slice(&piece[..take]);
+            self.pending_len += take;
+            if self.pending_len < width {
+                return;
+            }
+            self.pending_len = 0;
+            if std::str::from_utf8(&self.pending[..width]).is_err() {
+                self.error = Some(self.pending_pos);
+                return;
+            }
+            piece = &piece[take..];
+            pos += take;
+        }
+        if let Err(e) = std::str::from_utf8(piece) {
+            let bad = e.valid_up_to();
+            if e.error_len().is_none() {
+                // Có thể được nối tiếp ở chunk sau
+                self.pending[..piece.len() - bad].copy_from_slice(&piece[bad..]);
+                self.pending_len = piece.len() - bad;
+                self.pending_pos = pos + bad;
+            } else {
+                self.error = Some(pos + bad);
+            }
+        }
+    }
+
+    // Đoạn kết thúc: một ký tự còn dở cũng là lỗi
+    fn end(&mut self) -> Result<(), FdonError> {
+        if self.pending_len > 0 && self.error.is_none() {
+            self.error = Some(self.pending_pos);
+        }
+        self.pending_len = 0;
+        match self.error.take() {
+            Some(pos) => Err(FdonError::InvalidUtf8 { pos }),
+            None => Ok(()),
+        }
+    }
+}
+
+// Số byte của ký tự UTF-8 theo byte đầu (cùng quy tắc với FdonParser cho `\<ký tự>`)
+fn utf8_width(lead: u8) -> usize {
+    match lead {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Cần một type tag
+    Value,
+    /// Sau 'O' / 'A': cần '{' / '['
+    ObjectOpen,
+    ArrayOpen,
+    /// Sau 'S': 'E' (SE"...") hoặc delimiter (S"...")
+    AfterS,
+    /// Sau 'SE': cần delimiter
+    AfterSE,
+    /// Sau 'T': delimiter (T"...") hoặc số
+    AfterT,
+    /// Sau 'D': cần delimiter
+    RawOpen,
+    /// Trong S"..." / D"..." / T"..." (nội dung bắt đầu tại `token_start`)
+    Raw,
+    /// Trong SE"..." (chunk hiện tại bắt đầu tại `token_start`)
+    Escaped,
+    /// Ngay sau '\' trong SE"..."
+    EscapedBackslash,
+    /// Trong escape `\u...` (gom vào `escape_buf`, '\' tại `token_start`)
+    UnicodeEscape,
+    /// Ký tự nhiều byte sau '\' (validate_utf8), gom trong `utf8`
+    EscapedChar,
+    /// Trong số (bắt đầu tại `token_start`), kết thúc bởi ',' '}' ']' hoặc EOF
+    Number,
+    /// Sau 'B' (đã khớp `bool_len` byte, bắt đầu tại `token_start`)
+    Bool,
+    /// Đầu tài liệu, có thể là chỉ thị `%fdon N` (gom vào `number_buf`)
+    Directive,
+    /// Trong JSON keyword `keyword` (đã khớp `bool_len` byte, bắt đầu tại `token_start`)
+    Keyword,
+    /// Ngay sau '{': '}' hoặc key
+    ObjectFirst,
+    /// Ngay sau '[': ']' hoặc value
+    ArrayFirst,
+    /// Trong key (bắt đầu tại `token_start`), kết thúc bởi ':'
+    Key,
+    /// Sau 'S' ở đầu key: delimiter (key S"..."), 'E' hoặc phần còn lại của key thường
+    KeyAfterS,
+    /// Sau 'SE' ở đầu key: delimiter (key SE"...") hoặc phần còn lại của key thường
+    KeyAfterSE,
+    /// Sau key trong dấu nháy: cần ':'
+    KeyColon,
+    /// Sau một value: ',' hoặc dấu đóng (hoặc EOF nếu ở gốc)
+    AfterValue,
+    /// Sau ',': key/value tiếp theo (dấu đóng ngay sau đây là trailing comma)
+    AfterComma,
+}
+
+/// Incremental FDON syntax checker that never builds a tree.
+///
+/// Feed it the (minified) document in chunks of any size; tokens may span chunk
+/// boundaries. It stops at the first error, which is the one (with the same
+/// absolute byte offset) the parser would report on the whole buffer with the same
+/// options, except for two options the Validator ignores:
+/// - `allow_whitespace`: whitespace between tokens is still an error here
+///   (validate minified input, or the output of `minify_fdon`);
+/// - `reject_duplicate_keys`: a repeated key is accepted here (`lint_duplicate_keys`
+///   finds them without building a tree).
+pub struct Validator {
+    options: ParseOptions,
+    state: State,
+    stack: ContainerStack,
+    /// Offset tuyệt đối của byte đầu tiên trong chunk hiện tại
+    offset: usize,
+    token_start: usize,
+    /// Đầu nội dung SE"..." (token_start bị dời sau mỗi escape), cho max_token_scan
+    scan_start: usize,
+    /// Số bị cắt ngang giữa hai chunk được gom vào đây
+    number_buf: Vec<u8>,
+    /// Escape `\u...` đang đọc dở (không cấp phát)
+    escape_buf: [u8; MAX_UNICODE_ESCAPE],
+    escape_len: usize,
+    bool_buf: [u8; 5],
+    bool_len: usize,
+    keyword: &'static [u8],
+    /// Tag của chuỗi đang đọc: `S`, `D`, `T` hoặc `K` (key trong dấu nháy, kể cả SE"...")
+    raw_tag: u8,
+    /// Offset của key trong dấu nháy đang đọc (dấu nháy mở hoặc 'S')
+    key_start: usize,
+    /// Nội dung D"..." / T"..." bị cắt ngang giữa các chunk (strict_date_time);
+    /// `date_time_len` vẫn đếm tiếp khi vượt quá buffer (khi đó chắc chắn sai)
+    date_time_buf: [u8; MAX_TIME_LEN],
+    date_time_len: usize,
+    utf8: Utf8Check,
+    /// Offset của value gốc (sau chỉ thị `%fdon`, nếu có)
+    root_start: usize,
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Validator {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_options(ParseOptions::default())
+    }
+
+    #[inline]
+    pub fn with_options(options: ParseOptions) -> Self {
+        Validator {
+            options,
+            state: if options.allow_version_directive { State::Directive } else { State::Value },
+            stack: ContainerStack::default(),
+            offset: 0,
+            token_start: 0,
+            scan_start: 0,
+            number_buf: Vec::new(),
+            escape_buf: [0; MAX_UNICODE_ESCAPE],
+            escape_len: 0,
+            bool_buf: [0; 5],
+            bool_len: 0,
+            keyword: b"",
+            raw_tag: b'S',
+            key_start: 0,
+            date_time_buf: [0; MAX_TIME_LEN],
+            date_time_len: 0,
+            utf8: Utf8Check::default(),
+            root_start: 0,
+        }
+    }
+
+    /// Absolute number of bytes consumed so far.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Validates the next chunk of the document.
+    #[inline]
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<(), FdonError> {
+        self.feed_inner(chunk, FeedMode::Chunk).map(|_| ())
+    }
+
+    /// Like `feed`, but stops right after the root value instead of reporting
+    /// `ExtraData`, and returns how many bytes of `chunk` were consumed.
+    #[inline]
+    pub(crate) fn feed_frame(&mut self, chunk: &[u8]) -> Result<usize, FdonError> {
+        self.feed_inner(chunk, FeedMode::Frame)
+    }
+
+    /// `feed` of the final chunk followed by `finish`. Never allocates for documents
+    /// up to 128 levels deep without a version directive.
+    #[inline]
+    pub(crate) fn feed_last(mut self, chunk: &[u8]) -> Result<(), FdonError> {
+        self.feed_inner(chunk, FeedMode::Last)?;
+        self.finish()
+    }
+
+    /// The root value has ended (a root number only ends at `finish`).
+    #[inline]
+    pub(crate) fn root_complete(&self) -> bool {
+        self.state == State::AfterValue && self.stack.is_empty()
+    }
+
+    /// Lower bound on the bytes still needed to complete the root value: what the
+    /// current token needs at minimum, plus one closing byte per open container.
+    pub(crate) fn min_bytes_needed(&self) -> usize {
+        let token = match self.state {
+            State::AfterValue | State::ObjectFirst | State::ArrayFirst => 0,
+            // `U`
+            State::Value | State::Directive => 1,
+            // `{}` / `[]` (chưa có trên stack)
+            State::ObjectOpen | State::ArrayOpen => 2,
+            // Dấu mở + dấu đóng chuỗi
+            State::AfterS | State::AfterSE | State::RawOpen => 2,
+            // Số: ít nhất một chữ số rồi dấu kết thúc
+            State::AfterT => 1,
+            State::Number | State::Raw | State::Escaped => 1,
+            State::EscapedBackslash => 2,
+            State::UnicodeEscape => 2,
+            State::EscapedChar => utf8_width(self.utf8.pending[0]) - self.utf8.pending_len + 1,
+            State::Bool => {
+                let seen = &self.bool_buf[..self.bool_len];
+                if b"true".starts_with(seen) { 4 - self.bool_len } else { 5 - self.bool_len }
+            }
+            State::Keyword => self.keyword.len() - self.bool_len,
+            // key (có thể rỗng) + ':' + `U`
+            State::Key => 2,
+            // delimiter đóng (nếu có) + ':' + `U`
+            State::KeyAfterS | State::KeyAfterSE => 2,
+            State::KeyColon => 2,
+            State::AfterComma => match self.stack.last() {
+                Some(Container::Object) => 2,
+                _ => 1,
+            },
+        };
+        (token + self.stack.len()).max(1)
+    }
+
+    fn feed_inner(&mut self, chunk: &[u8], mode: FeedMode) -> Result<usize, FdonError> {
+        let delim = self.options.string_delimiter;
+        check_delimiter(delim)?;
+        let base = self.offset;
+        let mut i = 0;
+
+        while i < chunk.len() {
+            let byte = chunk[i];
+            let pos = base + i;
+            match self.state {
+                State::Value => {
+                    if matches!(byte, b'O' | b'A') && self.stack.len() >= self.options.max_depth {
+                        return Err(FdonError::DepthLimitExceeded { pos });
+                    }
+                    i += 1;
+                    self.state = match byte {
+                        b'O' => State::ObjectOpen,
+                        b'A' => State::ArrayOpen,
+                        b'S' => State::AfterS,
+                        b'D' => State::RawOpen,
+                        b'T' => State::AfterT,
+                        b'N' => {
+                            self.token_start = pos + 1;
+                            State::Number
+                        }
+                        b'B' => {
+                            self.token_start = pos + 1;
+                            self.bool_len = 0;
+                            State::Bool
+                        }
+                        b'U' => State::AfterValue,
+                        b'n' | b't' | b'f' if self.options.allow_json_keywords => {
+                            self.token_start = pos;
+                            self.keyword = match byte {
+                                b'n' => b"null",
+                                b't' => b"true",
+                                _ => b"false",
+                            };
+                            self.bool_len = 1;
+                            State::Keyword
+                        }
+                        b'{' | b'[' | b'"' if pos == self.root_start => return Err(FdonError::LooksLikeJson { pos }),
+                        _ => return Err(FdonError::UnknownTypeSpecifier { byte, pos }),
+                    };
+                }
+                State::ObjectOpen | State::ArrayOpen => {
+                    let (open, container, next) = if self.state == State::ObjectOpen {
+                        (b'{', Container::Object, State::ObjectFirst)
+                    } else {
+                        (b'[', Container::Array, State::ArrayFirst)
+                    };
+                    if byte != open {
+                        return Err(FdonError::Expected { expected: open, found: Some(byte), pos });
+                    }
+                    self.stack.push(container);
+                    self.state = next;
+                    i += 1;
+                }
+                State::AfterS => {
+                    if byte == b'E' {
+                        self.state = State::AfterSE;
+                    } else if byte == delim {
+                        self.token_start = pos + 1;
+                        self.raw_tag = b'S';
+                        self.state = State::Raw;
+                    } else {
+                        return Err(FdonError::Expected { expected: delim, found: Some(byte), pos });
+                    }
+                    i += 1;
+                }
+                State::AfterSE => {
+                    if byte != delim {
+                        return Err(FdonError::ExpectedDelimiterAfterSE { delimiter: delim, pos });
+                    }
+                    self.token_start = pos + 1;
+                    self.scan_start = pos + 1;
+                    self.raw_tag = b'S';
+                    self.state = State::Escaped;
+                    i += 1;
+                }
+                State::AfterT => {
+                    if byte == delim {
+                        self.token_start = pos + 1;
+                        self.raw_tag = b'T';
+                        self.state = State::Raw;
+                        i += 1;
+                    } else {
+                        // T... dạng số: byte này thuộc về số, xử lý lại ở trạng thái Number
+                        self.token_start = pos;
+                        self.state = State::Number;
+                    }
+                }
+                State::RawOpen => {
+                    if byte != delim {
+                        return Err(FdonError::Expected { expected: delim, found: Some(byte), pos });
+                    }
+                    self.token_start = pos + 1;
+                    self.raw_tag = b'D';
+                    self.state = State::Raw;
+                    i += 1;
+                }
+                State::Raw => match self.find_raw_end(&chunk[i..]) {
+                    Some(p) => {
+                        self.check_token_len(self.token_start, base + i + p)?;
+                        if chunk[i + p] != delim {
+                            return Err(FdonError::LiteralNewline { pos: base + i + p });
+                        }
+                        if self.options.validate_utf8 {
+                            self.utf8.feed(&chunk[i..i + p], base + i);
+                            self.utf8.end()?;
+                        }
+                        if self.checks_date_time() {
+                            self.check_date_time(&chunk[i..i + p])?;
+                        }
+                        i += p + 1;
+                        self.state = if self.raw_tag == b'K' {
+                            if self.options.forbid_empty_keys && base + i - 1 == self.token_start {
+                                return Err(FdonError::EmptyKey { pos: self.key_start });
+                            }
+                            State::KeyColon
+                        } else {
+                            State::AfterValue
+                        };
+                    }
+                    None => {
+                        self.check_token_len(self.token_start, base + chunk.len())?;
+                        if self.options.validate_utf8 {
+                            self.utf8.feed(&chunk[i..], base + i);
+                        }
+                        if self.checks_date_time() {
+                            self.push_date_time(&chunk[i..]);
+                        }
+                        i = chunk.len();
+                    }
+                },
+                State::Escaped => match self.find_escape_or_end(&chunk[i..]) {
+                    Some(p) => {
+                        self.check_token_len(self.scan_start, base + i + p)?;
+                        if chunk[i + p] == b'\n' {
+                            return Err(FdonError::LiteralNewline { pos: base + i + p });
+                        }
+                        if self.options.validate_utf8 {
+                            self.utf8.feed(&chunk[i..i + p], base + i);
+                            self.utf8.end()?;
+                        }
+                        i += p + 1;
+                        self.state = if chunk[i - 1] != delim {
+                            State::EscapedBackslash
+                        } else if self.raw_tag == b'K' {
+                            if self.options.forbid_empty_keys && base + i - 1 == self.scan_start {
+                                return Err(FdonError::EmptyKey { pos: self.key_start });
+                            }
+                            State::KeyColon
+                        } else {
+                            State::AfterValue
+                        };
+                    }
+                    None => {
+                        self.check_token_len(self.scan_start, base + chunk.len())?;
+                        if self.options.validate_utf8 {
+                            self.utf8.feed(&chunk[i..], base + i);
+                        }
+                        i = chunk.len();
+                    }
+                },
+                State::EscapedBackslash if byte == b'\n' && self.options.forbid_literal_newlines_in_strings => {
+                    return Err(FdonError::LiteralNewline { pos });
+                }
+                State::EscapedBackslash if byte == delim => {
+                    i += 1;
+                    self.token_start = pos + 1;
+                    self.state = State::Escaped;
+                }
+                State::EscapedBackslash if byte == b'u' => {
+                    self.escape_buf[..2].copy_from_slice(b"\\u");
+                    self.escape_len = 2;
+                    self.token_start = pos - 1;
+                    self.state = State::UnicodeEscape;
+                    i += 1;
+                }
+                State::UnicodeEscape => {
+                    // decode_unicode_escape quyết định xong trong tối đa MAX_UNICODE_ESCAPE byte
+                    self.escape_buf[self.escape_len] = byte;
+                    self.escape_len += 1;
+                    match decode_unicode_escape(&self.escape_buf[..self.escape_len]) {
+                        Ok(_) => {
+                            self.escape_len = 0;
+                            self.token_start = pos + 1;
+                            self.state = State::Escaped;
+                        }
+                        Err(UnicodeEscapeError::Incomplete) => {}
+                        Err(UnicodeEscapeError::Invalid) => {
+                            return Err(FdonError::InvalidUnicodeEscape { pos: self.token_start });
+                        }
+                    }
+                    i += 1;
+                }
+                State::EscapedBackslash => {
+                    // Escape lạ chỉ là lỗi khi bật strict_escapes (mặc định: giữ nguyên ký tự)
+                    if self.options.strict_escapes && !is_known_escape(byte, delim) {
+                        return Err(FdonError::InvalidEscape { ch: byte, pos: pos - 1 });
+                    }
+                    if byte >= 0x80 && self.options.validate_utf8 {
+                        // Parser lấy trọn ký tự sau '\' và kiểm tra ngay
+                        self.state = State::EscapedChar;
+                        continue;
+                    }
+                    i += 1;
+                    self.token_start = pos + 1;
+                    self.state = State::Escaped;
+                }
+                State::EscapedChar => {
+                    self.utf8.feed(&[byte], pos);
+                    if self.utf8.error.is_some() || self.utf8.pending_len == 0 {
+                        self.utf8.end()?;
+                        self.token_start = pos + 1;
+                        self.state = State::Escaped;
+                    }
+                    i += 1;
+                }
+                State::Number => match memchr3(b',', b'}', b']', &chunk[i..]) {
+                    Some(p) => {
+                        let end = i + p;
+                        self.check_token_len(self.token_start, base + end)?;
+                        if self.number_buf.is_empty() {
+                            // Số nằm trọn trong chunk: kiểm tra trực tiếp, không copy
+                            let from = self.token_start.saturating_sub(base);
+                            parse_number_slice(&chunk[from..end], self.token_start, &self.options)?;
+                        } else {
+                            self.number_buf.extend_from_slice(&chunk[i..end]);
+                            parse_number_slice(&self.number_buf, self.token_start, &self.options)?;
+                            self.number_buf.clear();
+                        }
+                        // Dấu kết thúc không thuộc về số: xử lý lại ở AfterValue
+                        i = end;
+                        self.state = State::AfterValue;
+                    }
+                    None if mode == FeedMode::Last && self.number_buf.is_empty() => {
+                        // Số kết thúc bởi EOF, nằm trọn trong chunk cuối
+                        self.check_token_len(self.token_start, base + chunk.len())?;
+                        let from = self.token_start.saturating_sub(base);
+                        parse_number_slice(&chunk[from..], self.token_start, &self.options)?;
+                        i = chunk.len();
+                        self.state = State::AfterValue;
+                    }
+                    None => {
+                        self.check_token_len(self.token_start, base + chunk.len())?;
+                        let from = if self.number_buf.is_empty() {
+                            self.token_start.saturating_sub(base)
+                        } else {
+                            i
+                        };
+                        self.number_buf.extend_from_slice(&chunk[from..]);
+                        i = chunk.len();
+                    }
+                },
+                State::Bool => {
+                    self.bool_buf[self.bool_len] = byte;
+                    self.bool_len += 1;
+                    let seen = &self.bool_buf[..self.bool_len];
+                    if seen == b"true" || seen == b"false" {
+                        self.state = State::AfterValue;
+                    } else if !b"true".starts_with(seen) && !b"false".starts_with(seen) {
+                        return Err(FdonError::InvalidBool { pos: self.token_start });
+                    }
+                    i += 1;
+                }
+                State::Directive => {
+                    let buf = &self.number_buf;
+                    if buf.is_empty() && byte != b'%' {
+                        self.state = State::Value;
+                        continue;
+                    }
+                    if buf.len() < 5 {
+                        if byte != b"%fdon"[buf.len()] {
+                            return Err(FdonError::InvalidDirective { pos: 0 });
+                        }
+                    } else if !directive_accepts(&buf[5..], byte) {
+                        // Hết chỉ thị: kiểm tra rồi xử lý lại byte này như value gốc
+                        check_directive(buf)?;
+                        self.number_buf.clear();
+                        self.root_start = pos;
+                        self.state = State::Value;
+                        continue;
+                    }
+                    self.number_buf.push(byte);
+                    i += 1;
+                }
+                State::Keyword => {
+                    if byte != self.keyword[self.bool_len] {
+                        return Err(FdonError::UnknownTypeSpecifier { byte: self.keyword[0], pos: self.token_start });
+                    }
+                    self.bool_len += 1;
+                    if self.bool_len == self.keyword.len() {
+                        self.state = State::AfterValue;
+                    }
+                    i += 1;
+                }
+                State::ObjectFirst => {
+                    if byte == b'}' {
+                        self.stack.pop();
+                        self.state = State::AfterValue;
+                        i += 1;
+                    } else {
+                        self.token_start = pos;
+                        self.state = State::Key;
+                    }
+                }
+                State::ArrayFirst => {
+                    if byte == b']' {
+                        self.stack.pop();
+                        self.state = State::AfterValue;
+                        i += 1;
+                    } else {
+                        self.state = State::Value;
+                    }
+                }
+                // Key trong dấu nháy: đọc như S"..." rồi cần ':'
+                State::Key if pos == self.token_start && (byte == delim || byte == b'S') => {
+                    self.key_start = pos;
+                    if byte == delim {
+                        self.token_start = pos + 1;
+                        self.raw_tag = b'K';
+                        self.state = State::Raw;
+                    } else {
+                        self.state = State::KeyAfterS;
+                    }
+                    i += 1;
+                }
+                State::KeyAfterS => {
+                    if byte == delim {
+                        self.token_start = pos + 1;
+                        self.raw_tag = b'K';
+                        self.state = State::Raw;
+                        i += 1;
+                    } else if byte == b'E' {
+                        self.state = State::KeyAfterSE;
+                        i += 1;
+                    } else {
+                        // Key thường bắt đầu bằng 'S': byte này được xử lý lại ở trạng thái Key
+                        self.state = State::Key;
+                    }
+                }
+                // Key SE"...": escape như value SE"..." rồi cần ':'
+                State::KeyAfterSE => {
+                    if byte == delim {
+                        self.token_start = pos + 1;
+                        self.scan_start = pos + 1;
+                        self.raw_tag = b'K';
+                        self.state = State::Escaped;
+                        i += 1;
+                    } else {
+                        // Key thường bắt đầu bằng 'SE'
+                        self.state = State::Key;
+                    }
+                }
+                State::KeyColon => {
+                    if byte != b':' {
+                        return Err(FdonError::Expected { expected: b':', found: Some(byte), pos });
+                    }
+                    self.state = State::Value;
+                    i += 1;
+                }
+                State::Key => match memchr(b':', &chunk[i..]) {
+                    Some(p) => {
+                        if self.options.forbid_empty_keys && base + i + p == self.token_start {
+                            return Err(FdonError::EmptyKey { pos: self.token_start });
+                        }
+                        self.check_token_len(self.token_start, base + i + p)?;
+                        if self.options.validate_utf8 {
+                            self.utf8.feed(&chunk[i..i + p], base + i);
+                            self.utf8.end()?;
+                        }
+                        i += p + 1;
+                        self.state = State::Value;
+                    }
+                    None => {
+                        self.check_token_len(self.token_start, base + chunk.len())?;
+                        if self.options.validate_utf8 {
+                            self.utf8.feed(&chunk[i..], base + i);
+                        }
+                        i = chunk.len();
+                    }
+                },
+                State::AfterValue => {
+                    let Some(top) = self.stack.last() else {
+                        if mode == FeedMode::Frame {
+                            self.offset += i;
+                            return Ok(i);
+                        }
+                        return Err(FdonError::ExtraData { pos });
+                    };
+                    match (byte, top) {
+                        (b',', _) => self.state = State::AfterComma,
+                        (b'}', Container::Object) | (b']', Container::Array) => {
+                            self.stack.pop();
+                        }
+                        _ => return Err(FdonError::MissingComma { container: top, pos }),
+                    }
+                    i += 1;
+                }
+                State::AfterComma => {
+                    let top = self.stack.last().expect("comma outside container");
+                    let close = if top == Container::Object { b'}' } else { b']' };
+                    if byte == close {
+                        return Err(FdonError::TrailingComma { container: top, pos });
+                    }
+                    if top == Container::Object {
+                        self.token_start = pos;
+                        self.state = State::Key;
+                    } else {
+                        self.state = State::Value;
+                    }
+                }
+            }
+        }
+
+        self.offset += chunk.len();
+        Ok(chunk.len())
+    }
+
+    /// Signals end of input and reports anything left unterminated.
+    pub fn finish(mut self) -> Result<(), FdonError> {
+        let pos = self.offset;
+        let delim = self.options.string_delimiter;
+        check_delimiter(delim)?;
+        match self.state {
+            State::Value | State::ArrayFirst => Err(FdonError::UnexpectedEof { pos }),
+            State::Directive if self.number_buf.is_empty() => Err(FdonError::UnexpectedEof { pos }),
+            State::Directive if self.number_buf.len() < 5 => Err(FdonError::InvalidDirective { pos: 0 }),
+            State::Directive => {
+                check_directive(&self.number_buf)?;
+                Err(FdonError::UnexpectedEof { pos })
+            }
+            State::ObjectOpen => Err(FdonError::Expected { expected: b'{', found: None, pos }),
+            State::ArrayOpen => Err(FdonError::Expected { expected: b'[', found: None, pos }),
+            State::AfterS | State::RawOpen => Err(FdonError::Expected { expected: delim, found: None, pos }),
+            State::AfterSE => Err(FdonError::ExpectedDelimiterAfterSE { delimiter: delim, pos }),
+            State::AfterT => Err(FdonError::EmptyNumber { pos }),
+            State::Raw => Err(FdonError::UnterminatedString { escaped: false, pos: self.token_start }),
+            State::Escaped => {
+                // Các byte escape cuối cùng chưa được đo trong feed
+                self.check_token_len(self.scan_start, pos)?;
+                Err(FdonError::UnterminatedString { escaped: true, pos: self.token_start })
+            }
+            State::EscapedBackslash => Err(FdonError::EofAfterEscape { pos }),
+            State::UnicodeEscape => Err(FdonError::InvalidUnicodeEscape { pos: self.token_start }),
+            // Ký tự sau '\' bị cắt bởi EOF
+            State::EscapedChar => Err(FdonError::InvalidUtf8 { pos: self.utf8.pending_pos }),
+            State::Number => {
+                // EOF cũng kết thúc số
+                let number = std::mem::take(&mut self.number_buf);
+                parse_number_slice(&number, self.token_start, &self.options)?;
+                self.end_of_value_at_eof(pos)
+            }
+            State::Bool => Err(FdonError::InvalidBool { pos: self.token_start }),
+            State::Keyword => Err(FdonError::UnknownTypeSpecifier { byte: self.keyword[0], pos: self.token_start }),
+            State::ObjectFirst => Err(FdonError::UnterminatedKey { pos }),
+            State::Key => Err(FdonError::UnterminatedKey { pos: self.token_start }),
+            State::KeyAfterS | State::KeyAfterSE => {
+                // 'S' / 'SE' cuối cùng là key thường chưa được đo trong feed
+                self.check_token_len(self.token_start, pos)?;
+                Err(FdonError::UnterminatedKey { pos: self.token_start })
+            }
+            State::KeyColon => Err(FdonError::Expected { expected: b':', found: None, pos }),
+            State::AfterComma => match self.stack.last() {
+                Some(Container::Object) => Err(FdonError::UnterminatedKey { pos }),
+                _ => Err(FdonError::UnexpectedEof { pos }),
+            },
+            State::AfterValue => self.end_of_value_at_eof(pos),
+        }
+    }
+
+    // Cùng cách tìm dấu kết thúc chuỗi như FdonParser (kể cả '\n' trần khi bị cấm)
+    #[inline]
+    fn find_raw_end(&self, haystack: &[u8]) -> Option<usize> {
+        let delim = self.options.string_delimiter;
+        if self.options.forbid_literal_newlines_in_strings {
+            memchr2(delim, b'\n', haystack)
+        } else {
+            memchr(delim, haystack)
+        }
+    }
+
+    #[inline]
+    fn find_escape_or_end(&self, haystack: &[u8]) -> Option<usize> {
+        let delim = self.options.string_delimiter;
+        if self.options.forbid_literal_newlines_in_strings {
+            memchr3(b'\\', delim, b'\n', haystack)
+        } else {
+            memchr2(b'\\', delim, haystack)
+        }
+    }
+
+    #[inline]
+    fn checks_date_time(&self) -> bool {
+        self.options.strict_date_time && matches!(self.raw_tag, b'D' | b'T')
+    }
+
+    // Gom một phần nội dung D"..." / T"..." (chuỗi còn tiếp ở chunk sau)
+    fn push_date_time(&mut self, piece: &[u8]) {
+        let end = self.date_time_len.saturating_add(piece.len());
+        if end <= MAX_TIME_LEN {
+            self.date_time_buf[self.date_time_len..end].copy_from_slice(piece);
+        }
+        self.date_time_len = end;
+    }
+
+    // `tail`: phần cuối nội dung (trước delimiter); nằm trọn trong chunk thì kiểm tra tại chỗ
+    fn check_date_time(&mut self, tail: &[u8]) -> Result<(), FdonError> {
+        let is_valid = if self.raw_tag == b'D' { is_valid_date } else { is_valid_time };
+        let valid = if self.date_time_len == 0 {
+            is_valid(tail)
+        } else {
+            self.push_date_time(tail);
+            self.date_time_len <= MAX_TIME_LEN && is_valid(&self.date_time_buf[..self.date_time_len])
+        };
+        self.date_time_len = 0;
+        // Lỗi trỏ vào type tag (trước delimiter mở)
+        let pos = self.token_start - 2;
+        match (valid, self.raw_tag) {
+            (true, _) => Ok(()),
+            (false, b'D') => Err(FdonError::InvalidDate { pos }),
+            (false, _) => Err(FdonError::InvalidTime { pos }),
+        }
+    }
+
+    // Token bắt đầu tại `start` đã chạy tới `end` (chưa gồm dấu kết thúc): vượt max_token_scan?
+    #[inline]
+    fn check_token_len(&self, start: usize, end: usize) -> Result<(), FdonError> {
+        match self.options.max_token_scan {
+            Some(max) if end - start > max => Err(FdonError::TokenTooLong { pos: start }),
+            _ => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn end_of_value_at_eof(&self, pos: usize) -> Result<(), FdonError> {
+        match self.stack.last() {
+            None => Ok(()),
+            Some(container) => Err(FdonError::MissingComma { container, pos }),
+        }
+    }
+}
+
+// Byte tiếp theo có còn thuộc chỉ thị không (`rest` = phần sau "%fdon")
+fn directive_accepts(rest: &[u8], byte: u8) -> bool {
+    let has_digits = rest.iter().any(u8::is_ascii_digit);
+    let after_digits = has_digits && !rest.last().is_some_and(u8::is_ascii_digit);
+    match byte {
+        b'0'..=b'9' => !after_digits,
+        b' ' | b'\t' => true,
+        b'\r' | b'\n' => has_digits,
+        _ => false,
+    }
+}
+
+/// Checks that `input` is a well-formed (minified) FDON document without building it.
+#[inline]
+pub fn validate(input: &str) -> Result<(), FdonError> {
+    Validator::new().feed_last(input.as_bytes())
+}
+
+/// `validate(input).is_ok()`, for filtering many small inputs: inlined and, for
+/// documents up to 128 levels deep, allocation-free.
+#[inline]
+pub fn is_valid(input: &str) -> bool {
+    validate(input).is_ok()
+}
+
+/// Validates a (minified) FDON document from a reader in bounded memory.
+///
+/// Stops reading at the first error, which carries the absolute byte offset in the
+/// stream, so a huge file that is malformed near its start fails immediately.
+pub fn validate_reader<R: Read>(reader: R) -> Result<(), FdonError> {
+    validate_reader_with_options(reader, ParseOptions::default())
+}
+
+/// Same as `validate_reader`, with explicit `ParseOptions`.
+pub fn validate_reader_with_options<R: Read>(
+    mut reader: R,
+    options: ParseOptions,
+) -> Result<(), FdonError> {
+    let mut validator = Validator::with_options(options);
+    let mut buf = vec![0u8; READ_CHUNK];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(FdonError::Io(e)),
+        };
+        validator.feed(&buf[..n])?;
+    }
+    validator.finish()
+}
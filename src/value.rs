@@ -0,0 +1,1160 @@
+// --- Tiện ích trên cây FdonValue (đã parse xong, không cần parse lại) ---
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::{ControlFlow, Index};
+use std::fmt::Write;
+
+use bumpalo::{
+    Bump,
+    collections::String as BumpString,
+    collections::Vec as BumpVec,
+};
+
+use crate::{parse_number_slice, FdonArray, FdonNumber, FdonObject, FdonValue, ParseOptions};
+
+/// Size/shape statistics of an already-built value tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TreeMetrics {
+    /// Every value in the tree, containers included (the root counts as 1).
+    pub values: usize,
+    pub objects: usize,
+    pub arrays: usize,
+    /// Object entries across all objects.
+    pub keys: usize,
+    /// `N` and numeric `T` values.
+    pub numbers: usize,
+    /// `S`, `SE`, `D` and `T"..."` values.
+    pub strings: usize,
+    /// Payload bytes of those strings (after unescaping; keys not included).
+    pub string_bytes: usize,
+    /// Number of nested containers on the deepest path (`N1` = 0, `A[]` = 1, `A[O{}]` = 2).
+    pub max_depth: usize,
+}
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Walks the tree once and collects `TreeMetrics`.
+    pub fn metrics(&self) -> TreeMetrics {
+        let mut metrics = TreeMetrics::default();
+        self.collect_metrics(0, &mut metrics);
+        metrics
+    }
+
+    fn collect_metrics(&self, depth: usize, m: &mut TreeMetrics) {
+        m.values += 1;
+        match self {
+            FdonValue::Null | FdonValue::Bool(_) => {}
+            FdonValue::Number(_) | FdonValue::Timestamp(_) => m.numbers += 1,
+            FdonValue::RawString(s) | FdonValue::Date(s) | FdonValue::Time(s) => {
+                m.strings += 1;
+                m.string_bytes += s.len();
+            }
+            FdonValue::EscapedString(s) => {
+                m.strings += 1;
+                m.string_bytes += s.len();
+            }
+            FdonValue::Array(arr) => {
+                m.arrays += 1;
+                m.max_depth = m.max_depth.max(depth + 1);
+                for v in arr.iter() {
+                    v.collect_metrics(depth + 1, m);
+                }
+            }
+            FdonValue::Object(obj) => {
+                m.objects += 1;
+                m.keys += obj.len();
+                m.max_depth = m.max_depth.max(depth + 1);
+                for v in obj.values() {
+                    v.collect_metrics(depth + 1, m);
+                }
+            }
+        }
+    }
+}
+
+// --- Thứ tự toàn phần (numeric-aware) ---
+
+/// Compares two numbers by numeric value, so `Integer(1)` and `Float(1.0)` are equal.
+/// Floats use `f64::total_cmp` semantics: `-NaN` < every number < `NaN`.
+fn cmp_numbers(a: &FdonNumber, b: &FdonNumber) -> Ordering {
+    match (a, b) {
+        (FdonNumber::Float(x), FdonNumber::Float(y)) => x.total_cmp(y),
+        (FdonNumber::Float(x), _) => cmp_int_float(int_value(b), *x).reverse(),
+        (_, FdonNumber::Float(y)) => cmp_int_float(int_value(a), *y),
+        _ => int_value(a).cmp(&int_value(b)),
+    }
+}
+
+// Integer/UInt -> i128 (chứa trọn cả hai miền giá trị)
+#[inline]
+fn int_value(n: &FdonNumber) -> i128 {
+    match n {
+        FdonNumber::Integer(i) => *i as i128,
+        FdonNumber::UInt(u) => *u as i128,
+        FdonNumber::Float(_) => unreachable!("float passed as integer"),
+    }
+}
+
+// So sánh chính xác số nguyên với f64 (không ép sang f64 để tránh mất chính xác ngoài 2^53)
+#[inline]
+fn cmp_int_float(i: i128, f: f64) -> Ordering {
+    if f.is_nan() {
+        return if f.is_sign_negative() { Ordering::Greater } else { Ordering::Less };
+    }
+    // 2^127 là biên của i128
+    if f >= 1.7014118346046923e38 {
+        return Ordering::Less;
+    }
+    if f < -1.7014118346046923e38 {
+        return Ordering::Greater;
+    }
+    let truncated = f.trunc();
+    match i.cmp(&(truncated as i128)) {
+        Ordering::Equal => 0.0f64.total_cmp(&(f - truncated)),
+        other => other,
+    }
+}
+
+// --- So sánh bằng ---
+
+/// Equal numeric value, whatever the variant: `Integer(1) == UInt(1) == Float(1.0)`,
+/// compared exactly (no rounding through `f64`). `0.0 == -0.0`, and unlike `f64`,
+/// NaN equals NaN so that every value equals itself. `eq_strict` also requires the
+/// same variant.
+impl PartialEq for FdonNumber {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (FdonNumber::Float(x), FdonNumber::Float(y)) => x == y || (x.is_nan() && y.is_nan()),
+            _ => cmp_numbers(self, other) == Ordering::Equal,
+        }
+    }
+}
+
+/// Objects compare by key set and per-key value regardless of entry order, arrays
+/// element by element in order, numbers by numeric value (`N1 == N1.0`, see
+/// `FdonNumber`) and raw and escaped strings by content (`S"a" == SE"a"`). Other
+/// kinds never equal each other: `N5 != T5`, `S"2024-01-01" != D"2024-01-01"`.
+/// This is the same notion as comparing with an `OwnedFdonValue`; `eq_strict`
+/// compares variants exactly.
+impl PartialEq for FdonValue<'_, '_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (FdonValue::Null, FdonValue::Null) => true,
+            (FdonValue::Bool(a), FdonValue::Bool(b)) => a == b,
+            (FdonValue::Number(a), FdonValue::Number(b))
+            | (FdonValue::Timestamp(a), FdonValue::Timestamp(b)) => a == b,
+            (FdonValue::Date(a), FdonValue::Date(b)) | (FdonValue::Time(a), FdonValue::Time(b)) => a == b,
+            (FdonValue::Array(a), FdonValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| x == y)
+            }
+            (FdonValue::Object(a), FdonValue::Object(b)) => {
+                a.len() == b.len() && a.iter().all(|(k, v)| b.get(k).is_some_and(|w| v == w))
+            }
+            _ => match (self.string_content(), other.string_content()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl FdonNumber {
+    /// Same variant and same value: `Integer(1)` differs from `Float(1.0)` and
+    /// `UInt(1)`, and floats compare with `f64`'s `==` (NaN never equal).
+    #[inline]
+    pub fn eq_strict(&self, other: &FdonNumber) -> bool {
+        match (self, other) {
+            (FdonNumber::Integer(a), FdonNumber::Integer(b)) => a == b,
+            (FdonNumber::UInt(a), FdonNumber::UInt(b)) => a == b,
+            (FdonNumber::Float(a), FdonNumber::Float(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl FdonValue<'_, '_> {
+    /// Like `==`, but numbers must have the same variant (`FdonNumber::eq_strict`)
+    /// and a raw string never equals an escaped one. Object entry order is still
+    /// ignored and string content is compared, not whether it was borrowed.
+    pub fn eq_strict(&self, other: &FdonValue<'_, '_>) -> bool {
+        match (self, other) {
+            (FdonValue::Number(a), FdonValue::Number(b))
+            | (FdonValue::Timestamp(a), FdonValue::Timestamp(b)) => a.eq_strict(b),
+            (FdonValue::RawString(a), FdonValue::RawString(b)) => a == b,
+            (FdonValue::EscapedString(a), FdonValue::EscapedString(b)) => a == b,
+            (FdonValue::Array(a), FdonValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| x.eq_strict(y))
+            }
+            (FdonValue::Object(a), FdonValue::Object(b)) => {
+                a.len() == b.len() && a.iter().all(|(k, v)| b.get(k).is_some_and(|w| v.eq_strict(w)))
+            }
+            (FdonValue::RawString(_) | FdonValue::EscapedString(_), _)
+            | (_, FdonValue::RawString(_) | FdonValue::EscapedString(_)) => false,
+            _ => self == other,
+        }
+    }
+}
+
+// --- Số học trên FdonNumber ---
+
+impl FdonNumber {
+    /// Adds two numbers, promoting instead of failing.
+    ///
+    /// Any `Float` operand makes the result a `Float`. Otherwise the exact integer
+    /// sum is kept as `Integer` if it fits in `i64`, else `UInt` if it fits in `u64`
+    /// (the same rule the parser applies to literals), and only beyond that becomes
+    /// a `Float`.
+    pub fn checked_add(self, other: FdonNumber) -> FdonNumber {
+        match (self, other) {
+            (FdonNumber::Float(x), _) => FdonNumber::Float(x + other.to_f64()),
+            (_, FdonNumber::Float(y)) => FdonNumber::Float(self.to_f64() + y),
+            _ => {
+                // i128 chứa được tổng của hai số i64/u64 bất kỳ
+                let sum = int_value(&self) + int_value(&other);
+                if let Ok(i) = i64::try_from(sum) {
+                    FdonNumber::Integer(i)
+                } else if let Ok(u) = u64::try_from(sum) {
+                    FdonNumber::UInt(u)
+                } else {
+                    FdonNumber::Float(sum as f64)
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        match self {
+            FdonNumber::Integer(i) => i as f64,
+            FdonNumber::UInt(u) => u as f64,
+            FdonNumber::Float(f) => f,
+        }
+    }
+}
+
+/// Folds with `checked_add`; the empty sum is `Integer(0)`.
+impl std::iter::Sum for FdonNumber {
+    fn sum<I: Iterator<Item = FdonNumber>>(iter: I) -> Self {
+        iter.fold(FdonNumber::Integer(0), FdonNumber::checked_add)
+    }
+}
+
+impl<'n> std::iter::Sum<&'n FdonNumber> for FdonNumber {
+    fn sum<I: Iterator<Item = &'n FdonNumber>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    // Hạng của từng loại giá trị khi so sánh khác loại
+    #[inline]
+    fn kind_rank(&self) -> u8 {
+        match self {
+            FdonValue::Null => 0,
+            FdonValue::Bool(_) => 1,
+            FdonValue::Number(_) => 2,
+            FdonValue::Timestamp(_) => 3,
+            FdonValue::RawString(_) | FdonValue::EscapedString(_) => 4,
+            FdonValue::Date(_) => 5,
+            FdonValue::Time(_) => 6,
+            FdonValue::Array(_) => 7,
+            FdonValue::Object(_) => 8,
+        }
+    }
+
+    /// A deterministic total order over all values.
+    ///
+    /// Values of different kinds order as
+    /// `Null < Bool < Number < Timestamp < string < Date < Time < Array < Object`,
+    /// where raw (`S`) and escaped (`SE`) strings are the same kind and compare by content.
+    /// Within a kind: `false < true`; numbers by numeric value (`Integer(1) == Float(1.0)`,
+    /// with `f64::total_cmp`'s NaN placement: a negative NaN before every number, a
+    /// positive NaN after); strings, dates and times by bytes; arrays lexicographically by element;
+    /// objects lexicographically by their entries sorted by key (key first, then value).
+    pub fn total_cmp(&self, other: &FdonValue<'_, '_>) -> Ordering {
+        match (self, other) {
+            (FdonValue::Bool(a), FdonValue::Bool(b)) => a.cmp(b),
+            (FdonValue::Number(a), FdonValue::Number(b))
+            | (FdonValue::Timestamp(a), FdonValue::Timestamp(b)) => cmp_numbers(a, b),
+            (FdonValue::Date(a), FdonValue::Date(b)) | (FdonValue::Time(a), FdonValue::Time(b)) => {
+                a.cmp(b)
+            }
+            (FdonValue::Array(a), FdonValue::Array(b)) => {
+                for (x, y) in a.iter().zip(b.iter()) {
+                    let ord = x.total_cmp(y);
+                    if ord != Ordering::Equal {
+                        return ord;
+                    }
+                }
+                a.len().cmp(&b.len())
+            }
+            (FdonValue::Object(a), FdonValue::Object(b)) => {
+                let mut a: Vec<_> = a.iter().collect();
+                let mut b: Vec<_> = b.iter().collect();
+                a.sort_unstable_by_key(|(k, _)| **k);
+                b.sort_unstable_by_key(|(k, _)| **k);
+                for ((ka, va), (kb, vb)) in a.iter().zip(b.iter()) {
+                    let ord = ka.cmp(kb).then_with(|| va.total_cmp(vb));
+                    if ord != Ordering::Equal {
+                        return ord;
+                    }
+                }
+                a.len().cmp(&b.len())
+            }
+            _ => match (self.string_content(), other.string_content()) {
+                (Some(a), Some(b)) => a.cmp(b),
+                _ => self.kind_rank().cmp(&other.kind_rank()),
+            },
+        }
+    }
+
+    // Nội dung của S"..." / SE"..." (hai loại này so sánh như nhau)
+    #[inline]
+    pub(crate) fn string_content(&self) -> Option<&str> {
+        match self {
+            FdonValue::RawString(s) => Some(s),
+            FdonValue::EscapedString(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Sorts the elements of every array in the tree (nested ones included) by
+    /// `total_cmp`, so documents that differ only in array order become identical.
+    /// The sort is stable: elements that compare equal keep their relative order.
+    pub fn sort_arrays_recursive(&mut self) {
+        match self {
+            FdonValue::Array(arr) => {
+                for v in arr.iter_mut() {
+                    v.sort_arrays_recursive();
+                }
+                arr.sort_by(|a, b| a.total_cmp(b));
+            }
+            FdonValue::Object(obj) => {
+                for v in obj.values_mut() {
+                    v.sort_arrays_recursive();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// --- So sánh gần đúng (float) ---
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Structural equality that tolerates float error: numbers (and timestamps)
+    /// match if they differ by at most `epsilon` (absolute), integers compare exactly
+    /// with each other and within `epsilon` against floats. Everything else must be
+    /// equal as with `==`, except that `S` and `SE` strings compare by content.
+    /// Arrays match element by element, objects key by key (order ignored).
+    ///
+    /// NaN matches only NaN (so snapshots containing NaN still compare equal), and
+    /// an infinity only the same infinity. `N1` and `T1` do not match (different kinds).
+    pub fn approx_eq(&self, other: &FdonValue<'_, '_>, epsilon: f64) -> bool {
+        match (self, other) {
+            (FdonValue::Number(a), FdonValue::Number(b))
+            | (FdonValue::Timestamp(a), FdonValue::Timestamp(b)) => numbers_approx_eq(a, b, epsilon),
+            (FdonValue::Array(a), FdonValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| x.approx_eq(y, epsilon))
+            }
+            (FdonValue::Object(a), FdonValue::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(k, v)| b.get(k).is_some_and(|w| v.approx_eq(w, epsilon)))
+            }
+            (FdonValue::Null, FdonValue::Null) => true,
+            (FdonValue::Bool(a), FdonValue::Bool(b)) => a == b,
+            (FdonValue::Date(a), FdonValue::Date(b)) | (FdonValue::Time(a), FdonValue::Time(b)) => a == b,
+            _ => match (self.string_content(), other.string_content()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+fn numbers_approx_eq(a: &FdonNumber, b: &FdonNumber, epsilon: f64) -> bool {
+    match (a, b) {
+        (FdonNumber::Float(x), FdonNumber::Float(y)) => floats_approx_eq(*x, *y, epsilon),
+        (FdonNumber::Float(x), n) | (n, FdonNumber::Float(x)) => floats_approx_eq(*x, n.to_f64(), epsilon),
+        _ => int_value(a) == int_value(b),
+    }
+}
+
+#[inline]
+fn floats_approx_eq(x: f64, y: f64, epsilon: f64) -> bool {
+    // x == y bắt được inf == inf (inf - inf là NaN)
+    (x.is_nan() && y.is_nan()) || x == y || (x - y).abs() <= epsilon
+}
+
+// --- Loại giá trị & ép kiểu lỏng (coerce) ---
+
+/// The kind of a `FdonValue`, without its payload. Raw (`S`) and escaped (`SE`)
+/// strings are both `String`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    Null,
+    Bool,
+    Number,
+    Timestamp,
+    String,
+    Date,
+    Time,
+    Array,
+    Object,
+}
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    #[inline]
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            FdonValue::Null => ValueKind::Null,
+            FdonValue::Bool(_) => ValueKind::Bool,
+            FdonValue::Number(_) => ValueKind::Number,
+            FdonValue::Timestamp(_) => ValueKind::Timestamp,
+            FdonValue::RawString(_) | FdonValue::EscapedString(_) => ValueKind::String,
+            FdonValue::Date(_) => ValueKind::Date,
+            FdonValue::Time(_) => ValueKind::Time,
+            FdonValue::Array(_) => ValueKind::Array,
+            FdonValue::Object(_) => ValueKind::Object,
+        }
+    }
+
+    /// Loosely converts the value to `kind`, for forgiving config loaders.
+    ///
+    /// A value already of `kind` is copied as is (containers deeply, into `arena`).
+    /// Otherwise the rules are:
+    /// - `Bool`: strings `"true"`/`"false"` (exact spelling); `N` integers `1`/`0`.
+    /// - `Number`/`Timestamp`: the other numeric kind; `Bool` as `1`/`0`; a string that
+    ///   is a valid FDON number literal (e.g. `"5"`, `"-2.5"`, no surrounding spaces).
+    /// - `String`: dates and times (borrowed); numbers and timestamps formatted in
+    ///   `arena`; `Bool` as `"true"`/`"false"`.
+    /// - `Date`/`Time`: raw strings and the other of the two (borrowed, not validated).
+    ///
+    /// Everything else returns `None`: `Null` never converts to or from another kind,
+    /// containers never convert, escaped strings cannot become `Date`/`Time` (those
+    /// borrow from the input), and floats or integers other than `0`/`1` do not become
+    /// `Bool`.
+    pub fn coerce_to(&self, kind: ValueKind, arena: &'bump Bump) -> Option<FdonValue<'a, 'bump>> {
+        if self.kind() == kind {
+            return Some(self.clone_into(arena));
+        }
+        match (kind, self) {
+            (ValueKind::Bool, FdonValue::Number(FdonNumber::Integer(i))) if *i == 0 || *i == 1 => {
+                Some(FdonValue::Bool(*i == 1))
+            }
+            (ValueKind::Bool, _) => match self.string_content()? {
+                "true" => Some(FdonValue::Bool(true)),
+                "false" => Some(FdonValue::Bool(false)),
+                _ => None,
+            },
+            (ValueKind::Number | ValueKind::Timestamp, _) => {
+                let n = match self {
+                    FdonValue::Number(n) | FdonValue::Timestamp(n) => *n,
+                    FdonValue::Bool(b) => FdonNumber::Integer(*b as i64),
+                    _ => {
+                        let s = self.string_content()?;
+                        parse_number_slice(s.as_bytes(), 0, &ParseOptions::default()).ok()?
+                    }
+                };
+                Some(if kind == ValueKind::Number {
+                    FdonValue::Number(n)
+                } else {
+                    FdonValue::Timestamp(n)
+                })
+            }
+            (ValueKind::String, FdonValue::Date(s) | FdonValue::Time(s)) => Some(FdonValue::RawString(s)),
+            (ValueKind::String, FdonValue::Number(n) | FdonValue::Timestamp(n)) => {
+                let mut s = BumpString::new_in(arena);
+                // Float dùng Debug để luôn giữ dạng float (5.0 thay vì 5)
+                let _ = match n {
+                    FdonNumber::Integer(i) => write!(s, "{}", i),
+                    FdonNumber::UInt(u) => write!(s, "{}", u),
+                    FdonNumber::Float(f) => write!(s, "{:?}", f),
+                };
+                Some(FdonValue::EscapedString(s.into()))
+            }
+            (ValueKind::String, FdonValue::Bool(b)) => {
+                Some(FdonValue::RawString(if *b { "true" } else { "false" }))
+            }
+            (ValueKind::Date, FdonValue::RawString(s) | FdonValue::Time(s)) => Some(FdonValue::Date(s)),
+            (ValueKind::Time, FdonValue::RawString(s) | FdonValue::Date(s)) => Some(FdonValue::Time(s)),
+            _ => None,
+        }
+    }
+}
+
+// --- Duyệt & sửa cây tại chỗ ---
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Calls `f` on this value and then on every value below it, parents before
+    /// children. Children are visited after `f` has run on their parent, so
+    /// entries `f` removes or replaces are never descended into.
+    pub fn walk_mut<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&mut FdonValue<'a, 'bump>),
+    {
+        f(self);
+        match self {
+            FdonValue::Array(arr) => {
+                for v in arr.iter_mut() {
+                    v.walk_mut(f);
+                }
+            }
+            FdonValue::Object(obj) => {
+                for v in obj.values_mut() {
+                    v.walk_mut(f);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Keeps only the object entries for which `f` returns `true`.
+    /// Does nothing on non-objects and does not recurse.
+    pub fn retain_entries<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&str, &mut FdonValue<'a, 'bump>) -> bool,
+    {
+        if let FdonValue::Object(obj) = self {
+            obj.retain(|k, v| f(k, v));
+        }
+    }
+
+    /// Removes every object key starting with `prefix` (e.g. `"_"` or `"$"`), at
+    /// any depth. Removed subtrees are dropped whole, without being visited.
+    pub fn strip_keys_with_prefix(&mut self, prefix: &str) {
+        self.walk_mut(&mut |v| v.retain_entries(|k, _| !k.starts_with(prefix)));
+    }
+
+    /// Replaces every `N` number in the tree with `f(number)`, e.g. to scale values
+    /// or round floats. Numeric timestamps (`T...`) are left alone; see
+    /// `map_numbers_and_timestamps`.
+    pub fn map_numbers(&mut self, f: impl Fn(&FdonNumber) -> FdonNumber) {
+        self.walk_mut(&mut |v| {
+            if let FdonValue::Number(n) = v {
+                *n = f(n);
+            }
+        });
+    }
+
+    /// Like `map_numbers`, but numeric timestamps (`T...`) go through `f` too.
+    pub fn map_numbers_and_timestamps(&mut self, f: impl Fn(&FdonNumber) -> FdonNumber) {
+        self.walk_mut(&mut |v| {
+            if let FdonValue::Number(n) | FdonValue::Timestamp(n) = v {
+                *n = f(n);
+            }
+        });
+    }
+}
+
+// --- Gộp cây (merge) ---
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Deep-merges `overlay` onto `self` into a new value in `arena`.
+    ///
+    /// Objects are merged key by key, recursively; for anything else (arrays
+    /// included) the overlay value replaces the base one.
+    pub fn merge(&self, overlay: &FdonValue<'a, 'bump>, arena: &'bump Bump) -> FdonValue<'a, 'bump> {
+        match (self, overlay) {
+            (FdonValue::Object(base), FdonValue::Object(over)) => {
+                let mut out = FdonObject::with_capacity_in(base.len() + over.len(), arena);
+                for (k, v) in base.iter() {
+                    out.insert(k, v.clone_into(arena));
+                }
+                for (k, v) in over.iter() {
+                    let merged = match base.get(k) {
+                        Some(b) => b.merge(v, arena),
+                        None => v.clone_into(arena),
+                    };
+                    out.insert(k, merged);
+                }
+                FdonValue::Object(out)
+            }
+            _ => overlay.clone_into(arena),
+        }
+    }
+
+    /// Merges two arrays of objects by an identity field, e.g. `id`.
+    ///
+    /// Base elements keep their positions; an overlay element whose `key` field
+    /// equals (`total_cmp`, so `N1` matches `N1.0`) that of a not-yet-matched base
+    /// element is deep-merged into it with `merge`. Overlay elements that match
+    /// nothing, or lack the field, are appended in order. If either side is not an
+    /// array, the result is a copy of `overlay`.
+    pub fn merge_arrays_by_key(
+        base: &FdonValue<'a, 'bump>,
+        overlay: &FdonValue<'a, 'bump>,
+        key: &str,
+        arena: &'bump Bump,
+    ) -> FdonValue<'a, 'bump> {
+        let (FdonValue::Array(base), FdonValue::Array(over)) = (base, overlay) else {
+            return overlay.clone_into(arena);
+        };
+
+        let mut out = BumpVec::with_capacity_in(base.len() + over.len(), arena);
+        out.extend(base.iter().map(|v| v.clone_into(arena)));
+
+        // Chỉ mục base theo giá trị key (đã sort) -> tìm nhị phân thay vì O(n*m)
+        let mut index: Vec<(&FdonValue<'a, 'bump>, usize)> = base
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| v.get(key).map(|id| (id, i)))
+            .collect();
+        index.sort_by(|a, b| a.0.total_cmp(b.0).then(a.1.cmp(&b.1)));
+        let mut used = vec![false; base.len()];
+
+        for v in over.iter() {
+            let found = v.get(key).and_then(|id| {
+                let first = index.partition_point(|(k, _)| k.total_cmp(id) == Ordering::Less);
+                index[first..]
+                    .iter()
+                    .take_while(|(k, _)| k.total_cmp(id) == Ordering::Equal)
+                    .map(|&(_, i)| i)
+                    .find(|&i| !used[i])
+            });
+            match found {
+                Some(i) => {
+                    used[i] = true;
+                    out[i] = base[i].merge(v, arena);
+                }
+                None => out.push(v.clone_into(arena)),
+            }
+        }
+        FdonValue::Array(out)
+    }
+
+    /// Splits an object's entries across `n` new objects (deep copies in `arena`).
+    ///
+    /// Entry `key` goes to shard `fnv1a_64(key bytes) % n` (FNV-1a, 64-bit, standard
+    /// offset basis and prime, no seed), so the same key lands in the same shard on
+    /// every run and platform. Returns no shards if `self` is not an object or `n` is 0.
+    pub fn shard_object(&self, n: usize, arena: &'bump Bump) -> Vec<FdonValue<'a, 'bump>> {
+        let FdonValue::Object(obj) = self else { return Vec::new() };
+        if n == 0 {
+            return Vec::new();
+        }
+        let mut shards: Vec<FdonObject<'a, 'bump>> = (0..n)
+            .map(|_| FdonObject::with_capacity_in(obj.len() / n, arena))
+            .collect();
+        for (k, v) in obj.iter() {
+            let shard = (fnv1a(FNV_OFFSET, k.as_bytes()) % n as u64) as usize;
+            shards[shard].insert(k, v.clone_into(arena));
+        }
+        shards.into_iter().map(FdonValue::Object).collect()
+    }
+}
+
+// --- Đường dẫn (JSON Pointer, RFC 6901) ---
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Every value in the tree with its JSON Pointer path (RFC 6901: `""` for the
+    /// root, `/key/0` below it, `~` and `/` in keys escaped as `~0` and `~1`).
+    ///
+    /// Parents come before their children; object entries are listed in insertion
+    /// order (document order for a parsed value).
+    pub fn paths(&self) -> Vec<(String, &FdonValue<'a, 'bump>)> {
+        let mut out = Vec::new();
+        let _ = self.walk_paths(&mut String::new(), &mut |path, v| {
+            out.push((path.to_string(), v));
+            ControlFlow::<()>::Continue(())
+        });
+        out
+    }
+
+    /// Path of `target` inside `self`, if `target` is a reference to one of its
+    /// values (compared by address, not by content).
+    pub fn path_of(&self, target: &FdonValue<'_, '_>) -> Option<String> {
+        let target = target as *const FdonValue<'_, '_> as *const ();
+        match self.walk_paths(&mut String::new(), &mut |path, v| {
+            if std::ptr::eq(v as *const FdonValue<'_, '_> as *const (), target) {
+                ControlFlow::Break(path.to_string())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }) {
+            ControlFlow::Break(path) => Some(path),
+            ControlFlow::Continue(()) => None,
+        }
+    }
+
+    /// Value at JSON Pointer `path` (RFC 6901), e.g. `/user/roles/0/name`. `""` is
+    /// the whole document; `~1` and `~0` in a token stand for `/` and `~`. Arrays
+    /// take decimal indices without leading zeros. `None` if a segment is missing,
+    /// indexes a scalar, or `path` is malformed (no leading `/`, bad `~` escape).
+    /// A trailing `/` names the key `""`, as in the RFC.
+    pub fn pointer(&self, path: &str) -> Option<&FdonValue<'a, 'bump>> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        let mut current = self;
+        for token in path.strip_prefix('/')?.split('/') {
+            let token = unescape_pointer_token(token)?;
+            current = match current {
+                FdonValue::Object(obj) => obj.get(token.as_ref())?,
+                FdonValue::Array(arr) => {
+                    let valid = !token.is_empty()
+                        && token.bytes().all(|b| b.is_ascii_digit())
+                        && (token.len() == 1 || !token.starts_with('0'));
+                    if !valid {
+                        return None;
+                    }
+                    arr.get(token.parse::<usize>().ok()?)?
+                }
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    // Duyệt tiền thứ tự, `path` được dùng lại (push/truncate) để tránh cấp phát
+    fn walk_paths<'s, B, F>(&'s self, path: &mut String, f: &mut F) -> ControlFlow<B>
+    where
+        F: FnMut(&str, &'s FdonValue<'a, 'bump>) -> ControlFlow<B>,
+    {
+        f(path, self)?;
+        let len = path.len();
+        match self {
+            FdonValue::Array(arr) => {
+                for (i, v) in arr.iter().enumerate() {
+                    let _ = write!(path, "/{}", i);
+                    v.walk_paths(path, f)?;
+                    path.truncate(len);
+                }
+            }
+            FdonValue::Object(obj) => {
+                for (k, v) in obj.iter() {
+                    path.push('/');
+                    push_pointer_token(path, k);
+                    v.walk_paths(path, f)?;
+                    path.truncate(len);
+                }
+            }
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+// RFC 6901: "~1" -> '/', "~0" -> '~'; '~' theo sau bởi ký tự khác là pointer sai
+fn unescape_pointer_token(token: &str) -> Option<Cow<'_, str>> {
+    if !token.contains('~') {
+        return Some(Cow::Borrowed(token));
+    }
+    let mut out = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        if c == '~' {
+            match chars.next()? {
+                '0' => out.push('~'),
+                '1' => out.push('/'),
+                _ => return None,
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    Some(Cow::Owned(out))
+}
+
+// RFC 6901: '~' -> "~0", '/' -> "~1"
+pub(crate) fn push_pointer_token(path: &mut String, key: &str) {
+    for c in key.chars() {
+        match c {
+            '~' => path.push_str("~0"),
+            '/' => path.push_str("~1"),
+            c => path.push(c),
+        }
+    }
+}
+
+// --- Đọc field của object, có giá trị mặc định (config) ---
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Field `key` of an object, matching keys case-insensitively (full Unicode
+    /// lowercase; ASCII keys take a byte-wise fast path). Keys are not rewritten.
+    ///
+    /// An exact match wins. Otherwise, if several keys differ only in case
+    /// (`Name`, `NAME`), the one that comes first in insertion order is returned.
+    pub fn get_ci(&self, key: &str) -> Option<&FdonValue<'a, 'bump>> {
+        let FdonValue::Object(obj) = self else { return None };
+        if let Some(value) = obj.get(key) {
+            return Some(value);
+        }
+        obj.iter().find(|(k, _)| eq_ignore_case(k, key)).map(|(_, v)| v)
+    }
+
+    /// First element of an array of objects whose field `key` equals `value`, e.g. the
+    /// server entry with `name` `S"primary"`. Equality is `total_cmp`'s, so numbers
+    /// match by value (`N1` finds `N1.0`) and `S`/`SE` strings by content.
+    /// Non-object elements are skipped; `None` if `self` is not an array.
+    pub fn array_find_by(&self, key: &str, value: &FdonValue<'_, '_>) -> Option<&FdonValue<'a, 'bump>> {
+        let FdonValue::Array(arr) = self else { return None };
+        arr.iter()
+            .find(|item| item.get(key).is_some_and(|field| field.total_cmp(value).is_eq()))
+    }
+
+    /// String field `key` (`S` or `SE`), or `default` if `self` is not an object,
+    /// the key is absent, or the value is not a string.
+    pub fn get_str_or<'s>(&'s self, key: &str, default: &'s str) -> &'s str {
+        self.get(key).and_then(FdonValue::string_content).unwrap_or(default)
+    }
+
+    /// Integer field `key` (see `as_i64`), or `default` otherwise.
+    pub fn get_i64_or(&self, key: &str, default: i64) -> i64 {
+        self.get(key).and_then(FdonValue::as_i64).unwrap_or(default)
+    }
+
+    /// Unsigned field `key` (a non-negative integer `N`), or `default` otherwise.
+    pub fn get_u64_or(&self, key: &str, default: u64) -> u64 {
+        match self.get(key) {
+            Some(FdonValue::Number(FdonNumber::Integer(i))) => u64::try_from(*i).unwrap_or(default),
+            Some(FdonValue::Number(FdonNumber::UInt(u))) => *u,
+            _ => default,
+        }
+    }
+
+    /// Numeric field `key` as `f64` (see `as_f64`), or `default` otherwise.
+    pub fn get_f64_or(&self, key: &str, default: f64) -> f64 {
+        self.get(key).and_then(FdonValue::as_f64).unwrap_or(default)
+    }
+
+    /// Boolean field `key`, or `default` otherwise.
+    pub fn get_bool_or(&self, key: &str, default: bool) -> bool {
+        match self.get(key) {
+            Some(FdonValue::Bool(b)) => *b,
+            _ => default,
+        }
+    }
+
+    /// Copies a flat object into an owned `HashMap<String, String>` (`KEY=value`
+    /// style config). Strings, dates and times are taken as-is; numbers and
+    /// timestamps are formatted like `FdonNumber`'s `Display` and booleans as
+    /// `true`/`false`. `None` if `self` is not an object or any value is `U`, an
+    /// array or an object.
+    pub fn to_string_map(&self) -> Option<HashMap<String, String>> {
+        let FdonValue::Object(obj) = self else { return None };
+        let mut map = HashMap::with_capacity(obj.len());
+        for (k, v) in obj.iter() {
+            let value = match v {
+                FdonValue::RawString(s) | FdonValue::Date(s) | FdonValue::Time(s) => (*s).to_string(),
+                FdonValue::EscapedString(s) => s.as_str().to_string(),
+                FdonValue::Number(n) | FdonValue::Timestamp(n) => n.to_string(),
+                FdonValue::Bool(b) => b.to_string(),
+                FdonValue::Null | FdonValue::Array(_) | FdonValue::Object(_) => return None,
+            };
+            map.insert((*k).to_string(), value);
+        }
+        Some(map)
+    }
+}
+
+#[inline]
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+    if a.is_ascii() && b.is_ascii() {
+        return a.eq_ignore_ascii_case(b);
+    }
+    a.chars().flat_map(char::to_lowercase).eq(b.chars().flat_map(char::to_lowercase))
+}
+
+// --- Dấu vân tay cấu trúc (schema) ---
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// FNV-1a: cố định, không phụ thuộc seed như ahash -> giống nhau giữa các lần chạy
+#[inline]
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Hash of the document's shape: object keys and the `ValueKind` of every value,
+    /// recursively, ignoring scalar contents. Documents with the same schema hash
+    /// equal, so this can bucket heterogeneous documents.
+    ///
+    /// - Deterministic across runs and platforms (FNV-1a, no random seed); object
+    ///   keys are sorted first, so key order does not matter.
+    /// - Arrays hash the *set* of distinct element shapes: length, order and
+    ///   repetition are ignored (`A[N1,N2]` == `A[N7]`, `A[N1,S"x"]` == `A[S"x",N1]`),
+    ///   but an empty array differs from a non-empty one.
+    /// - `S` and `SE` are the same kind, and so are ints and floats.
+    pub fn structural_hash(&self) -> u64 {
+        let kind = self.kind() as u8;
+        let hash = fnv1a(FNV_OFFSET, &[kind]);
+        match self {
+            FdonValue::Object(obj) => {
+                let mut entries: Vec<(&str, u64)> =
+                    obj.iter().map(|(k, v)| (*k, v.structural_hash())).collect();
+                entries.sort_unstable();
+                entries.iter().fold(hash, |h, (k, child)| {
+                    let h = fnv1a(h, &(k.len() as u64).to_le_bytes());
+                    let h = fnv1a(h, k.as_bytes());
+                    fnv1a(h, &child.to_le_bytes())
+                })
+            }
+            FdonValue::Array(arr) => {
+                let mut shapes: Vec<u64> = arr.iter().map(FdonValue::structural_hash).collect();
+                shapes.sort_unstable();
+                shapes.dedup();
+                let h = fnv1a(hash, &(shapes.len() as u64).to_le_bytes());
+                shapes.iter().fold(h, |h, s| fnv1a(h, &s.to_le_bytes()))
+            }
+            _ => hash,
+        }
+    }
+}
+
+// --- Chuyển cây sang Arena khác ---
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Rebuilds the value in `dest` so the source arena can be reset or dropped.
+    ///
+    /// Arena-allocated parts (containers, object keys, unescaped `SE` strings) are copied
+    /// into `dest`; `&'a str` slices keep borrowing the original input, which must therefore
+    /// outlive `dest`. Cheaper than `to_owned_value` when most strings are raw.
+    pub fn move_to_arena<'new>(self, dest: &'new Bump) -> FdonValue<'a, 'new>
+    where
+        'a: 'new,
+    {
+        match self {
+            FdonValue::Null => FdonValue::Null,
+            FdonValue::Bool(b) => FdonValue::Bool(b),
+            FdonValue::Number(n) => FdonValue::Number(n),
+            FdonValue::Timestamp(n) => FdonValue::Timestamp(n),
+            FdonValue::RawString(s) => FdonValue::RawString(s),
+            FdonValue::EscapedString(s) => FdonValue::EscapedString(s.clone_in(dest)),
+            FdonValue::Date(s) => FdonValue::Date(s),
+            FdonValue::Time(s) => FdonValue::Time(s),
+            FdonValue::Array(arr) => {
+                let mut out = BumpVec::with_capacity_in(arr.len(), dest);
+                out.extend(arr.into_iter().map(|v| v.move_to_arena(dest)));
+                FdonValue::Array(out)
+            }
+            FdonValue::Object(obj) => {
+                let mut out = FdonObject::with_capacity_in(obj.len(), dest);
+                for (k, v) in obj {
+                    out.insert(dest.alloc_str(k), v.move_to_arena(dest));
+                }
+                FdonValue::Object(out)
+            }
+        }
+    }
+
+    /// Deep-copies the value into `arena`, leaving `self` untouched (`move_to_arena`
+    /// without consuming the source). `arena` may also be the one `self` lives in.
+    ///
+    /// Containers, object keys and unescaped `SE` strings are reallocated in `arena`;
+    /// `&'a str` slices keep borrowing the original input, which must outlive `arena`.
+    pub fn clone_into<'new>(&self, arena: &'new Bump) -> FdonValue<'a, 'new>
+    where
+        'a: 'new,
+    {
+        match self {
+            FdonValue::Null => FdonValue::Null,
+            FdonValue::Bool(b) => FdonValue::Bool(*b),
+            FdonValue::Number(n) => FdonValue::Number(*n),
+            FdonValue::Timestamp(n) => FdonValue::Timestamp(*n),
+            FdonValue::RawString(s) => FdonValue::RawString(s),
+            FdonValue::EscapedString(s) => FdonValue::EscapedString(s.clone_in(arena)),
+            FdonValue::Date(s) => FdonValue::Date(s),
+            FdonValue::Time(s) => FdonValue::Time(s),
+            FdonValue::Array(arr) => {
+                let mut out = BumpVec::with_capacity_in(arr.len(), arena);
+                out.extend(arr.iter().map(|v| v.clone_into(arena)));
+                FdonValue::Array(out)
+            }
+            FdonValue::Object(obj) => {
+                let mut out = FdonObject::with_capacity_in(obj.len(), arena);
+                for (k, v) in obj.iter() {
+                    out.insert(arena.alloc_str(k), v.clone_into(arena));
+                }
+                FdonValue::Object(out)
+            }
+        }
+    }
+}
+
+// --- Đổi tên key ---
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Renames key `from` to `to` in this object, keeping its value. Returns `false`
+    /// (and changes nothing) if `self` is not an object or has no key `from`; an
+    /// existing `to` entry is overwritten.
+    ///
+    /// The renamed entry keeps its position. The new key can be built in the same
+    /// arena (`arena.alloc_str(..)`).
+    pub fn rename_key(&mut self, from: &str, to: &'bump str) -> bool {
+        let FdonValue::Object(obj) = self else { return false };
+        obj.rename(from, to)
+    }
+}
+
+// --- Cắt chuỗi dài (log) ---
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// Shortens every `S`/`SE` string longer than `max_len` bytes, at any depth, to
+    /// at most `max_len` bytes followed by `…`, so huge documents can be logged.
+    ///
+    /// The cut moves back to the previous UTF-8 character boundary, so the kept
+    /// prefix may be a few bytes shorter than `max_len` but is never split inside
+    /// a character. Truncated strings are rebuilt in `arena` and become
+    /// `EscapedString` (still `ValueKind::String`). Dates, times and keys are left alone.
+    pub fn truncate_strings(&mut self, max_len: usize, arena: &'bump Bump) {
+        self.walk_mut(&mut |v| {
+            let Some(s) = v.string_content() else { return };
+            if s.len() <= max_len {
+                return;
+            }
+            let mut cut = max_len;
+            while !s.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            let mut out = BumpString::with_capacity_in(cut + '…'.len_utf8(), arena);
+            out.push_str(&s[..cut]);
+            out.push('…');
+            *v = FdonValue::EscapedString(out.into());
+        });
+    }
+}
+
+// --- Accessor (Option thay cho match lồng nhau) ---
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// `true` for `U`.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        matches!(self, FdonValue::Null)
+    }
+
+    /// Text of an `S`, `SE`, `D` or `T"..."` value; `None` for everything else.
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            FdonValue::RawString(s) | FdonValue::Date(s) | FdonValue::Time(s) => Some(s),
+            FdonValue::EscapedString(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            FdonValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_array(&self) -> Option<&FdonArray<'a, 'bump>> {
+        match self {
+            FdonValue::Array(arr) => Some(arr),
+            _ => None,
+        }
+    }
+
+    /// The object's map, so lookups chain: `v.as_object()?.get("name")?.as_str()`.
+    #[inline]
+    pub fn as_object(&self) -> Option<&FdonObject<'a, 'bump>> {
+        match self {
+            FdonValue::Object(obj) => Some(obj),
+            _ => None,
+        }
+    }
+
+    /// Field `key` of an object; `None` if absent or `self` is not an object.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&FdonValue<'a, 'bump>> {
+        match self {
+            FdonValue::Object(obj) => obj.get(key),
+            _ => None,
+        }
+    }
+
+    /// Element `i` of an array; `None` if out of range or `self` is not an array.
+    #[inline]
+    pub fn get_index(&self, i: usize) -> Option<&FdonValue<'a, 'bump>> {
+        match self {
+            FdonValue::Array(arr) => arr.get(i),
+            _ => None,
+        }
+    }
+}
+
+/// `value["user"]["name"]`: like `serde_json::Value`, a missing key or a non-object
+/// gives `U` instead of panicking, so lookups chain safely.
+impl<'a, 'bump> Index<&str> for FdonValue<'a, 'bump> {
+    type Output = FdonValue<'a, 'bump>;
+
+    #[inline]
+    fn index(&self, key: &str) -> &Self::Output {
+        self.get(key).unwrap_or(&FdonValue::Null)
+    }
+}
+
+/// `value[0]`: an index out of range or a non-array gives `U` (never panics).
+impl<'a, 'bump> Index<usize> for FdonValue<'a, 'bump> {
+    type Output = FdonValue<'a, 'bump>;
+
+    #[inline]
+    fn index(&self, i: usize) -> &Self::Output {
+        self.get_index(i).unwrap_or(&FdonValue::Null)
+    }
+}
+
+// --- Đọc số (chặt) và đọc số lỏng từ chuỗi ---
+
+impl<'a, 'bump> FdonValue<'a, 'bump> {
+    /// An integer `N` or numeric `T` that fits in `i64`; `None` for floats and the rest.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            FdonValue::Number(FdonNumber::Integer(i)) | FdonValue::Timestamp(FdonNumber::Integer(i)) => Some(*i),
+            FdonValue::Number(FdonNumber::UInt(u)) | FdonValue::Timestamp(FdonNumber::UInt(u)) => {
+                i64::try_from(*u).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Any `N` or numeric `T` as `f64` (integers are widened, so `N1` gives `1.0`).
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            FdonValue::Number(n) | FdonValue::Timestamp(n) => Some(n.to_f64()),
+            _ => None,
+        }
+    }
+
+    /// Like `as_i64`, but also reads an `S`/`SE` string holding a whole number:
+    /// optional `-`, ASCII digits, surrounding whitespace allowed (`S" 42 "`).
+    /// No `+`, fraction, exponent or digit separators.
+    pub fn as_i64_coerce(&self) -> Option<i64> {
+        self.as_i64().or_else(|| {
+            let s = numeric_string(self.string_content()?)?;
+            if s.contains('.') { None } else { s.parse().ok() }
+        })
+    }
+
+    /// Like `as_f64`, but also reads an `S`/`SE` string holding a number in FDON's
+    /// own literal syntax: optional `-`, digits, optionally `.` and more digits
+    /// (`S"42"`, `S"-0.5"`), surrounding whitespace allowed. No `+`, exponent,
+    /// `NaN` or `inf`.
+    pub fn as_f64_coerce(&self) -> Option<f64> {
+        self.as_f64().or_else(|| numeric_string(self.string_content()?)?.parse().ok())
+    }
+}
+
+// `-?\d+(\.\d+)?` sau khi bỏ khoảng trắng hai đầu
+fn numeric_string(s: &str) -> Option<&str> {
+    let s = s.trim();
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    let (int, frac) = match digits.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (digits, None),
+    };
+    let all_digits = |p: &str| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit());
+    (all_digits(int) && frac.is_none_or(all_digits)).then_some(s)
+}
@@ -0,0 +1,75 @@
+// --- Cảnh báo không chặn parse (mất độ chính xác, key trùng) ---
+
+use bumpalo::Bump;
+
+use crate::{FdonError, FdonParser, FdonValue, ParseOptions};
+
+/// What a `FdonWarning` is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningKind {
+    /// An object key appeared more than once; the last value was kept.
+    DuplicateKey,
+    /// A float literal has more significant digits than an `f64` keeps (or
+    /// overflows to infinity), so the parsed value differs from what was written.
+    LossyFloat,
+    /// A raw `S"..."` string contains `\`, which is kept literally (`\n` is two
+    /// characters); `SE"..."` was probably meant. Needs
+    /// `ParseOptions::warn_backslash_in_raw_string`.
+    BackslashInRawString,
+}
+
+/// A non-fatal issue found during a successful parse. `pos` is the byte offset of
+/// the key (`DuplicateKey`), the number literal (`LossyFloat`) or the `S` tag
+/// (`BackslashInRawString`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FdonWarning {
+    pub kind: WarningKind,
+    pub pos: usize,
+}
+
+/// Same as `parse_fdon_zero_copy_arena`, but also returns the warnings collected
+/// along the way, ordered by position. Strict pipelines can reject on any warning.
+#[inline]
+pub fn parse_with_warnings<'a, 'bump>(
+    minified_data: &'a str,
+    arena: &'bump Bump,
+) -> Result<(FdonValue<'a, 'bump>, Vec<FdonWarning>), FdonError> {
+    parse_with_warnings_with_options(minified_data, arena, ParseOptions::default())
+}
+
+/// Same as `parse_with_warnings`, with explicit `ParseOptions` (which also turn on
+/// optional warnings such as `warn_backslash_in_raw_string`).
+pub fn parse_with_warnings_with_options<'a, 'bump>(
+    minified_data: &'a str,
+    arena: &'bump Bump,
+    options: ParseOptions,
+) -> Result<(FdonValue<'a, 'bump>, Vec<FdonWarning>), FdonError> {
+    let mut parser = FdonParser::with_options(minified_data, arena, options);
+    parser.warnings = Some(Vec::new());
+    let value = parser.parse()?;
+    let mut warnings = parser.warnings.unwrap_or_default();
+    // DuplicateKey được ghi sau khi parse xong value của nó -> sắp lại theo vị trí
+    warnings.sort_by_key(|w| w.pos);
+    Ok((value, warnings))
+}
+
+/// `true` if `literal` has significant digits that `value` (its parsed form) dropped.
+///
+/// Compares the literal's digits with the shortest round-trip representation of
+/// `value`; extra non-zero digits in the literal were lost.
+pub(crate) fn float_loses_precision(literal: &[u8], value: f64) -> bool {
+    if !value.is_finite() {
+        return true;
+    }
+    let repr = format!("{:e}", value);
+    significant_digits(literal) != significant_digits(repr.as_bytes())
+}
+
+// Chữ số có nghĩa của phần mantissa: bỏ dấu, dấu chấm, hậu tố, số 0 ở đầu và cuối
+fn significant_digits(s: &[u8]) -> Vec<u8> {
+    let mantissa = s.split(|&b| b == b'e' || b == b'E').next().unwrap_or(s);
+    let digits: Vec<u8> = mantissa.iter().copied().filter(u8::is_ascii_digit).collect();
+    let start = digits.iter().position(|&d| d != b'0').unwrap_or(digits.len());
+    let end = digits.iter().rposition(|&d| d != b'0').map_or(start, |i| i + 1);
+    digits[start..end].to_vec()
+}
@@ -0,0 +1,124 @@
+use bumpalo::Bump;
+use fdon_rs::parse_fdon_zero_copy_arena;
+
+// --- as_* / is_null ---
+
+const ALL: &str = r#"A[U,Btrue,N-3,N18446744073709551615,N2.5,T7,S"s",SE"e\n",D"2024-01-02",T"10:00",A[N1],O{k:N1}]"#;
+
+#[test]
+fn accessors_match_only_their_variants() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(ALL, &arena).unwrap();
+    let items = value.as_array().unwrap();
+
+    let strs: Vec<Option<&str>> = items.iter().map(|v| v.as_str()).collect();
+    assert_eq!(strs[..6], [None; 6]);
+    assert_eq!(strs[6..10], [Some("s"), Some("e\n"), Some("2024-01-02"), Some("10:00")]);
+    assert_eq!(strs[10..], [None; 2]);
+
+    let ints: Vec<Option<i64>> = items.iter().map(|v| v.as_i64()).collect();
+    // u64 lớn hơn i64::MAX và float: None
+    assert_eq!(ints, [None, None, Some(-3), None, None, Some(7), None, None, None, None, None, None]);
+
+    let floats: Vec<Option<f64>> = items.iter().map(|v| v.as_f64()).collect();
+    let big = u64::MAX as f64;
+    assert_eq!(floats, [None, None, Some(-3.0), Some(big), Some(2.5), Some(7.0), None, None, None, None, None, None]);
+
+    let bools: Vec<Option<bool>> = items.iter().map(|v| v.as_bool()).collect();
+    assert_eq!(bools[1], Some(true));
+    assert!(bools.iter().enumerate().all(|(i, b)| i == 1 || b.is_none()));
+
+    let nulls: Vec<bool> = items.iter().map(|v| v.is_null()).collect();
+    assert!(nulls[0] && nulls[1..].iter().all(|n| !n));
+
+    for (i, item) in items.iter().enumerate() {
+        assert_eq!(item.as_array().is_some(), i == 10, "{}", item);
+        assert_eq!(item.as_object().is_some(), i == 11, "{}", item);
+    }
+}
+
+#[test]
+fn accessors_chain_with_question_mark() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(r#"O{user:O{name:S"An",tags:A[S"a"]}}"#, &arena).unwrap();
+    let name = || value.as_object()?.get("user")?.as_object()?.get("name")?.as_str();
+    assert_eq!(name(), Some("An"));
+    let missing = || value.as_object()?.get("user")?.as_array()?.first()?.as_str();
+    assert_eq!(missing(), None);
+}
+
+// --- Index / get / get_index ---
+
+#[test]
+fn index_chains_to_null_on_missing_paths() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(r#"O{user:O{name:S"An",roles:A[S"a",S"b"]},n:N1}"#, &arena).unwrap();
+    assert_eq!(value["user"]["name"].as_str(), Some("An"));
+    assert_eq!(value["user"]["roles"][1].as_str(), Some("b"));
+
+    // Key thiếu, sai kiểu, chỉ số vượt quá: đều ra U, kể cả khi nối tiếp
+    assert!(value["missing"].is_null());
+    assert!(value["missing"]["deeper"][0].is_null());
+    assert!(value["n"]["x"].is_null());
+    assert!(value[0].is_null());
+    assert!(value["user"]["roles"][2].is_null());
+    assert!(value["user"]["roles"][usize::MAX].is_null());
+    assert!(value["user"]["roles"]["0"].is_null());
+}
+
+#[test]
+fn get_and_get_index_return_none() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(r#"A[O{k:N1},N2]"#, &arena).unwrap();
+    assert_eq!(value.get_index(1).and_then(|v| v.as_i64()), Some(2));
+    assert_eq!(value.get_index(0).and_then(|v| v.get("k")).and_then(|v| v.as_i64()), Some(1));
+    assert!(value.get_index(2).is_none());
+    assert!(value.get_index(usize::MAX).is_none());
+    assert!(value.get("k").is_none());
+    assert!(value.get_index(0).unwrap().get("missing").is_none());
+    assert!(value.get_index(0).unwrap().get_index(0).is_none());
+    assert!(value.get_index(1).unwrap().get("k").is_none());
+}
+
+// --- pointer (RFC 6901) ---
+
+#[test]
+fn pointer_walks_objects_and_arrays() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(r#"O{user:O{roles:A[O{name:S"admin"},O{name:S"dev"}]},n:N1}"#, &arena).unwrap();
+    assert_eq!(value.pointer("/user/roles/0/name").and_then(|v| v.as_str()), Some("admin"));
+    assert_eq!(value.pointer("/user/roles/1/name").and_then(|v| v.as_str()), Some("dev"));
+    assert!(value.pointer("").unwrap().eq_strict(&value));
+
+    // Thiếu segment, sai kiểu, chỉ số không hợp lệ
+    for path in ["/user/roles/2", "/user/roles/01", "/user/roles/-1", "/user/roles/+1", "/user/roles/x", "/n/0", "/x"] {
+        assert!(value.pointer(path).is_none(), "{}", path);
+    }
+    // Không có '/' ở đầu
+    assert!(value.pointer("user").is_none());
+}
+
+#[test]
+fn pointer_unescapes_tokens() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(r#"O{"a/b":N1,"m~n":N2,"~1":N3,"":O{"":N4}}"#, &arena).unwrap();
+    assert_eq!(value.pointer("/a~1b").and_then(|v| v.as_i64()), Some(1));
+    assert_eq!(value.pointer("/m~0n").and_then(|v| v.as_i64()), Some(2));
+    // "~01" là "~1" chứ không phải "/"
+    assert_eq!(value.pointer("/~01").and_then(|v| v.as_i64()), Some(3));
+    // Escape '~' không hợp lệ
+    assert!(value.pointer("/m~n").is_none());
+    assert!(value.pointer("/m~2n").is_none());
+    assert!(value.pointer("/a~").is_none());
+}
+
+#[test]
+fn pointer_trailing_slash_names_the_empty_key() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(r#"O{"":O{"":N4},a:O{b:N5},l:A[N6]}"#, &arena).unwrap();
+    assert!(value.pointer("/").unwrap().as_object().is_some());
+    assert_eq!(value.pointer("//").and_then(|v| v.as_i64()), Some(4));
+    assert!(value.pointer("/a/b/").is_none());
+    assert!(value.pointer("/a/").is_none());
+    assert!(value.pointer("/l/").is_none());
+}
@@ -0,0 +1,70 @@
+use bumpalo::Bump;
+use fdon_rs::{parse_fdon_bytes, parse_fdon_zero_copy_arena, FdonError, FdonParser, ParseOptions, Validator};
+
+const FIXTURE: &[u8] = b"O{name:S\"Nguy\xe1\xbb\x85n\",note:SE\"caf\xc3\xa9\\n\",k\xc3\xa9y:A[D\"2024-01-02\",N1,U]}";
+
+// --- parse_fdon_bytes ---
+
+#[test]
+fn bytes_fixture_parses_like_the_str() {
+    let arena = Bump::new();
+    let value = parse_fdon_bytes(FIXTURE, &arena).unwrap();
+    let text = std::str::from_utf8(FIXTURE).unwrap();
+    assert!(value.eq_strict(&parse_fdon_zero_copy_arena(text, &arena).unwrap()));
+    assert_eq!(value["name"].as_str(), Some("Nguyễn"));
+    assert_eq!(value["note"].as_str(), Some("café\n"));
+    assert_eq!(value["kéy"][0].as_str(), Some("2024-01-02"));
+
+    // Borrowed: chuỗi trỏ thẳng vào buffer byte
+    let name = value["name"].as_str().unwrap();
+    assert!(FIXTURE.as_ptr_range().contains(&name.as_ptr()));
+
+    let mut parser = FdonParser::from_bytes(FIXTURE, &arena);
+    assert!(parser.parse().unwrap().eq_strict(&value));
+}
+
+// --- Kiểm tra UTF-8 của key và chuỗi ---
+
+fn bytes_err(input: &[u8]) -> String {
+    let arena = Bump::new();
+    let err = parse_fdon_bytes(input, &arena).unwrap_err();
+    // Validator chỉ kiểm tra khi bật validate_utf8, và báo đúng byte đó
+    let options = ParseOptions { validate_utf8: true, ..ParseOptions::default() };
+    let mut v = Validator::with_options(options);
+    let streamed = v.feed(input).and_then(|_| v.finish()).unwrap_err();
+    assert_eq!(format!("{:?}", streamed), format!("{:?}", err));
+    let mut v = Validator::new();
+    assert!(v.feed(input).and_then(|_| v.finish()).is_ok());
+    format!("{:?}", err)
+}
+
+#[test]
+fn truncated_multibyte_sequence_is_a_clean_error() {
+    // "ễ" = E1 BB 85: thiếu byte cuối ngay trước dấu đóng
+    assert_eq!(bytes_err(b"A[S\"Nguy\xe1\xbb\"]"), "InvalidUtf8 { pos: 8 }");
+    assert_eq!(bytes_err(b"SE\"ab\xc3\""), "InvalidUtf8 { pos: 5 }");
+    assert_eq!(bytes_err(b"SE\"a\\n\xff\""), "InvalidUtf8 { pos: 6 }");
+    assert_eq!(bytes_err(b"O{k\xc3:N1}"), "InvalidUtf8 { pos: 3 }");
+    assert_eq!(bytes_err(b"A[D\"\xed\xa0\x80\"]"), "InvalidUtf8 { pos: 4 }");
+    assert_eq!(bytes_err(b"O{\"q\xe2\x82\":T\"x\"}"), "InvalidUtf8 { pos: 4 }");
+}
+
+#[test]
+fn split_sequences_across_validator_chunks() {
+    let options = ParseOptions { validate_utf8: true, ..ParseOptions::default() };
+    let input = b"A[S\"Nguy\xe1\xbb\x85n\",SE\"\xf0\x9f\x98\x80\"]";
+    for split in 0..input.len() {
+        let mut v = Validator::with_options(options);
+        let (a, b) = input.split_at(split);
+        assert!(v.feed(a).and_then(|_| v.feed(b)).and_then(|_| v.finish()).is_ok(), "split at {}", split);
+    }
+    let mut bad = input.to_vec();
+    // Cắt 4 byte của 😀 còn 3
+    bad.remove(19);
+    for split in 0..bad.len() {
+        let mut v = Validator::with_options(options);
+        let (a, b) = bad.split_at(split);
+        let err = v.feed(a).and_then(|_| v.feed(b)).and_then(|_| v.finish()).unwrap_err();
+        assert!(matches!(err, FdonError::InvalidUtf8 { pos: 17 }), "split at {}: {:?}", split, err);
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "chrono")]
+
+use bumpalo::Bump;
+use chrono::{DateTime, NaiveDate, NaiveTime};
+use fdon_rs::{parse_fdon_zero_copy_arena, FdonNumber, FdonValue};
+
+// --- as_naive_date ---
+
+#[test]
+fn dates_must_exist() {
+    let arena = Bump::new();
+    let date = |input: &str| parse_fdon_zero_copy_arena(input, &arena).unwrap().as_naive_date();
+    assert_eq!(date(r#"D"2024-01-02""#), NaiveDate::from_ymd_opt(2024, 1, 2));
+    assert_eq!(date(r#"D"2024-02-29""#), NaiveDate::from_ymd_opt(2024, 2, 29));
+    assert_eq!(date(r#"D"2000-02-29""#), NaiveDate::from_ymd_opt(2000, 2, 29));
+    for bad in ["2023-02-29", "1900-02-29", "2024-13-01", "2024-00-10", "2024-01-32", "2024-04-31", "2024-1-02"] {
+        assert_eq!(date(&format!("D\"{}\"", bad)), None, "{}", bad);
+    }
+    for bad in ["20240102", "2024/01/02", "2024-01-02T00:00", "", "abcd-ef-gh", "+024-01-02"] {
+        assert_eq!(date(&format!("D\"{}\"", bad)), None, "{}", bad);
+    }
+    // Chỉ D"..." mới là ngày
+    assert_eq!(date(r#"S"2024-01-02""#), None);
+    assert_eq!(date(r#"T"2024-01-02""#), None);
+}
+
+// --- as_naive_time ---
+
+#[test]
+fn times_with_optional_fraction() {
+    let arena = Bump::new();
+    let time = |input: &str| parse_fdon_zero_copy_arena(input, &arena).unwrap().as_naive_time();
+    assert_eq!(time(r#"T"10:20:30""#), NaiveTime::from_hms_opt(10, 20, 30));
+    assert_eq!(time(r#"T"00:00:00""#), NaiveTime::from_hms_opt(0, 0, 0));
+    assert_eq!(time(r#"T"23:59:59.25""#), NaiveTime::from_hms_milli_opt(23, 59, 59, 250));
+    assert_eq!(time(r#"T"08:30:00.123456789""#), NaiveTime::from_hms_nano_opt(8, 30, 0, 123_456_789));
+    for bad in ["24:00:00", "12:60:00", "12:00:61", "12:00", "1:00:00", "12:00:00.", "12:00:00.1234567890", "12-00-00"] {
+        assert_eq!(time(&format!("T\"{}\"", bad)), None, "{}", bad);
+    }
+    // T số và S"..." không phải giờ trong ngày
+    assert_eq!(time("T36000"), None);
+    assert_eq!(time(r#"S"10:20:30""#), None);
+}
+
+// --- as_datetime_utc ---
+
+#[test]
+fn numeric_timestamps_are_unix_seconds() {
+    let arena = Bump::new();
+    let instant = |input: &str| parse_fdon_zero_copy_arena(input, &arena).unwrap().as_datetime_utc();
+    assert_eq!(instant("T0"), DateTime::from_timestamp(0, 0));
+    assert_eq!(instant("T1700000000"), DateTime::from_timestamp(1_700_000_000, 0));
+    assert_eq!(instant("T-86400"), DateTime::from_timestamp(-86_400, 0));
+    assert_eq!(instant("T1.5"), DateTime::from_timestamp(1, 500_000_000));
+    assert_eq!(instant("T-0.25"), DateTime::from_timestamp(-1, 750_000_000));
+    // Làm tròn phần lẻ lên tròn một giây
+    assert_eq!(instant("T1.9999999999"), DateTime::from_timestamp(2, 0));
+
+    // Ngoài khoảng chrono, hoặc không phải T số
+    assert_eq!(instant("T18446744073709551615"), None);
+    assert_eq!(instant("T9223372036854775807"), None);
+    assert_eq!(instant("T1e300"), None);
+    assert_eq!(instant(r#"T"10:00:00""#), None);
+    assert_eq!(instant("N1700000000"), None);
+    assert_eq!(FdonValue::Timestamp(FdonNumber::Float(f64::NAN)).as_datetime_utc(), None);
+}
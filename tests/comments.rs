@@ -0,0 +1,94 @@
+use bumpalo::Bump;
+use fdon_rs::{
+    minify_fdon, minify_fdon_with_options, parse_fdon_zero_copy_arena, to_fdon_string_preserving, FdonNumber, FdonValue,
+    ParseOptions,
+};
+
+fn with_comments() -> ParseOptions {
+    ParseOptions { allow_comments: true, ..ParseOptions::default() }
+}
+
+#[test]
+fn comments_next_to_type_tags_are_stripped() {
+    let options = with_comments();
+    let cases = [
+        ("A[N1 /* note */ ,N2]", "A[N1,N2]"),
+        ("A[N1/*x*/]", "A[N1]"),
+        ("A[/*x*/Btrue, # hash\n U // slash\n]", "A[Btrue,U]"),
+        ("O{a/* k */:N1}", "O{a:N1}"),
+        ("O{a:S\"x\"}#tail", "O{a:S\"x\"}"),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(minify_fdon_with_options(input, &options), expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn comment_markers_inside_strings_are_kept() {
+    let options = with_comments();
+    let input = r##"A[S"a/*b*/", SE"x\"/*", D"#1", S"//"] // end"##;
+    let minified = minify_fdon_with_options(input, &options);
+    assert_eq!(minified, r##"A[S"a/*b*/",SE"x\"/*",D"#1",S"//"]"##);
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(&minified, &arena).unwrap();
+    assert_eq!(value.as_array().unwrap()[1].as_str(), Some("x\"/*"));
+}
+
+#[test]
+fn block_comments_do_not_nest() {
+    let options = with_comments();
+    // '*/' đầu tiên đóng comment; phần sau trở thành dữ liệu
+    assert_eq!(minify_fdon_with_options("A[/* a /* b */N1]", &options), "A[N1]");
+    assert_eq!(minify_fdon_with_options("A[/* a /* b */ c */N1]", &options), "A[c*/N1]");
+}
+
+#[test]
+fn comments_are_data_without_the_option() {
+    assert_eq!(minify_fdon("A[N1 # x\n]"), "A[N1#x]");
+}
+
+// --- to_fdon_string_preserving ---
+
+const COMMENTED: &str = r#"# config
+O{
+  // leading of name
+  name: S"old", # trailing of name
+  port: N80, /* port */
+  tags: A[
+    S"a" // first tag
+    /* before close */
+  ]
+}
+"#;
+
+#[test]
+fn a_rewritten_value_keeps_its_comments() {
+    let options = with_comments();
+    let arena = Bump::new();
+    let minified = minify_fdon_with_options(COMMENTED, &options);
+    let mut value = parse_fdon_zero_copy_arena(&minified, &arena).unwrap();
+    if let FdonValue::Object(obj) = &mut value {
+        *obj.get_mut("name").unwrap() = FdonValue::RawString("new");
+        *obj.get_mut("port").unwrap() = FdonValue::Number(FdonNumber::Integer(8080));
+    }
+    let out = to_fdon_string_preserving(&value, COMMENTED, &options).unwrap();
+    assert_eq!(out.trim_end(), COMMENTED.replace(r#"S"old""#, r#"S"new""#).replace("N80", "N8080").trim_end());
+    assert!(parse_fdon_zero_copy_arena(&minify_fdon_with_options(&out, &options), &arena).unwrap().eq_strict(&value));
+}
+
+#[test]
+fn comments_of_removed_values_are_dropped() {
+    let options = with_comments();
+    let arena = Bump::new();
+    let minified = minify_fdon_with_options(COMMENTED, &options);
+    let mut value = parse_fdon_zero_copy_arena(&minified, &arena).unwrap();
+    if let FdonValue::Object(obj) = &mut value {
+        obj.remove("name");
+    }
+    let out = to_fdon_string_preserving(&value, COMMENTED, &options).unwrap();
+    assert!(!out.contains("of name"), "{}", out);
+    assert!(out.contains("# config") && out.contains("/* port */") && out.contains("/* before close */"));
+
+    // Nguồn không đọc được: lỗi của parser
+    assert!(to_fdon_string_preserving(&value, "O{a:N1,,}", &options).is_err());
+}
@@ -0,0 +1,211 @@
+use bumpalo::Bump;
+use fdon_rs::{from_fdon_str, FdonError};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+enum Shape {
+    Circle { r: f64 },
+    Rect { w: u32, h: u32, label: String },
+    Unit,
+    Point(Point),
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Drawing {
+    name: String,
+    shapes: Vec<Shape>,
+}
+
+fn deserialize_error(input: &str) -> (String, Option<usize>) {
+    let arena = Bump::new();
+    match from_fdon_str::<Drawing>(input, &arena) {
+        Err(FdonError::Deserialize { msg, pos }) => (msg, pos),
+        other => panic!("{}: {:?}", input, other),
+    }
+}
+
+// --- Enum có tag nội bộ (#[serde(tag = "type")]) ---
+
+#[test]
+fn internally_tagged_variants() {
+    let arena = Bump::new();
+    let cases = [
+        (r#"O{type:S"Circle",r:N1.5}"#, Shape::Circle { r: 1.5 }),
+        // Tag không nhất thiết đứng đầu; SE cũng được
+        (r#"O{w:N2,type:SE"Rect",h:N3,label:SE"a\"b"}"#, Shape::Rect { w: 2, h: 3, label: "a\"b".into() }),
+        (r#"O{type:S"Unit"}"#, Shape::Unit),
+        (r#"O{type:S"Point",x:N1,y:N-2}"#, Shape::Point(Point { x: 1, y: -2 })),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(from_fdon_str::<Shape>(input, &arena).unwrap(), expected, "{}", input);
+    }
+
+    let drawing: Drawing =
+        from_fdon_str(r#"O{name:S"d",shapes:A[O{type:S"Unit"},O{type:S"Circle",r:N2}]}"#, &arena).unwrap();
+    assert_eq!(drawing.shapes, vec![Shape::Unit, Shape::Circle { r: 2.0 }]);
+}
+
+#[test]
+fn unknown_tag_is_a_clear_error_at_the_tag() {
+    let input = r#"O{name:S"d",shapes:A[O{type:S"Unit"},O{type:S"Hexagon",r:N1}]}"#;
+    let (msg, pos) = deserialize_error(input);
+    assert_eq!(msg, "unknown variant `Hexagon`, expected one of `Circle`, `Rect`, `Unit`, `Point`");
+    assert_eq!(pos, input.find(r#"S"Hexagon""#));
+}
+
+#[test]
+fn variant_errors_point_at_the_tagged_object() {
+    // Lỗi phát sinh sau khi object đã được đọc hết vẫn chỉ về đúng object
+    let input = r#"O{name:S"d",shapes:A[O{type:S"Unit"},O{type:S"Circle"}]}"#;
+    let (msg, pos) = deserialize_error(input);
+    assert_eq!(msg, "missing field `r`");
+    assert_eq!(pos, input.find(r#"O{type:S"Circle""#));
+
+    let input = r#"O{name:S"d",shapes:A[O{r:N1}]}"#;
+    assert_eq!(deserialize_error(input), ("missing field `type`".to_string(), input.find("O{r:")));
+
+    let input = r#"O{name:S"d",shapes:A[O{type:S"Circle",r:S"big"}]}"#;
+    let (msg, pos) = deserialize_error(input);
+    assert_eq!(msg, "invalid type: string \"big\", expected f64");
+    assert_eq!(pos, input.find(r#"O{type:S"Circle""#));
+}
+
+#[test]
+fn missing_field_at_the_root_points_at_the_root() {
+    let (msg, pos) = deserialize_error(r#"O{name:S"d"}"#);
+    assert_eq!((msg.as_str(), pos), ("missing field `shapes`", Some(0)));
+}
+
+// --- Ánh xạ kiểu cơ bản ---
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Mode {
+    Fast,
+    Limited(u32),
+    Window { from: u8, to: u8 },
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Config {
+    #[serde(rename = "display-name")]
+    name: String,
+    port: u16,
+    ratio: f64,
+    scale: f32,
+    offset: i64,
+    enabled: bool,
+    nothing: Option<u8>,
+    something: Option<u8>,
+    unit: (),
+    pair: (i8, String),
+    tags: Vec<String>,
+    limits: std::collections::BTreeMap<String, u64>,
+    origin: Point,
+    modes: Vec<Mode>,
+    #[serde(default)]
+    missing_ok: u8,
+}
+
+#[test]
+fn every_fdon_kind_maps_to_its_serde_type() {
+    let arena = Bump::new();
+    let input = concat!(
+        r#"O{display-name:SE"fdon \"rs\"",port:N8080,ratio:N0.25,scale:N2,offset:T-17,enabled:Btrue,"#,
+        r#"nothing:U,something:N7,unit:U,pair:A[N-1,D"2024-01-02"],tags:A[S"a",SE"b",T"10:00"],"#,
+        r#"limits:O{max:N18446744073709551615,min:N0},origin:O{x:N1,y:N2},"#,
+        r#"modes:A[S"Fast",O{Limited:N3},O{Window:O{from:N1,to:N9}}]}"#
+    );
+    let config: Config = from_fdon_str(input, &arena).unwrap();
+    assert_eq!(
+        config,
+        Config {
+            name: "fdon \"rs\"".into(),
+            port: 8080,
+            ratio: 0.25,
+            scale: 2.0,
+            offset: -17,
+            enabled: true,
+            nothing: None,
+            something: Some(7),
+            unit: (),
+            pair: (-1, "2024-01-02".into()),
+            tags: vec!["a".into(), "b".into(), "10:00".into()],
+            limits: [("max".to_string(), u64::MAX), ("min".to_string(), 0)].into_iter().collect(),
+            origin: Point { x: 1, y: 2 },
+            modes: vec![Mode::Fast, Mode::Limited(3), Mode::Window { from: 1, to: 9 }],
+            missing_ok: 0,
+        }
+    );
+}
+
+#[test]
+fn type_mismatches_report_the_value_position() {
+    let arena = Bump::new();
+    let input = r#"O{x:N1,y:N300000000000}"#;
+    let err = from_fdon_str::<Point>(input, &arena).unwrap_err();
+    assert!(matches!(&err, FdonError::Deserialize { pos: Some(p), .. } if *p == input.find("N3").unwrap()), "{:?}", err);
+
+    let err = from_fdon_str::<Point>("O{x:N1,y:N2}N3", &arena).unwrap_err();
+    assert!(matches!(err, FdonError::ExtraData { pos: 12 }), "{:?}", err);
+    let err = from_fdon_str::<Point>(r#"{"x":1}"#, &arena).unwrap_err();
+    assert!(matches!(err, FdonError::LooksLikeJson { pos: 0 }), "{:?}", err);
+}
+
+// --- &'de str mượn thẳng từ input ---
+
+#[derive(Debug, Deserialize)]
+struct Borrowed<'a> {
+    name: &'a str,
+    day: &'a str,
+    #[serde(borrow)]
+    note: std::borrow::Cow<'a, str>,
+}
+
+#[test]
+fn raw_strings_deserialize_without_allocating() {
+    let arena = Bump::new();
+    let input = r#"O{name:S"fdon",day:D"2024-01-02",note:SE"plain"}"#;
+    let b: Borrowed<'_> = from_fdon_str(input, &arena).unwrap();
+    let inside = |s: &str| input.as_bytes().as_ptr_range().contains(&s.as_ptr());
+    assert!(inside(b.name) && inside(b.day));
+    assert!(matches!(b.note, std::borrow::Cow::Borrowed(s) if inside(s)));
+    assert_eq!((b.name, b.day, &*b.note), ("fdon", "2024-01-02", "plain"));
+    assert_eq!(arena.allocated_bytes(), 0);
+
+    // SE có escape: Cow thành Owned; &str thì không mượn được
+    let b: Borrowed<'_> = from_fdon_str(r#"O{name:S"a",day:D"b",note:SE"x\ny"}"#, &arena).unwrap();
+    assert!(matches!(b.note, std::borrow::Cow::Owned(ref s) if s == "x\ny"));
+    let err = from_fdon_str::<Borrowed<'_>>(r#"O{name:SE"x\ny",day:D"b",note:S"c"}"#, &arena).unwrap_err();
+    assert!(matches!(&err, FdonError::Deserialize { msg, pos: Some(7) } if msg.contains("borrowed string")), "{:?}", err);
+}
+
+// --- Key SE"..." ---
+
+#[test]
+fn escaped_keys_name_fields_map_entries_and_variants() {
+    let arena = Bump::new();
+    let point: Point = from_fdon_str(r#"O{SE"\u{78}":N1,SE"y":N2}"#, &arena).unwrap();
+    assert_eq!(point, Point { x: 1, y: 2 });
+
+    let map: std::collections::BTreeMap<String, u8> = from_fdon_str(r#"O{SE"a\"b:c":N1,SE"\t":N2}"#, &arena).unwrap();
+    assert_eq!(map, [("a\"b:c".to_string(), 1), ("\t".to_string(), 2)].into_iter().collect());
+    let modes: Vec<Mode> = from_fdon_str(r#"A[O{SE"Limit\u{65}d":N3},O{SE"Fast":U}]"#, &arena).unwrap();
+    assert_eq!(modes, [Mode::Limited(3), Mode::Fast]);
+
+    // reject_duplicate_keys so sánh key đã unescape
+    let options = fdon_rs::ParseOptions { reject_duplicate_keys: true, ..Default::default() };
+    let err = fdon_rs::from_fdon_str_with_options::<std::collections::BTreeMap<String, u8>>(
+        r#"O{ab:N1,SE"a\u{62}":N2}"#,
+        &arena,
+        options,
+    )
+    .unwrap_err();
+    assert!(matches!(err, FdonError::DuplicateKey { ref key, pos: 8 } if key == "ab"), "{:?}", err);
+}
@@ -0,0 +1,67 @@
+use bumpalo::Bump;
+use fdon_rs::{
+    is_valid_string_delimiter, parse_fdon_zero_copy_arena, parse_fdon_zero_copy_arena_with_options, to_fdon_string,
+    to_fdon_writer_with_options, FdonError, ParseOptions, SerializeOptions, Validator,
+};
+
+fn backtick() -> ParseOptions {
+    ParseOptions { string_delimiter: b'`', ..ParseOptions::default() }
+}
+
+fn to_fdon_with_delimiter(value: &fdon_rs::FdonValue<'_, '_>, delim: u8) -> std::io::Result<String> {
+    let options = SerializeOptions { string_delimiter: delim, ..SerializeOptions::default() };
+    let mut out = Vec::new();
+    to_fdon_writer_with_options(value, &mut out, &options)?;
+    Ok(String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn backtick_delimiter_round_trips() {
+    let arena = Bump::new();
+    let input = r#"O{plain:S"abc",quote:SE"say \"hi\"",tick:S"a`b",esc:SE"x\ny\\z","a:b":D"2024-01-02",t:T"10:00:00",n:A[N1,N-2.5,Btrue,U]}"#;
+    let value = parse_fdon_zero_copy_arena(input, &arena).unwrap();
+
+    let text = to_fdon_with_delimiter(&value, b'`').unwrap();
+    // `"` không còn là delimiter: nằm nguyên trong chuỗi raw; '`' thì phải escape
+    assert!(text.contains(r#"SE`say "hi"`"#), "{}", text);
+    assert!(text.contains(r"SE`a\`b`"), "{}", text);
+    assert!(text.contains("`a:b`:D`2024-01-02`"), "{}", text);
+
+    let back = parse_fdon_zero_copy_arena_with_options(&text, &arena, backtick()).unwrap();
+    assert_eq!(back, value);
+    // Ghi lại lần nữa cho ra đúng văn bản đó
+    assert_eq!(to_fdon_with_delimiter(&back, b'`').unwrap(), text);
+}
+
+#[test]
+fn default_delimiter_output_is_unchanged() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(r#"O{a:S"x",b:SE"q\"t\\"}"#, &arena).unwrap();
+    assert_eq!(to_fdon_with_delimiter(&value, b'"').unwrap(), to_fdon_string(&value));
+    assert_eq!(to_fdon_string(&value), r#"O{a:S"x",b:SE"q\"t\\"}"#);
+}
+
+#[test]
+fn invalid_delimiters_are_rejected() {
+    for delim in [b':', b'N', b'{', b',', b'\\', b'-', b'7', b' ', b'#'] {
+        assert!(!is_valid_string_delimiter(delim), "{:?}", delim as char);
+
+        let arena = Bump::new();
+        let options = ParseOptions { string_delimiter: delim, ..ParseOptions::default() };
+        let err = parse_fdon_zero_copy_arena_with_options("N1", &arena, options).unwrap_err();
+        assert!(matches!(err, FdonError::InvalidDelimiter { byte } if byte == delim), "{:?}", err);
+        assert_eq!(err.pos(), None);
+
+        let err = Validator::with_options(options).feed(b"N1").unwrap_err();
+        assert!(matches!(err, FdonError::InvalidDelimiter { .. }), "{:?}", err);
+        let err = Validator::with_options(options).finish().unwrap_err();
+        assert!(matches!(err, FdonError::InvalidDelimiter { .. }), "{:?}", err);
+
+        let value = parse_fdon_zero_copy_arena("S\"x\"", &arena).unwrap();
+        let err = to_fdon_with_delimiter(&value, delim).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+    for delim in [b'"', b'`', b'\'', b'|', b'~'] {
+        assert!(is_valid_string_delimiter(delim), "{:?}", delim as char);
+    }
+}
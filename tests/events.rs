@@ -0,0 +1,140 @@
+use std::ops::ControlFlow;
+
+use bumpalo::Bump;
+use fdon_rs::{parse_events, parse_fdon_zero_copy_arena, FdonError, FdonNumber, FdonVisitor};
+
+// Ghi lại mọi sự kiện dưới dạng chuỗi
+#[derive(Default)]
+struct Log(Vec<String>);
+
+impl FdonVisitor for Log {
+    fn on_object_start(&mut self) -> ControlFlow<()> {
+        self.0.push("{".into());
+        ControlFlow::Continue(())
+    }
+    fn on_key(&mut self, key: &str) -> ControlFlow<()> {
+        self.0.push(format!("{}:", key));
+        ControlFlow::Continue(())
+    }
+    fn on_object_end(&mut self) -> ControlFlow<()> {
+        self.0.push("}".into());
+        ControlFlow::Continue(())
+    }
+    fn on_array_start(&mut self) -> ControlFlow<()> {
+        self.0.push("[".into());
+        ControlFlow::Continue(())
+    }
+    fn on_array_end(&mut self) -> ControlFlow<()> {
+        self.0.push("]".into());
+        ControlFlow::Continue(())
+    }
+    fn on_string(&mut self, s: &str) -> ControlFlow<()> {
+        self.0.push(format!("s{:?}", s));
+        ControlFlow::Continue(())
+    }
+    fn on_number(&mut self, n: FdonNumber) -> ControlFlow<()> {
+        self.0.push(format!("n{:?}", n));
+        ControlFlow::Continue(())
+    }
+    fn on_bool(&mut self, b: bool) -> ControlFlow<()> {
+        self.0.push(format!("b{}", b));
+        ControlFlow::Continue(())
+    }
+    fn on_null(&mut self) -> ControlFlow<()> {
+        self.0.push("null".into());
+        ControlFlow::Continue(())
+    }
+    fn on_date(&mut self, s: &str) -> ControlFlow<()> {
+        self.0.push(format!("d{:?}", s));
+        ControlFlow::Continue(())
+    }
+}
+
+// Chỉ lấy các value (chuỗi) nằm ngay dưới một key nhất định, ở mọi tầng
+struct CollectKey<'k> {
+    key: &'k str,
+    armed: bool,
+    found: Vec<String>,
+    limit: usize,
+}
+
+impl FdonVisitor for CollectKey<'_> {
+    fn on_key(&mut self, key: &str) -> ControlFlow<()> {
+        self.armed = key == self.key;
+        ControlFlow::Continue(())
+    }
+    fn on_string(&mut self, s: &str) -> ControlFlow<()> {
+        if std::mem::take(&mut self.armed) {
+            self.found.push(s.to_string());
+            if self.found.len() == self.limit {
+                return ControlFlow::Break(());
+            }
+        }
+        ControlFlow::Continue(())
+    }
+    fn on_object_start(&mut self) -> ControlFlow<()> {
+        self.armed = false;
+        ControlFlow::Continue(())
+    }
+    fn on_array_start(&mut self) -> ControlFlow<()> {
+        self.armed = false;
+        ControlFlow::Continue(())
+    }
+}
+
+fn collect(input: &str, key: &str, limit: usize) -> (Result<ControlFlow<()>, FdonError>, Vec<String>) {
+    let mut v = CollectKey { key, armed: false, found: Vec::new(), limit };
+    let result = parse_events(input, &mut v);
+    (result, v.found)
+}
+
+const USERS: &str = r#"O{users:A[O{name:S"An",tags:A[S"x"]},O{name:SE"Bình",age:N30}],meta:O{name:S"list"}}"#;
+
+// --- parse_events ---
+
+#[test]
+fn events_arrive_in_document_order() {
+    let mut log = Log::default();
+    let doc = r#"O{a:A[N1,N-2.5,Btrue,U],b:SE"x\ny",c:D"2024-01-02",d:T"10:00",e:T5,f:O{}}"#;
+    assert_eq!(parse_events(doc, &mut log).unwrap(), ControlFlow::Continue(()));
+    let expected = [
+        "{", "a:", "[", "nInteger(1)", "nFloat(-2.5)", "btrue", "null", "]", "b:", r#"s"x\ny""#, "c:",
+        r#"d"2024-01-02""#, "d:", r#"s"10:00""#, "e:", "nInteger(5)", "f:", "{", "}", "}",
+    ];
+    assert_eq!(log.0, expected);
+}
+
+#[test]
+fn visitor_collects_only_values_at_a_key() {
+    let (result, found) = collect(USERS, "name", usize::MAX);
+    assert_eq!(result.unwrap(), ControlFlow::Continue(()));
+    assert_eq!(found, ["An", "B\u{ec}nh", "list"]);
+    // Value không phải chuỗi dưới key đó thì bỏ qua
+    assert!(collect(USERS, "age", usize::MAX).1.is_empty());
+    assert!(collect(USERS, "tags", usize::MAX).1.is_empty());
+}
+
+#[test]
+fn break_stops_before_later_errors() {
+    // Lỗi nằm sau value thứ nhất: dừng sớm thì không thấy lỗi
+    let broken = r#"O{name:S"An",rest:A[N1,]}"#;
+    let (result, found) = collect(broken, "name", 1);
+    assert_eq!(result.unwrap(), ControlFlow::Break(()));
+    assert_eq!(found, ["An"]);
+
+    // Không dừng: các sự kiện trước lỗi vẫn được gọi, rồi mới tới lỗi của parser
+    let (result, found) = collect(broken, "name", 2);
+    let err = result.unwrap_err();
+    let arena = Bump::new();
+    let expected = parse_fdon_zero_copy_arena(broken, &arena).unwrap_err();
+    assert_eq!(format!("{:?}", err), format!("{:?}", expected));
+    assert_eq!(found, ["An"]);
+}
+
+#[test]
+fn escaped_keys_arrive_unescaped() {
+    let mut log = Log::default();
+    let doc = r#"O{SE"a\"b:c":N1,SE"plain":O{SE"\u{1F600}":U}}"#;
+    assert_eq!(parse_events(doc, &mut log).unwrap(), ControlFlow::Continue(()));
+    assert_eq!(log.0, ["{", "a\"b:c:", "nInteger(1)", "plain:", "{", "\u{1F600}:", "null", "}", "}"]);
+}
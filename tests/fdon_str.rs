@@ -0,0 +1,42 @@
+use bumpalo::Bump;
+use fdon_rs::{parse_fdon_zero_copy_arena, FdonStr, FdonValue};
+
+fn escaped<'v, 'a, 'bump>(value: &'v FdonValue<'a, 'bump>) -> &'v FdonStr<'a, 'bump> {
+    match value {
+        FdonValue::EscapedString(s) => s,
+        other => panic!("not SE: {:?}", other),
+    }
+}
+
+// --- SE"..." không có escape thì mượn input ---
+
+#[test]
+fn zero_escape_string_borrows_the_input() {
+    let arena = Bump::new();
+    let input = r#"SE"plain text, no escapes""#;
+    let value = parse_fdon_zero_copy_arena(input, &arena).unwrap();
+    let s = escaped(&value);
+    assert!(s.is_borrowed());
+    assert_eq!(s.as_str(), "plain text, no escapes");
+    // Đúng là slice của input, và arena không cấp phát gì
+    assert_eq!(s.as_ptr(), input[3..].as_ptr());
+    assert_eq!(arena.allocated_bytes(), 0);
+}
+
+#[test]
+fn strings_with_escapes_are_unescaped_into_the_arena() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(r#"A[SE"",SE"a\nb",SE"tail\\",SE"\u00e9"]"#, &arena).unwrap();
+    let items = value.as_array().unwrap();
+    assert!(escaped(&items[0]).is_borrowed());
+    for (item, expected) in items[1..].iter().zip(["a\nb", "tail\\", "é"]) {
+        let s = escaped(item);
+        assert!(!s.is_borrowed(), "{:?}", s);
+        assert_eq!(s.as_str(), expected);
+    }
+    // Hai dạng so sánh bằng nhau theo nội dung
+    let borrowed = parse_fdon_zero_copy_arena(r#"SE"ab""#, &arena).unwrap();
+    let owned = parse_fdon_zero_copy_arena(r#"SE"\u0061b""#, &arena).unwrap();
+    assert!(escaped(&borrowed).is_borrowed() && !escaped(&owned).is_borrowed());
+    assert_eq!(borrowed, owned);
+}
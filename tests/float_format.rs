@@ -0,0 +1,64 @@
+use bumpalo::Bump;
+use fdon_rs::{
+    fdon_to_json_writer_with_options, parse_fdon_zero_copy_arena, to_fdon_writer_with_options, FdonNumber, FdonValue,
+    FloatFormat, SerializeOptions,
+};
+
+fn write_both(f: f64, float_format: FloatFormat) -> (String, String) {
+    let value = FdonValue::Number(FdonNumber::Float(f));
+    let options = SerializeOptions { float_format, ..SerializeOptions::default() };
+    let (mut fdon, mut json) = (Vec::new(), Vec::new());
+    to_fdon_writer_with_options(&value, &mut fdon, &options).unwrap();
+    fdon_to_json_writer_with_options(&value, &mut json, &options).unwrap();
+    (String::from_utf8(fdon).unwrap(), String::from_utf8(json).unwrap())
+}
+
+#[test]
+fn fixed_rounds_the_exact_binary_value() {
+    // Làm tròn theo giá trị nhị phân thật: 2.675 thực ra là 2.67499..., 0.125 hòa -> chẵn
+    let cases = [(2.675, "2.67"), (0.125, "0.12"), (0.375, "0.38"), (1.005, "1.00"), (-0.5, "-0.50"), (9.87654, "9.88")];
+    for (f, expected) in cases {
+        let (fdon, json) = write_both(f, FloatFormat::Fixed(2));
+        assert_eq!(json, expected);
+        assert_eq!(fdon, format!("N{}", expected));
+    }
+    assert_eq!(write_both(2.5, FloatFormat::Fixed(0)), ("N2".to_string(), "2".to_string()));
+    assert_eq!(write_both(1e21, FloatFormat::Exponential(2)).1, "1.00e21");
+}
+
+#[test]
+fn fixed_output_is_the_same_every_time() {
+    for f in [0.1, 2.0 / 3.0, 123456.789, -1e-7] {
+        let first = write_both(f, FloatFormat::Fixed(4));
+        for _ in 0..3 {
+            assert_eq!(write_both(f, FloatFormat::Fixed(4)), first);
+        }
+    }
+}
+
+#[test]
+fn shortest_round_trips_exactly() {
+    let arena = Bump::new();
+    let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+    let mut values = vec![0.1, 1.0 / 3.0, 5e-324, f64::MAX, f64::MIN_POSITIVE, -0.0, 1e21, 123.0];
+    for _ in 0..2000 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let f = f64::from_bits(state);
+        if f.is_finite() {
+            values.push(f);
+        }
+    }
+    for f in values {
+        let (fdon, json) = write_both(f, FloatFormat::Shortest);
+        let back = parse_fdon_zero_copy_arena(&fdon, &arena).unwrap();
+        match back {
+            FdonValue::Number(FdonNumber::Float(g)) => assert_eq!(g.to_bits(), f.to_bits(), "{}", fdon),
+            other => panic!("{} read back as {:?}", fdon, other),
+        }
+        // serde_json (không bật float_roundtrip) có thể lệch 1 ulp khi đọc -> dùng str::parse
+        let g: f64 = json.parse().unwrap();
+        assert_eq!(g.to_bits(), f.to_bits(), "{}", json);
+    }
+}
@@ -0,0 +1,83 @@
+use bumpalo::Bump;
+use fdon_rs::{parse_fdon_zero_copy_arena, FdonError, IncrementalParser, Progress};
+
+const DOCS: [&str; 5] = [
+    r#"O{a:N1,b:A[S"x,y]",SE"q\"\u{1F600}",D"2024-01-02"],c:O{}}"#,
+    "A[]",
+    r#"S"plain""#,
+    r#"SE"é\n""#,
+    "A[N-1.5e3,T5,Btrue,U]",
+];
+
+// --- Nạp từng byte ---
+
+#[test]
+fn one_byte_at_a_time() {
+    let arena = Bump::new();
+    for doc in DOCS {
+        let mut p = IncrementalParser::new();
+        for (i, byte) in doc.bytes().enumerate() {
+            let progress = p.feed(&[byte]).unwrap();
+            let remaining = doc.len() - i - 1;
+            match progress {
+                Progress::Complete { len } => {
+                    assert_eq!((remaining, len), (0, doc.len()), "{}", doc);
+                }
+                Progress::Incomplete { bytes_needed_hint } => {
+                    // Gợi ý là cận dưới: không bao giờ vượt quá số byte thực sự còn thiếu
+                    assert!(bytes_needed_hint >= 1 && bytes_needed_hint <= remaining, "{} at {}", doc, i);
+                }
+            }
+        }
+        let expected = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+        assert!(p.value(&arena).unwrap().eq_strict(&expected), "{}", doc);
+    }
+}
+
+#[test]
+fn frames_follow_each_other_on_one_stream() {
+    let arena = Bump::new();
+    let stream = format!("{}\n{} {}", DOCS[0], DOCS[1], DOCS[4]);
+    let mut p = IncrementalParser::new();
+    let mut expected = [DOCS[0], DOCS[1], DOCS[4]].into_iter();
+    for chunk in stream.as_bytes().chunks(7) {
+        let mut progress = p.feed(chunk).unwrap();
+        while let Progress::Complete { .. } = progress {
+            let doc = expected.next().unwrap();
+            let want = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+            assert!(p.value(&arena).unwrap().eq_strict(&want), "{}", doc);
+            progress = p.next_frame().unwrap();
+        }
+    }
+    assert!(expected.next().is_none());
+    assert!(p.buffered().is_empty());
+}
+
+// --- Số ở gốc, EOF và lỗi ---
+
+#[test]
+fn root_number_completes_only_at_finish() {
+    let arena = Bump::new();
+    let mut p = IncrementalParser::new();
+    assert!(matches!(p.feed(b"N12").unwrap(), Progress::Incomplete { .. }));
+    assert!(matches!(p.value(&arena), Err(FdonError::UnexpectedEof { .. })));
+    assert_eq!(p.finish().unwrap(), Progress::Complete { len: 3 });
+    assert_eq!(p.value(&arena).unwrap().as_i64(), Some(12));
+
+    let mut p = IncrementalParser::new();
+    p.feed(b"A[N1,").unwrap();
+    assert!(p.finish().is_err());
+    assert!(matches!(IncrementalParser::new().finish(), Err(FdonError::UnexpectedEof { .. })));
+}
+
+#[test]
+fn errors_are_reported_as_soon_as_the_bad_byte_arrives() {
+    let mut p = IncrementalParser::new();
+    assert!(p.feed(b"A[N1,").is_ok());
+    let err = p.feed(b"]").unwrap_err();
+    assert!(matches!(err, FdonError::TrailingComma { pos: 5, .. }), "{:?}", err);
+
+    let mut p = IncrementalParser::new();
+    let err = p.feed(b"O{a:X").unwrap_err();
+    assert!(matches!(err, FdonError::UnknownTypeSpecifier { byte: b'X', pos: 4 }), "{:?}", err);
+}
@@ -0,0 +1,78 @@
+use bumpalo::Bump;
+use fdon_rs::{
+    parse_fdon_zero_copy_arena, parse_fdon_zero_copy_arena_with_options, to_json_value, FdonNumber, FdonValue, ParseOptions,
+};
+use serde_json::{json, Value};
+
+// --- to_json_value / to_json_ordered ---
+
+#[test]
+fn json_value_matches_serde_output() {
+    let arena = Bump::new();
+    let doc = r#"O{a:A[N1,N-2.5,N18446744073709551615,Btrue,U,O{}],b:O{c:SE"x\ny",d:D"2024-01-02",t:T"10:00",ts:T5},e:A[]}"#;
+    let value = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+    assert_eq!(to_json_value(&value), serde_json::to_value(&value).unwrap());
+    assert_eq!(value.to_json_ordered(), to_json_value(&value));
+}
+
+#[test]
+fn json_value_of_each_variant() {
+    let arena = Bump::new();
+    let cases = [
+        ("U", json!(null)),
+        ("Btrue", json!(true)),
+        ("N-5", json!(-5)),
+        ("N18446744073709551615", json!(u64::MAX)),
+        ("N2.5", json!(2.5)),
+        ("T1700000000", json!(1_700_000_000)),
+        (r#"S"raw""#, json!("raw")),
+        (r#"SE"a\tb""#, json!("a\tb")),
+        (r#"D"2024-01-02""#, json!("2024-01-02")),
+        (r#"T"10:00:00""#, json!("10:00:00")),
+        ("A[N1,A[],O{}]", json!([1, [], {}])),
+        (r#"O{k:S"v",n:O{m:U}}"#, json!({"k": "v", "n": {"m": null}})),
+    ];
+    for (doc, expected) in cases {
+        let value = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+        assert_eq!(to_json_value(&value), expected, "{}", doc);
+    }
+    // JSON không có NaN/Infinity
+    for f in [f64::NAN, f64::INFINITY] {
+        assert_eq!(to_json_value(&FdonValue::Number(FdonNumber::Float(f))), json!(null));
+    }
+}
+
+#[test]
+fn json_value_outlives_the_arena() {
+    let json = {
+        let input = String::from(r#"O{a:A[SE"x\ny",N1]}"#);
+        let arena = Bump::new();
+        let value = parse_fdon_zero_copy_arena(&input, &arena).unwrap();
+        to_json_value(&value)
+    };
+    assert_eq!(json, json!({"a": ["x\ny", 1]}));
+}
+
+#[test]
+fn json_value_of_a_deep_tree() {
+    let arena = Bump::new();
+    let depth = 100_000;
+    let input = format!("{}N7{}", "A[O{k:".repeat(depth), "}]".repeat(depth));
+    let options = ParseOptions { max_depth: usize::MAX, ..ParseOptions::default() };
+    let value = parse_fdon_zero_copy_arena_with_options(&input, &arena, options).unwrap();
+
+    let json = to_json_value(&value);
+    let mut levels = 0;
+    let mut node = &json;
+    while let Value::Array(items) = node {
+        assert_eq!(items.len(), 1);
+        node = &items[0]["k"];
+        levels += 1;
+    }
+    assert_eq!(levels, depth);
+    assert_eq!(node, &Value::from(7));
+
+    // Drop của cả hai cây đều đệ quy: bỏ qua, bộ nhớ được trả lại khi test kết thúc
+    std::mem::forget(json);
+    std::mem::forget(value);
+}
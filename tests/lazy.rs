@@ -0,0 +1,87 @@
+use bumpalo::Bump;
+use fdon_rs::{parse_fdon_zero_copy_arena, parse_lazy_object, FdonError, ParseOptions};
+
+const WIDE: &str = r#"O{id:N7,name:S"An",big:A[O{x:N1},O{x:N2}],nested:O{a:SE"x\ny"},id2:N1e3}"#;
+
+// --- parse_lazy_object ---
+
+#[test]
+fn values_match_a_full_parse() {
+    let arena = Bump::new();
+    let full = parse_fdon_zero_copy_arena(WIDE, &arena).unwrap();
+    let lazy = parse_lazy_object(WIDE, &arena, ParseOptions::default()).unwrap();
+    assert_eq!(lazy.len(), 5);
+    assert_eq!(lazy.keys().collect::<Vec<_>>(), ["id", "name", "big", "nested", "id2"]);
+    for key in lazy.keys() {
+        assert!(lazy.get(key).unwrap().unwrap().eq_strict(&full[key]), "{}", key);
+    }
+    assert!(lazy.get("missing").unwrap().is_none());
+    assert!(!lazy.contains_key("missing") && lazy.contains_key("big"));
+}
+
+#[test]
+fn a_value_is_parsed_once_and_cached() {
+    let arena = Bump::new();
+    let lazy = parse_lazy_object(WIDE, &arena, ParseOptions::default()).unwrap();
+    let first: *const _ = lazy.get("nested").unwrap().unwrap();
+    let used = arena.allocated_bytes();
+    let second: *const _ = lazy.get("nested").unwrap().unwrap();
+    assert_eq!(first, second);
+    assert_eq!(arena.allocated_bytes(), used);
+}
+
+#[test]
+fn a_repeated_key_keeps_its_first_position_and_last_value() {
+    let arena = Bump::new();
+    let lazy = parse_lazy_object("O{a:N1,b:N2,a:N3}", &arena, ParseOptions::default()).unwrap();
+    assert_eq!(lazy.keys().collect::<Vec<_>>(), ["a", "b"]);
+    assert_eq!(lazy.get("a").unwrap().unwrap().as_i64(), Some(3));
+
+    let options = ParseOptions { reject_duplicate_keys: true, ..ParseOptions::default() };
+    let err = parse_lazy_object("O{a:N1,b:N2,a:N3}", &arena, options).err().unwrap();
+    assert!(matches!(err, FdonError::DuplicateKey { ref key, pos: 12 } if key == "a"), "{:?}", err);
+}
+
+// --- Lỗi: lúc quét hay lúc get ---
+
+#[test]
+fn structure_errors_fail_the_scan() {
+    let arena = Bump::new();
+    let scan = |input: &str| parse_lazy_object(input, &arena, ParseOptions::default()).err().unwrap();
+    assert!(matches!(scan("A[N1]"), FdonError::Expected { expected: b'O', found: Some(b'A'), pos: 0 }));
+    assert!(matches!(scan(r#"{"a":1}"#), FdonError::LooksLikeJson { pos: 0 }));
+    assert!(matches!(scan("O{a:A[N1,]}"), FdonError::TrailingComma { pos: 9, .. }));
+    assert!(matches!(scan("O{a:N1}N2"), FdonError::ExtraData { pos: 7 }));
+}
+
+#[test]
+fn value_errors_surface_on_get() {
+    let arena = Bump::new();
+    // N1x chỉ bị phát hiện khi value đó được parse
+    let lazy = parse_lazy_object("O{ok:N1,bad:A[N1x]}", &arena, ParseOptions::default()).unwrap();
+    assert_eq!(lazy.get("ok").unwrap().unwrap().as_i64(), Some(1));
+    let err = lazy.get("bad").unwrap_err();
+    assert!(matches!(err, FdonError::InvalidInteger { pos: 16 }), "{:?}", err);
+    // Lỗi không được cache thành value
+    assert!(lazy.get("bad").is_err());
+}
+
+#[test]
+fn depth_counts_the_lazy_root() {
+    let arena = Bump::new();
+    let options = ParseOptions { max_depth: 2, ..ParseOptions::default() };
+    let lazy = parse_lazy_object("O{a:A[N1],b:O{c:U}}", &arena, options).unwrap();
+    assert!(lazy.get("a").is_ok() && lazy.get("b").is_ok());
+    // Quét cấu trúc đã kiểm tra độ sâu, giống skip_value
+    let err = parse_lazy_object("O{a:A[N1],b:A[A[]]}", &arena, options).err().unwrap();
+    assert!(matches!(err, FdonError::DepthLimitExceeded { pos: 14 }), "{:?}", err);
+}
+
+#[test]
+fn escaped_keys_are_unescaped_into_the_arena() {
+    let arena = Bump::new();
+    let lazy = parse_lazy_object(r#"O{SE"a\"b:c":N1,SE"x":O{SE"\n":N2}}"#, &arena, ParseOptions::default()).unwrap();
+    assert_eq!(lazy.keys().collect::<Vec<_>>(), ["a\"b:c", "x"]);
+    assert_eq!(lazy.get("a\"b:c").unwrap().unwrap().as_i64(), Some(1));
+    assert_eq!(lazy.get("x").unwrap().unwrap()["\n"].as_i64(), Some(2));
+}
@@ -0,0 +1,45 @@
+use bumpalo::Bump;
+use fdon_rs::{
+    minify_fdon, parse_fdon_lenient, parse_fdon_zero_copy_arena, prettify_fdon, FdonError, PrettyOptions,
+};
+
+// --- parse_fdon_lenient (allow_whitespace) ---
+
+#[test]
+fn pretty_output_parses_without_minify() {
+    let arena = Bump::new();
+    let doc = r#"O{name:S"a  b",tags:A[D"2024 01 02",T"10 : 00",SE"x\t y"],nested:O{e:A[],o:O{}},n:N-1.5}"#;
+    let value = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+    for opts in [PrettyOptions::default(), PrettyOptions { use_tabs: true, ..PrettyOptions::default() }] {
+        let pretty = prettify_fdon(&value, opts);
+        assert!(pretty.contains('\n'));
+        let lenient = parse_fdon_lenient(&pretty, &arena).unwrap();
+        assert!(lenient.eq_strict(&value), "{}", pretty);
+        // Giống hệt con đường cũ: minify rồi parse
+        let minified = minify_fdon(&pretty);
+        assert!(parse_fdon_zero_copy_arena(&minified, &arena).unwrap().eq_strict(&lenient));
+    }
+}
+
+#[test]
+fn whitespace_inside_strings_is_kept() {
+    let arena = Bump::new();
+    let input = " \r\n\tA [ S\" a \" , SE\" b\\n \" ,\r\n D\"2024 01 01\" , T\" 10:00 \" ] \n";
+    let value = parse_fdon_lenient(input, &arena).unwrap();
+    let items: Vec<&str> = value.as_array().unwrap().iter().map(|v| v.as_str().unwrap()).collect();
+    assert_eq!(items, [" a ", " b\n ", "2024 01 01", " 10:00 "]);
+}
+
+#[test]
+fn keys_keep_inner_spaces_and_tokens_stay_whole() {
+    let arena = Bump::new();
+    let value = parse_fdon_lenient("O {\n  a b : N1 ,\n  c:N2\n}", &arena).unwrap();
+    let obj = value.as_object().unwrap();
+    assert_eq!(obj.keys().collect::<Vec<_>>(), ["a b", "c"]);
+
+    // Khoảng trắng giữa một token vẫn là lỗi; vị trí tính trên input gốc
+    let err = parse_fdon_lenient("A[\n  N1 2\n]", &arena).unwrap_err();
+    assert!(matches!(err, FdonError::MissingComma { pos: 8, .. }), "{:?}", err);
+    let err = parse_fdon_lenient("A[ S \"x\" ]", &arena).unwrap_err();
+    assert!(matches!(err, FdonError::Expected { expected: b'"', pos: 4, .. }), "{:?}", err);
+}
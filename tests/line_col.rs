@@ -0,0 +1,53 @@
+use bumpalo::Bump;
+use fdon_rs::{
+    line_col_at, minify_fdon_with_options, parse_fdon_zero_copy_arena, parse_fdon_zero_copy_arena_with_options,
+    source_offset, ParseOptions,
+};
+
+// Lỗi ở dòng 3: `N1x` thiếu dấu phẩy, `x` ở cột 8
+const DOC: &str = "O{\n  a: N1,\n  b: N1x,\n  c: N3\n}";
+
+// --- line_col_at ---
+
+#[test]
+fn line_col_at_counts_lines_and_chars() {
+    assert_eq!(line_col_at(DOC, 0), (1, 1));
+    assert_eq!(line_col_at(DOC, 2), (1, 3));
+    assert_eq!(line_col_at(DOC, 3), (2, 1));
+    assert_eq!(line_col_at(DOC, DOC.find("x").unwrap()), (3, 8));
+    // Quá cuối input: kẹp về cuối
+    assert_eq!(line_col_at(DOC, usize::MAX), (5, 2));
+
+    // Cột đếm theo ký tự; vị trí giữa một ký tự nhiều byte lùi về đầu ký tự đó
+    let text = "A[\nS\"é€\",Q]";
+    let q = text.find('Q').unwrap();
+    assert_eq!(line_col_at(text, q), (2, 7));
+    let inside_euro = text.find('€').unwrap() + 1;
+    assert_eq!(line_col_at(text, inside_euro), (2, 4));
+}
+
+// --- Lỗi parse trên tài liệu nhiều dòng ---
+
+#[test]
+fn error_on_line_three_with_whitespace_allowed() {
+    let arena = Bump::new();
+    let options = ParseOptions { allow_whitespace: true, ..ParseOptions::default() };
+    let err = parse_fdon_zero_copy_arena_with_options(DOC, &arena, options).unwrap_err();
+    assert_eq!(line_col_at(DOC, err.pos().unwrap()), (3, 8), "{:?}", err);
+}
+
+#[test]
+fn minified_error_maps_back_to_line_three() {
+    let arena = Bump::new();
+    let options = ParseOptions { allow_comments: true, ..ParseOptions::default() };
+    let source = "# config\nO{\n  b: N1x, # lỗi ở đây\n  c: N3\n}";
+    let minified = minify_fdon_with_options(source, &options);
+    let err = parse_fdon_zero_copy_arena(&minified, &arena).unwrap_err();
+    let pos = err.pos().unwrap();
+    // Vị trí trong bản đã minify không còn khớp với dòng/cột gốc
+    assert_eq!(line_col_at(&minified, pos).0, 1);
+
+    let original = source_offset(source, &options, pos);
+    assert_eq!(&source[original..original + 1], "x");
+    assert_eq!(line_col_at(source, original), (3, 8));
+}
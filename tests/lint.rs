@@ -0,0 +1,69 @@
+use fdon_rs::{lint_duplicate_keys, DuplicateKey, FdonError, ParseOptions};
+
+fn lint(input: &str) -> Vec<(&str, usize, usize)> {
+    lint_duplicate_keys(input, ParseOptions::default())
+        .unwrap()
+        .into_iter()
+        .map(|DuplicateKey { key, first_pos, dup_pos }| (key, first_pos, dup_pos))
+        .collect()
+}
+
+// --- lint_duplicate_keys ---
+
+#[test]
+fn every_duplicate_in_every_object_is_reported() {
+    //          0         1         2         3         4
+    //          0123456789012345678901234567890123456789012345
+    let input = "O{a:N1,b:A[O{x:U,x:U}],a:N2,c:O{b:U},a:N3}";
+    assert_eq!(lint(input), [("x", 13, 17), ("a", 2, 23), ("a", 2, 37)]);
+}
+
+#[test]
+fn same_key_in_different_objects_is_not_a_duplicate() {
+    assert!(lint("A[O{a:N1},O{a:N2},O{b:O{a:U}}]").is_empty());
+    assert!(lint("O{a:O{a:O{a:U}}}").is_empty());
+    assert!(lint("N1").is_empty());
+}
+
+#[test]
+fn quoted_keys_and_whitespace() {
+    assert_eq!(lint(r#"O{"a:b":N1,"a:b":N2}"#), [("a:b", 2, 11)]);
+
+    let options = ParseOptions { allow_whitespace: true, ..ParseOptions::default() };
+    let found = lint_duplicate_keys("O{\n  k: N1,\n  k: N2\n}", options).unwrap();
+    assert_eq!(found, [DuplicateKey { key: "k", first_pos: 5, dup_pos: 14 }]);
+}
+
+#[test]
+fn syntax_errors_abort_the_lint() {
+    let err = lint_duplicate_keys("O{a:N1,a:N2,}", ParseOptions::default()).unwrap_err();
+    assert!(matches!(err, FdonError::TrailingComma { pos: 12, .. }), "{:?}", err);
+    let err = lint_duplicate_keys("O{a:N1}N2", ParseOptions::default()).unwrap_err();
+    assert!(matches!(err, FdonError::ExtraData { pos: 7 }), "{:?}", err);
+}
+
+#[test]
+fn escaped_keys_are_compared_unescaped() {
+    //          0         1         2         3
+    //          0123456789012345678901234567890123456
+    let input = r#"O{SE"a\u{62}":N1,ab:N2,SE"a\u{62}":U}"#;
+    // Key được báo đúng như đã viết
+    assert_eq!(lint(input), [("ab", 2, 17), (r"a\u{62}", 2, 23)]);
+    assert_eq!(lint(r#"O{SE"x":N1,x:N2}"#), [("x", 2, 11)]);
+}
+
+#[test]
+fn deep_nesting_does_not_recurse() {
+    // Mỗi tầng là O{k:...,k:U}: key trùng ở mọi tầng, sâu hơn nhiều so với call stack
+    let depth = 200_000;
+    let input = format!("{}N1{}", "O{k:".repeat(depth), ",k:U}".repeat(depth));
+    let options = ParseOptions { max_depth: usize::MAX, ..ParseOptions::default() };
+    let found = lint_duplicate_keys(&input, options).unwrap();
+    assert_eq!(found.len(), depth);
+    // Tầng trong cùng đóng trước
+    assert_eq!(found[0], DuplicateKey { key: "k", first_pos: (depth - 1) * 4 + 2, dup_pos: depth * 4 + 3 });
+
+    let options = ParseOptions { max_depth: 1000, ..ParseOptions::default() };
+    let err = lint_duplicate_keys(&input, options).unwrap_err();
+    assert!(matches!(err, FdonError::DepthLimitExceeded { pos: 4000 }), "{:?}", err);
+}
@@ -0,0 +1,61 @@
+use bumpalo::Bump;
+use fdon_rs::{minify_fdon, parse_fdon_zero_copy_arena};
+
+// --- SE chỉ là tag ở vị trí value ---
+
+#[test]
+fn se_inside_keys_is_not_a_string_opener() {
+    // Key chứa "SE" theo sau bởi khoảng trắng: phải bị nén như mọi key khác
+    assert_eq!(minify_fdon("O{ USE : N1 , CASE\"x : S\"a b\" }"), "O{USE:N1,CASE\"x:S\"a b\"}");
+    assert_eq!(minify_fdon("O{\n  SE: S\" keep  this \",\n  BASE: N1\n}"), "O{SE:S\" keep  this \",BASE:N1}");
+}
+
+#[test]
+fn se_inside_raw_strings_is_plain_text() {
+    let input = "A[ S\"USE \" , N1 , S\"x SE\" , SE\" a \\\" b \" ]";
+    let minified = minify_fdon(input);
+    assert_eq!(minified, "A[S\"USE \",N1,S\"x SE\",SE\" a \\\" b \"]");
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(&minified, &arena).unwrap();
+    let items: Vec<&str> = value.as_array().unwrap().iter().filter_map(|v| v.as_str()).collect();
+    assert_eq!(items, ["USE ", "x SE", " a \" b "]);
+}
+
+#[test]
+fn se_at_value_positions_keeps_escaped_quotes() {
+    // Sau ':', '[', ',' và ở gốc: \" không đóng chuỗi
+    assert_eq!(minify_fdon(r#"SE" a\" b ""#), r#"SE" a\" b ""#);
+    assert_eq!(minify_fdon(r#"O{ k : SE" \" , " , j : SE"\\" }"#), r#"O{k:SE" \" , ",j:SE"\\"}"#);
+}
+
+#[test]
+fn se_keys_keep_their_content() {
+    let minified = minify_fdon(r#"O{ SE" a \" b : c " : N1 , SE : N2 }"#);
+    assert_eq!(minified, r#"O{SE" a \" b : c ":N1,SE:N2}"#);
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(&minified, &arena).unwrap();
+    assert_eq!(value[" a \" b : c "].as_i64(), Some(1));
+    assert_eq!(value["SE"].as_i64(), Some(2));
+}
+
+// --- Nhiều chuỗi raw liên tiếp ---
+
+#[test]
+fn whitespace_survives_in_consecutive_raw_strings() {
+    let input = "A[ S\"a b\" , S\" c \" ,D\"2024 01 01\",T\"10 : 00\" , S\"\" , D\" \" ]";
+    let minified = minify_fdon(input);
+    assert_eq!(minified, "A[S\"a b\",S\" c \",D\"2024 01 01\",T\"10 : 00\",S\"\",D\" \"]");
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(&minified, &arena).unwrap();
+    let items: Vec<&str> = value.as_array().unwrap().iter().filter_map(|v| v.as_str()).collect();
+    assert_eq!(items, ["a b", " c ", "2024 01 01", "10 : 00", "", " "]);
+}
+
+#[test]
+fn adjacent_strings_in_objects_and_keys() {
+    let input = "O{ a : S\"x y\" , b:D\"2024 01 01\", \"q k\" : T\" t \" , d : A[ S\"1 2\",S\"3 4\" ] }";
+    assert_eq!(
+        minify_fdon(input),
+        "O{a:S\"x y\",b:D\"2024 01 01\",\"q k\":T\" t \",d:A[S\"1 2\",S\"3 4\"]}"
+    );
+}
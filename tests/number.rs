@@ -0,0 +1,212 @@
+use bumpalo::Bump;
+use fdon_rs::{
+    parse_fdon_zero_copy_arena, parse_fdon_zero_copy_arena_with_options, to_fdon_string, FdonNumber, ParseOptions, Validator,
+};
+
+fn same(a: FdonNumber, b: FdonNumber) -> bool {
+    // Float so bằng bit để NaN/-0.0 cũng so được
+    match (a, b) {
+        (FdonNumber::Float(x), FdonNumber::Float(y)) => x.to_bits() == y.to_bits(),
+        _ => a.eq_strict(&b),
+    }
+}
+
+// --- checked_add / Sum ---
+
+#[test]
+fn checked_add_keeps_integers_and_promotes() {
+    use FdonNumber::{Float, Integer, UInt};
+    let cases = [
+        (Integer(2), Integer(3), Integer(5)),
+        (Integer(-7), UInt(5), Integer(-2)),
+        // Vượt i64 thì thành UInt, vượt u64 mới thành Float
+        (Integer(i64::MAX), Integer(1), UInt(i64::MAX as u64 + 1)),
+        (UInt(u64::MAX), Integer(-1), UInt(u64::MAX - 1)),
+        (UInt(u64::MAX), UInt(1), Float(u64::MAX as f64 + 1.0)),
+        (Integer(i64::MIN), Integer(-1), Float(i64::MIN as f64 - 1.0)),
+        (Integer(1), Float(0.5), Float(1.5)),
+        (Float(0.25), UInt(2), Float(2.25)),
+    ];
+    for (a, b, expected) in cases {
+        assert!(same(a.checked_add(b), expected), "{:?} + {:?} = {:?}", a, b, a.checked_add(b));
+        assert!(same(b.checked_add(a), expected), "{:?} + {:?}", b, a);
+    }
+}
+
+#[test]
+fn sum_over_a_parsed_array() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena("A[N1,N2,N3,N-4]", &arena).unwrap();
+    let numbers: Vec<FdonNumber> = value
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|v| match v {
+            fdon_rs::FdonValue::Number(n) => Some(*n),
+            _ => None,
+        })
+        .collect();
+    assert!(same(numbers.iter().sum(), FdonNumber::Integer(2)));
+    assert!(same(numbers.into_iter().chain([FdonNumber::Float(0.5)]).sum(), FdonNumber::Float(2.5)));
+    assert!(same(std::iter::empty::<FdonNumber>().sum(), FdonNumber::Integer(0)));
+}
+
+// --- Số mũ và dấu '+' ---
+
+#[test]
+fn scientific_notation_and_plus_sign() {
+    use FdonNumber::{Float, Integer, UInt};
+    let arena = Bump::new();
+    let cases = [
+        ("N1e10", Float(1e10)),
+        ("N1.5E-3", Float(1.5e-3)),
+        ("N6.022e23", Float(6.022e23)),
+        ("N-2E+2", Float(-200.0)),
+        ("N+5", Integer(5)),
+        ("N+2.5", Float(2.5)),
+        ("N1000000", Integer(1_000_000)),
+        ("N-0", Integer(0)),
+        ("N18446744073709551615", UInt(u64::MAX)),
+        ("T1.7e9", Float(1.7e9)),
+    ];
+    for (input, expected) in cases {
+        let got = match parse_fdon_zero_copy_arena(input, &arena).unwrap() {
+            fdon_rs::FdonValue::Number(n) | fdon_rs::FdonValue::Timestamp(n) => n,
+            other => panic!("{}: {:?}", input, other),
+        };
+        assert!(same(got, expected), "{}: {:?}", input, got);
+    }
+    // Số mũ thiếu chữ số, dấu lặp
+    assert!(parse_err("N1e", ParseOptions::default()).starts_with("InvalidFloat"));
+    assert!(parse_err("N1e+", ParseOptions::default()).starts_with("InvalidFloat"));
+    assert_eq!(parse_err("N++5", ParseOptions::default()), "InvalidInteger { pos: 2 }");
+}
+
+// --- Số không hợp lệ và số không hữu hạn ---
+
+fn parse_err(input: &str, options: ParseOptions) -> String {
+    let arena = Bump::new();
+    let err = parse_fdon_zero_copy_arena_with_options(input, &arena, options).unwrap_err();
+    // Validator dùng chung đường đọc số: phải ra đúng lỗi đó
+    let mut v = Validator::with_options(options);
+    let streamed = v.feed(input.as_bytes()).and_then(|_| v.finish()).unwrap_err();
+    assert_eq!(format!("{:?}", streamed), format!("{:?}", err), "{}", input);
+    format!("{:?}", err)
+}
+
+#[test]
+fn malformed_numbers_point_at_the_offending_byte() {
+    let options = ParseOptions::default();
+    assert_eq!(parse_err("A[N1.2.3]", options), r#"InvalidFloat { detail: "unexpected `.`", pos: 6 }"#);
+    assert_eq!(parse_err("A[N--5]", options), "InvalidInteger { pos: 4 }");
+    assert_eq!(parse_err("A[N1_000]", options), "InvalidInteger { pos: 4 }");
+    assert_eq!(parse_err("A[N12x]", options), "InvalidInteger { pos: 5 }");
+    assert_eq!(parse_err("A[N-]", options), "InvalidInteger { pos: 3 }");
+    assert_eq!(parse_err("A[N,N1]", options), "EmptyNumber { pos: 3 }");
+    assert_eq!(parse_err("O{a:N}", options), "EmptyNumber { pos: 5 }");
+}
+
+#[test]
+fn non_finite_floats_are_rejected() {
+    let options = ParseOptions::default();
+    for input in ["N1e999", "N-1e999", "A[N1.5E400]", "O{t:T9e9999}"] {
+        let err = parse_err(input, options);
+        assert!(err.starts_with(r#"InvalidFloat { detail: "not a finite number""#), "{}: {}", input, err);
+    }
+    // Tràn về 0 thì vẫn là số hữu hạn
+    let arena = Bump::new();
+    assert_eq!(parse_fdon_zero_copy_arena("N1e-999", &arena).unwrap().as_f64(), Some(0.0));
+    assert_eq!(parse_fdon_zero_copy_arena("N1.7976931348623157e308", &arena).unwrap().as_f64(), Some(f64::MAX));
+
+    // inf/nan viết bằng chữ: không bao giờ là số, kể cả với hậu tố f
+    let suffixes = ParseOptions { allow_number_suffixes: true, overflow_to_float: true, ..ParseOptions::default() };
+    assert_eq!(parse_err("Ninff", suffixes), r#"InvalidFloat { detail: "not a finite number", pos: 1 }"#);
+    assert_eq!(parse_err("NNaNf", suffixes), r#"InvalidFloat { detail: "not a finite number", pos: 1 }"#);
+    assert_eq!(parse_err("Ninf", options), "InvalidInteger { pos: 1 }");
+}
+
+#[test]
+fn overflow_to_float_allows_infinity() {
+    let arena = Bump::new();
+    let options = ParseOptions { overflow_to_float: true, ..ParseOptions::default() };
+    let value = parse_fdon_zero_copy_arena_with_options("A[N1e999,N-1e999,N1e-999]", &arena, options).unwrap();
+    let floats: Vec<f64> = value.as_array().unwrap().iter().filter_map(|v| v.as_f64()).collect();
+    assert_eq!(floats, [f64::INFINITY, f64::NEG_INFINITY, 0.0]);
+    // Serializer ghi số không hữu hạn thành U
+    assert_eq!(to_fdon_string(&value), "A[U,U,N0.0]");
+    let mut v = Validator::with_options(options);
+    assert!(v.feed(b"A[N1e999]").and_then(|_| v.finish()).is_ok());
+}
+
+// --- Tràn số nguyên ---
+
+#[test]
+fn integers_at_and_past_the_i64_boundary() {
+    use FdonNumber::{Float, Integer, UInt};
+    let arena = Bump::new();
+    let number = |input: &str, options: ParseOptions| {
+        match parse_fdon_zero_copy_arena_with_options(input, &arena, options) {
+            Ok(fdon_rs::FdonValue::Number(n)) => n,
+            other => panic!("{}: {:?}", input, other),
+        }
+    };
+    let options = ParseOptions::default();
+    assert!(same(number("N9223372036854775807", options), Integer(i64::MAX)));
+    assert!(same(number("N-9223372036854775808", options), Integer(i64::MIN)));
+    // i64::MAX + 1 vẫn chính xác dưới dạng UInt
+    assert!(same(number("N9223372036854775808", options), UInt(i64::MAX as u64 + 1)));
+    assert!(same(number("N18446744073709551615", options), UInt(u64::MAX)));
+
+    // Ngoài cả u64 (hoặc nhỏ hơn i64::MIN): lỗi tràn, khác với lỗi định dạng
+    assert_eq!(parse_err("N18446744073709551616", options), "IntegerOverflow { pos: 1 }");
+    assert_eq!(parse_err("A[N-9223372036854775809]", options), "IntegerOverflow { pos: 3 }");
+    assert_eq!(parse_err("N12abc", options), "InvalidInteger { pos: 3 }");
+
+    let lossy = ParseOptions { overflow_to_float: true, ..ParseOptions::default() };
+    assert!(same(number("N18446744073709551616", lossy), Float(18446744073709551616.0)));
+    assert!(same(number("N-9223372036854775809", lossy), Float(-9223372036854775809.0)));
+    assert_eq!(parse_err("N12abc", lossy), "InvalidInteger { pos: 3 }");
+
+    // Hậu tố u: không bao giờ rơi về float
+    let suffixes = ParseOptions { allow_number_suffixes: true, ..lossy };
+    assert_eq!(parse_err("N18446744073709551616u", suffixes), "IntegerOverflow { pos: 1 }");
+}
+
+// --- allow_digit_separators ---
+
+#[test]
+fn digit_separators_between_digits_only() {
+    use FdonNumber::{Float, Integer};
+    let arena = Bump::new();
+    let options = ParseOptions { allow_digit_separators: true, ..ParseOptions::default() };
+    let value = parse_fdon_zero_copy_arena_with_options("A[N1_000_000,N1.250_5,N-1_0e1_0,T1_700]", &arena, options);
+    let numbers: Vec<FdonNumber> = value
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| match v {
+            fdon_rs::FdonValue::Number(n) | fdon_rs::FdonValue::Timestamp(n) => *n,
+            other => panic!("{:?}", other),
+        })
+        .collect();
+    let expected = [Integer(1_000_000), Float(1.2505), Float(-10e10), Integer(1700)];
+    assert!(numbers.iter().zip(expected).all(|(&a, b)| same(a, b)), "{:?}", numbers);
+
+    let cases = [
+        ("N_1", 1),
+        ("N1_", 2),
+        ("N1__0", 2),
+        ("N1_.5", 2),
+        ("N1._5", 3),
+        ("N1_e5", 2),
+        ("N1e_5", 3),
+        ("N-_1", 2),
+        ("A[N1,N2_]", 7),
+    ];
+    for (input, pos) in cases {
+        assert_eq!(parse_err(input, options), format!("InvalidDigitSeparator {{ pos: {} }}", pos), "{}", input);
+    }
+    // Tắt option (mặc định): '_' là byte lạ
+    assert_eq!(parse_err("N1_000", ParseOptions::default()), "InvalidInteger { pos: 2 }");
+}
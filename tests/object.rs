@@ -0,0 +1,106 @@
+use bumpalo::Bump;
+use fdon_rs::{
+    object_from_iter, parse_fdon_zero_copy_arena, to_fdon_string, FdonNumber, FdonObject, FdonObjectBuilder, FdonValue,
+};
+
+fn int(i: i64) -> FdonValue<'static, 'static> {
+    FdonValue::Number(FdonNumber::Integer(i))
+}
+
+fn keys<'bump>(value: &FdonValue<'_, 'bump>) -> Vec<&'bump str> {
+    value.as_object().unwrap().keys().collect()
+}
+
+// --- Thứ tự chèn ---
+
+#[test]
+fn built_objects_keep_insertion_order() {
+    let arena = Bump::new();
+    // Key không cùng một buffer: trước đây thứ tự phụ thuộc địa chỉ
+    let owned: Vec<String> = ["zeta", "alpha", "mid", "beta"].iter().map(|s| s.to_string()).collect();
+
+    let mut builder = FdonObjectBuilder::new(&arena);
+    for (i, k) in owned.iter().enumerate() {
+        builder.insert(k, FdonValue::Number(FdonNumber::Integer(i as i64)));
+    }
+    let built = builder.build();
+    assert_eq!(to_fdon_string(&built), "O{zeta:N0,alpha:N1,mid:N2,beta:N3}");
+
+    let from_iter = object_from_iter(&arena, owned.iter().rev().map(|k| (k.as_str(), FdonValue::Null)));
+    assert_eq!(keys(&from_iter), ["beta", "mid", "alpha", "zeta"]);
+
+    let json = serde_json::to_string(&built.to_json_ordered()).unwrap();
+    if cfg!(feature = "preserve_order") {
+        assert_eq!(json, r#"{"zeta":0,"alpha":1,"mid":2,"beta":3}"#);
+    } else {
+        assert_eq!(json, r#"{"alpha":1,"beta":3,"mid":2,"zeta":0}"#);
+    }
+    assert_eq!(serde_json::to_string(&built).unwrap(), r#"{"zeta":0,"alpha":1,"mid":2,"beta":3}"#);
+}
+
+#[test]
+fn repeated_key_keeps_its_first_position() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena("O{a:N1,b:N2,a:N3}", &arena).unwrap();
+    assert_eq!(to_fdon_string(&value), "O{a:N3,b:N2}");
+}
+
+#[test]
+fn merge_and_rename_order_is_deterministic() {
+    let arena = Bump::new();
+    let base = parse_fdon_zero_copy_arena("O{c:N1,a:O{y:N1,x:N2},b:N3}", &arena).unwrap();
+    let overlay = parse_fdon_zero_copy_arena("O{d:N4,a:O{z:N5,x:N6},c:N7}", &arena).unwrap();
+    let merged = base.merge(&overlay, &arena);
+    // Key của base trước, key mới của overlay nối sau
+    assert_eq!(to_fdon_string(&merged), "O{c:N7,a:O{y:N1,x:N6,z:N5},b:N3,d:N4}");
+
+    let mut value = parse_fdon_zero_copy_arena("O{a:N1,b:N2,c:N3}", &arena).unwrap();
+    let renamed = arena.alloc_str("renamed");
+    assert!(value.rename_key("b", renamed));
+    assert_eq!(to_fdon_string(&value), "O{a:N1,renamed:N2,c:N3}");
+    // Đổi sang một key đã có: entry cũ bị thay, entry được đổi tên giữ vị trí
+    assert!(value.rename_key("c", "a"));
+    assert_eq!(to_fdon_string(&value), "O{renamed:N2,a:N3}");
+    assert!(!value.rename_key("missing", "x"));
+}
+
+#[test]
+fn paths_and_get_ci_follow_object_order() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena("O{b:N1,NAME:N2,a:N3,Name:N4}", &arena).unwrap();
+    let paths: Vec<String> = value.paths().into_iter().map(|(p, _)| p).collect();
+    assert_eq!(paths, ["", "/b", "/NAME", "/a", "/Name"]);
+    assert_eq!(value.get_ci("name").and_then(|v| v.as_i64()), Some(2));
+}
+
+// --- Object lớn (có hash index) ---
+
+#[test]
+fn large_object_lookup_remove_and_retain() {
+    let arena = Bump::new();
+    let names: Vec<String> = (0..100).map(|i| format!("k{}", i)).collect();
+    let mut obj = FdonObject::new_in(&arena);
+    for (i, k) in names.iter().enumerate() {
+        assert!(obj.insert(k, int(i as i64)).is_none());
+    }
+    assert_eq!(obj.len(), 100);
+    assert!(obj.insert("k42", int(-42)).is_some());
+    assert_eq!(obj.get("k42").and_then(|v| v.as_i64()), Some(-42));
+    assert_eq!(obj.keys().nth(42), Some("k42"));
+
+    assert_eq!(obj.remove("k0").and_then(|v| v.as_i64()), Some(0));
+    assert!(obj.get("k0").is_none());
+    assert_eq!(obj.get("k99").and_then(|v| v.as_i64()), Some(99));
+    assert_eq!(obj.keys().next(), Some("k1"));
+
+    obj.retain(|k, _| k.ends_with('7'));
+    assert_eq!(obj.keys().collect::<Vec<_>>(), ["k7", "k17", "k27", "k37", "k47", "k57", "k67", "k77", "k87", "k97"]);
+    assert_eq!(obj.get("k57").and_then(|v| v.as_i64()), Some(57));
+    assert!(obj.get("k50").is_none());
+
+    let mut sized = FdonObject::with_capacity_in(50, &arena);
+    sized.extend(names.iter().take(50).map(|k| (k.as_str(), FdonValue::Null)));
+    assert_eq!(sized.len(), 50);
+    assert!(names.iter().take(50).all(|k| sized.contains_key(k)));
+    assert!(!sized.contains_key("k50"));
+}
@@ -0,0 +1,401 @@
+use bumpalo::Bump;
+use fdon_rs::{parse_fdon_zero_copy_arena, validate, FdonError, FdonValue};
+
+// --- SE vs S"E..." ---
+
+#[test]
+fn se_without_delimiter_points_after_the_tag() {
+    let arena = Bump::new();
+    for (input, pos) in [("SEfoo", 2), ("SE", 2), ("A[SEx]", 4)] {
+        let err = parse_fdon_zero_copy_arena(input, &arena).unwrap_err();
+        assert!(
+            matches!(err, FdonError::ExpectedDelimiterAfterSE { delimiter: b'"', pos: p } if p == pos),
+            "{}: {:?}",
+            input,
+            err
+        );
+        assert_eq!(err.to_string(), "Expected '\"' after SE");
+        // Validator báo cùng lỗi, cùng vị trí
+        assert_eq!(format!("{:?}", validate(input).unwrap_err()), format!("{:?}", err));
+    }
+}
+
+#[test]
+fn raw_string_starting_with_e_is_not_se() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(r#"S"E..""#, &arena).unwrap();
+    assert!(matches!(value, FdonValue::RawString("E..")));
+    assert!(validate(r#"S"E..""#).is_ok());
+
+    let value = parse_fdon_zero_copy_arena(r#"O{E:SE"x",SE:N1}"#, &arena).unwrap();
+    let obj = value.as_object().unwrap();
+    assert_eq!(obj.get("E").and_then(|v| v.as_str()), Some("x"));
+    assert!(obj.contains_key("SE"));
+}
+
+// --- Tuple lỗi cũ (FdonParseError) ---
+
+#[allow(deprecated)]
+fn old_style(input: &str) -> Result<usize, fdon_rs::FdonParseError> {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(input, &arena)?;
+    Ok(value.as_array().map_or(0, |a| a.len()))
+}
+
+#[test]
+fn error_converts_into_the_old_tuple() {
+    assert_eq!(old_style("A[N1,N2]"), Ok(2));
+    let (msg, pos) = old_style("A[N1,]").unwrap_err();
+    assert_eq!(msg, "Trailing comma detected in array");
+    assert_eq!(pos, 5);
+
+    let arena = Bump::new();
+    let err = parse_fdon_zero_copy_arena("X", &arena).unwrap_err();
+    let (msg, pos): (String, usize) = err.into();
+    assert_eq!((msg.as_str(), pos), ("Unknown data type specifier 'X'", 0));
+}
+
+// --- Validator và các option nó bỏ qua ---
+
+#[test]
+fn validator_ignores_whitespace_and_duplicate_key_options() {
+    let arena = Bump::new();
+    let options = fdon_rs::ParseOptions { allow_whitespace: true, reject_duplicate_keys: true, ..Default::default() };
+    let feed_all = |input: &str| {
+        let mut v = fdon_rs::Validator::with_options(options);
+        v.feed(input.as_bytes()).and_then(|_| v.finish())
+    };
+
+    assert!(fdon_rs::parse_fdon_zero_copy_arena_with_options("A[ N1 ]", &arena, options).is_ok());
+    assert!(matches!(feed_all("A[ N1 ]"), Err(FdonError::UnknownTypeSpecifier { byte: b' ', pos: 2 })));
+
+    let err = fdon_rs::parse_fdon_zero_copy_arena_with_options("O{a:N1,a:N2}", &arena, options).unwrap_err();
+    assert!(matches!(err, FdonError::DuplicateKey { pos: 7, .. }));
+    assert!(feed_all("O{a:N1,a:N2}").is_ok());
+}
+
+// --- skip_value ---
+
+#[test]
+fn skip_value_checks_structure_only() {
+    let arena = Bump::new();
+    let input = r#"O{a:N1x,b:D"nope",c:A[O{d:U,d:U}]},N2"#;
+    let options = fdon_rs::ParseOptions { strict_date_time: true, reject_duplicate_keys: true, ..Default::default() };
+    let mut parser = fdon_rs::FdonParser::with_options(input, &arena, options);
+    parser.skip_value().unwrap();
+    assert_eq!(parser.position(), input.len() - 3);
+
+    let mut parser = fdon_rs::FdonParser::new("A[N1,]", &arena);
+    assert!(matches!(parser.skip_value(), Err(FdonError::TrailingComma { pos: 5, .. })));
+}
+
+#[test]
+fn skip_value_does_not_recurse() {
+    let arena = Bump::new();
+    let depth = 200_000;
+    let input = format!("{}{}{}", "A[".repeat(depth), "O{k:N1}", "]".repeat(depth));
+    let options = fdon_rs::ParseOptions { max_depth: usize::MAX, ..Default::default() };
+    let mut parser = fdon_rs::FdonParser::with_options(&input, &arena, options);
+    parser.skip_value().unwrap();
+    assert_eq!(parser.position(), input.len());
+
+    let mut parser = fdon_rs::FdonParser::new(&input, &arena);
+    assert!(matches!(parser.skip_value(), Err(FdonError::DepthLimitExceeded { pos: 256 })));
+}
+
+// --- forbid_empty_keys ---
+
+#[test]
+fn empty_key_depends_on_forbid_empty_keys() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena("O{:N1}", &arena).unwrap();
+    assert!(value.as_object().unwrap().contains_key(""));
+
+    let options = fdon_rs::ParseOptions { forbid_empty_keys: true, ..Default::default() };
+    for (input, pos) in [("O{:N1}", 2), ("O{a:N1,:N2}", 7)] {
+        let err = fdon_rs::parse_fdon_zero_copy_arena_with_options(input, &arena, options).unwrap_err();
+        assert!(matches!(err, FdonError::EmptyKey { pos: p } if p == pos), "{}: {:?}", input, err);
+        let mut v = fdon_rs::Validator::with_options(options);
+        let streamed = v.feed(input.as_bytes()).and_then(|_| v.finish()).unwrap_err();
+        assert_eq!(format!("{:?}", streamed), format!("{:?}", err));
+    }
+}
+
+// --- allow_json_keywords ---
+
+fn parse_with_keywords<'a>(input: &'a str, arena: &'a Bump) -> Result<FdonValue<'a, 'a>, FdonError> {
+    let options = fdon_rs::ParseOptions { allow_json_keywords: true, ..Default::default() };
+    fdon_rs::parse_fdon_zero_copy_arena_with_options(input, arena, options)
+}
+
+#[test]
+fn json_keywords_mix_with_fdon_tags() {
+    let arena = Bump::new();
+    let parse = |input: &'static str| parse_with_keywords(input, &arena);
+
+    let mixed = parse("A[null,true,false,U,Btrue,Bfalse,N1]").unwrap();
+    let tags = parse_fdon_zero_copy_arena("A[U,Btrue,Bfalse,U,Btrue,Bfalse,N1]", &arena).unwrap();
+    assert!(mixed.eq_strict(&tags));
+
+    // Key không bị ảnh hưởng: `null`/`true` ở vị trí key vẫn là tên key
+    let obj = parse("O{null:true,true:null}").unwrap();
+    assert_eq!(obj.as_object().unwrap().get("null").and_then(|v| v.as_bool()), Some(true));
+    assert!(obj.as_object().unwrap().get("true").is_some_and(|v| v.is_null()));
+
+    // Chỉ đúng từ khóa viết thường; phần còn lại vẫn là lỗi
+    assert!(matches!(parse("A[nul]"), Err(FdonError::UnknownTypeSpecifier { byte: b'n', pos: 2 })));
+    assert!(matches!(parse("A[False]"), Err(FdonError::UnknownTypeSpecifier { byte: b'F', pos: 2 })));
+    assert!(matches!(parse("A[truex]"), Err(FdonError::MissingComma { pos: 6, .. })));
+
+    // Tắt option (mặc định): từ khóa JSON là type tag lạ
+    assert!(matches!(
+        parse_fdon_zero_copy_arena("A[null]", &arena),
+        Err(FdonError::UnknownTypeSpecifier { byte: b'n', pos: 2 })
+    ));
+}
+
+// --- strict_escapes ---
+
+#[test]
+fn unknown_escape_depends_on_strict_escapes() {
+    let arena = Bump::new();
+    let input = r#"A[SE"a\qb"]"#;
+    let value = parse_fdon_zero_copy_arena(input, &arena).unwrap();
+    assert_eq!(value.as_array().unwrap()[0].as_str(), Some("aqb"));
+    assert!(validate(input).is_ok());
+
+    let options = fdon_rs::ParseOptions { strict_escapes: true, ..Default::default() };
+    let err = fdon_rs::parse_fdon_zero_copy_arena_with_options(input, &arena, options).unwrap_err();
+    assert!(matches!(err, FdonError::InvalidEscape { ch: b'q', pos: 6 }), "{:?}", err);
+    assert_eq!(err.to_string(), "Unknown escape sequence '\\q'");
+    let mut v = fdon_rs::Validator::with_options(options);
+    let streamed = v.feed(input.as_bytes()).and_then(|_| v.finish()).unwrap_err();
+    assert_eq!(format!("{:?}", streamed), format!("{:?}", err));
+
+    // Các escape đã biết vẫn hợp lệ ở chế độ strict
+    let known = fdon_rs::parse_fdon_zero_copy_arena_with_options(r#"SE"\n\t\r\"\\A""#, &arena, options).unwrap();
+    assert_eq!(known.as_str(), Some("\n\t\r\"\\A"));
+}
+
+// --- forbid_literal_newlines_in_strings ---
+
+#[test]
+fn literal_newline_depends_on_forbid_literal_newlines() {
+    let arena = Bump::new();
+    let cases = [
+        ("A[S\"a\nb\"]", 5),
+        ("A[SE\"a\nb\"]", 6),
+        ("O{k:SE\"x\\n\r\ny\"}", 11),
+        ("A[D\"2024\n\",T\"x\"]", 8),
+        ("T\"\n\"", 2),
+    ];
+    let options = fdon_rs::ParseOptions { forbid_literal_newlines_in_strings: true, ..Default::default() };
+    for (input, pos) in cases {
+        // Mặc định: '\n' trần được chép nguyên vào chuỗi
+        let value = parse_fdon_zero_copy_arena(input, &arena).unwrap();
+        assert!(value.as_str().or_else(|| value[0].as_str()).or_else(|| value["k"].as_str()).unwrap().contains('\n'));
+        assert!(validate(input).is_ok());
+
+        let err = fdon_rs::parse_fdon_zero_copy_arena_with_options(input, &arena, options).unwrap_err();
+        assert!(matches!(err, FdonError::LiteralNewline { pos: p } if p == pos), "{:?} {}", err, input.escape_debug());
+        let mut v = fdon_rs::Validator::with_options(options);
+        let streamed = v.feed(input.as_bytes()).and_then(|_| v.finish()).unwrap_err();
+        assert_eq!(format!("{:?}", streamed), format!("{:?}", err));
+        let mut parser = fdon_rs::FdonParser::with_options(input, &arena, options);
+        assert_eq!(format!("{:?}", parser.skip_value().unwrap_err()), format!("{:?}", err));
+    }
+
+    // Escape \n vẫn hợp lệ khi bật option
+    let value = fdon_rs::parse_fdon_zero_copy_arena_with_options(r#"SE"a\nb""#, &arena, options).unwrap();
+    assert_eq!(value.as_str(), Some("a\nb"));
+}
+
+// --- max_depth ---
+
+#[test]
+fn ten_thousand_nested_arrays_are_an_error_not_a_crash() {
+    let arena = Bump::new();
+    let input = format!("{}{}", "A[".repeat(10_000), "]".repeat(10_000));
+    let err = parse_fdon_zero_copy_arena(&input, &arena).unwrap_err();
+    // Container thứ 129 bắt đầu tại 2 * 128
+    assert!(matches!(err, FdonError::DepthLimitExceeded { pos: 256 }), "{:?}", err);
+    assert_eq!(format!("{:?}", validate(&input).unwrap_err()), format!("{:?}", err));
+}
+
+#[test]
+fn max_depth_counts_the_root_as_one() {
+    let arena = Bump::new();
+    let nest = |depth: usize| format!("{}N1{}", "O{k:A[".repeat(depth / 2), "]}".repeat(depth / 2));
+    assert!(parse_fdon_zero_copy_arena(&nest(fdon_rs::DEFAULT_MAX_DEPTH), &arena).is_ok());
+    let err = parse_fdon_zero_copy_arena(&nest(fdon_rs::DEFAULT_MAX_DEPTH + 2), &arena).unwrap_err();
+    // "O{k:A[" dài 6 byte; container thứ 129 là một O
+    assert!(matches!(err, FdonError::DepthLimitExceeded { pos: 384 }), "{:?}", err);
+
+    let mut parser = fdon_rs::FdonParser::with_max_depth("A[A[A[]]]", &arena, 3);
+    assert!(parser.parse().is_ok());
+    let mut parser = fdon_rs::FdonParser::with_max_depth("A[A[A[]]]", &arena, 2);
+    assert!(matches!(parser.parse(), Err(FdonError::DepthLimitExceeded { pos: 4 })));
+    let mut parser = fdon_rs::FdonParser::with_max_depth("N1", &arena, 0);
+    assert!(parser.parse().is_ok());
+    let mut parser = fdon_rs::FdonParser::with_max_depth("A[]", &arena, 0);
+    assert!(matches!(parser.parse(), Err(FdonError::DepthLimitExceeded { pos: 0 })));
+}
+
+// --- is_valid ---
+
+#[test]
+fn is_valid_agrees_with_validate() {
+    for input in ["O{a:N1}", "A[]", r#"SE"\n""#, "N1e5", "A[N1,]", "O{a:N1}N2", "", "X", r#"S"open"#, "O{:N1}"] {
+        assert_eq!(fdon_rs::is_valid(input), validate(input).is_ok(), "{}", input);
+    }
+    assert!(fdon_rs::is_valid("O{a:N1}"));
+    assert!(!fdon_rs::is_valid("A[N1,]"));
+}
+
+// --- Parser không đệ quy ---
+
+#[test]
+fn a_million_nested_arrays_parse_without_a_depth_cap() {
+    let arena = Bump::new();
+    let depth = 1_000_000;
+    let input = format!("{}{}", "A[".repeat(depth), "]".repeat(depth));
+    let options = fdon_rs::ParseOptions { max_depth: usize::MAX, ..Default::default() };
+    let value = fdon_rs::parse_fdon_zero_copy_arena_with_options(&input, &arena, options).unwrap();
+    let mut levels = 1;
+    let mut node = &value;
+    while let Some(inner) = node.get_index(0) {
+        node = inner;
+        levels += 1;
+    }
+    assert_eq!(levels, depth);
+    assert!(node.as_array().unwrap().is_empty());
+    // Drop đệ quy theo độ sâu: bỏ qua
+    std::mem::forget(value);
+}
+
+#[test]
+fn deep_errors_match_the_validator() {
+    let arena = Bump::new();
+    let depth = 100_000;
+    let options = fdon_rs::ParseOptions { max_depth: usize::MAX, ..Default::default() };
+    let cases = [
+        (format!("{}N1,{}", "A[O{k:".repeat(depth), "}]".repeat(depth)), "TrailingComma { container: Object, pos: 600003 }"),
+        (format!("{}N1{}", "A[".repeat(depth), "]".repeat(depth - 1)), "MissingComma { container: Array, pos: 300001 }"),
+        (format!("{}N1}}]A[", "A[O{k:".repeat(depth)), "MissingComma { container: Object, pos: 600004 }"),
+    ];
+    for (input, expected) in &cases {
+        let err = fdon_rs::parse_fdon_zero_copy_arena_with_options(input, &arena, options).unwrap_err();
+        assert_eq!(format!("{:?}", err), *expected);
+        let mut v = fdon_rs::Validator::with_options(options);
+        let streamed = v.feed(input.as_bytes()).and_then(|_| v.finish()).unwrap_err();
+        assert_eq!(format!("{:?}", streamed), format!("{:?}", err));
+    }
+}
+
+// --- strict_date_time ---
+
+#[test]
+fn strict_date_time_checks_layout_and_ranges() {
+    let arena = Bump::new();
+    let options = fdon_rs::ParseOptions { strict_date_time: true, ..Default::default() };
+    let ok = r#"A[D"2024-02-29",D"2000-02-29",D"0001-01-01",T"00:00:00",T"23:59:59.999999999",T1700000000]"#;
+    assert!(fdon_rs::parse_fdon_zero_copy_arena_with_options(ok, &arena, options).is_ok());
+
+    let bad = [
+        (r#"A[D"2024-13-40"]"#, "InvalidDate { pos: 2 }"),
+        (r#"A[D"2023-02-29"]"#, "InvalidDate { pos: 2 }"),
+        (r#"A[D"1900-02-29"]"#, "InvalidDate { pos: 2 }"),
+        (r#"O{d:D"2024-1-1"}"#, "InvalidDate { pos: 4 }"),
+        (r#"D"2024-01-01T00:00""#, "InvalidDate { pos: 0 }"),
+        (r#"D"""#, "InvalidDate { pos: 0 }"),
+        (r#"A[N1,T"24:00:00"]"#, "InvalidTime { pos: 5 }"),
+        (r#"T"12:00""#, "InvalidTime { pos: 0 }"),
+        (r#"T"12:00:00.""#, "InvalidTime { pos: 0 }"),
+        (r#"T"12:00:60""#, "InvalidTime { pos: 0 }"),
+    ];
+    for (input, expected) in bad {
+        // Mặc định: chuỗi bất kỳ đều được nhận
+        assert!(parse_fdon_zero_copy_arena(input, &arena).is_ok(), "{}", input);
+        let err = fdon_rs::parse_fdon_zero_copy_arena_with_options(input, &arena, options).unwrap_err();
+        assert_eq!(format!("{:?}", err), expected, "{}", input);
+        let mut v = fdon_rs::Validator::with_options(options);
+        let streamed = v.feed(input.as_bytes()).and_then(|_| v.finish()).unwrap_err();
+        assert_eq!(format!("{:?}", streamed), expected, "{}", input);
+    }
+}
+
+// --- reject_duplicate_keys ---
+
+#[test]
+fn duplicate_keys_error_or_last_write_wins() {
+    let arena = Bump::new();
+    // Mặc định: value sau ghi đè, key giữ vị trí đầu
+    let value = parse_fdon_zero_copy_arena("O{a:N1,b:N2,a:N3}", &arena).unwrap();
+    assert_eq!(value["a"].as_i64(), Some(3));
+    assert_eq!(value.as_object().unwrap().keys().collect::<Vec<_>>(), ["a", "b"]);
+
+    let options = fdon_rs::ParseOptions { reject_duplicate_keys: true, ..Default::default() };
+    let parse = |input| fdon_rs::parse_fdon_zero_copy_arena_with_options(input, &arena, options);
+    let err = parse("O{a:N1,a:N2}").unwrap_err();
+    assert!(matches!(err, FdonError::DuplicateKey { ref key, pos: 7 } if key == "a"), "{:?}", err);
+    // Lồng trong array, và key dạng quoted
+    let err = parse(r#"A[O{x:U},O{"k:1":U,y:U,"k:1":N1}]"#).unwrap_err();
+    assert!(matches!(err, FdonError::DuplicateKey { ref key, pos: 23 } if key == "k:1"), "{:?}", err);
+    // Cùng key ở các object khác nhau thì không sao
+    assert!(parse("O{a:O{a:N1},b:A[O{a:U},O{a:U}]}").is_ok());
+}
+
+// --- Key SE"..." ---
+
+#[test]
+fn escaped_keys_unescape_like_se_strings() {
+    let arena = Bump::new();
+    let input = r#"O{SE"a\"b:c":N1,SE"tab\there":N2,SE" sp ":N3,SE"\u{1F600}\\":N4,SE"plain":N5,SE:N6,SEx:N7}"#;
+    let value = parse_fdon_zero_copy_arena(input, &arena).unwrap();
+    let obj = value.as_object().unwrap();
+    assert_eq!(
+        obj.keys().collect::<Vec<_>>(),
+        ["a\"b:c", "tab\there", " sp ", "\u{1F600}\\", "plain", "SE", "SEx"]
+    );
+    assert_eq!(value["a\"b:c"].as_i64(), Some(1));
+    // Không có escape: key mượn thẳng từ input
+    let plain = obj.keys().nth(4).unwrap();
+    assert!(input.as_bytes().as_ptr_range().contains(&plain.as_ptr()));
+    assert!(validate(input).is_ok());
+
+    // Cùng key viết hai cách là một key
+    let value = parse_fdon_zero_copy_arena(r#"O{ab:N1,SE"a\u{62}":N2}"#, &arena).unwrap();
+    assert_eq!(value.as_object().unwrap().len(), 1);
+    assert_eq!(value["ab"].as_i64(), Some(2));
+
+    let lenient = fdon_rs::parse_fdon_lenient("O{ SE\"a b\\n\" : N1 }", &arena).unwrap();
+    assert_eq!(lenient["a b\n"].as_i64(), Some(1));
+}
+
+#[test]
+fn escaped_key_errors_match_the_validator() {
+    let arena = Bump::new();
+    let strict = fdon_rs::ParseOptions { strict_escapes: true, forbid_empty_keys: true, ..Default::default() };
+    let cases = [
+        (r#"O{SE"ab"#, "UnterminatedString { escaped: true, pos: 5 }"),
+        (r#"O{SE"a\"#, "EofAfterEscape { pos: 7 }"),
+        (r#"O{SE"\u{zz}":U}"#, "InvalidUnicodeEscape { pos: 5 }"),
+        (r#"O{SE"a"N1}"#, "Expected { expected: 58, found: Some(78), pos: 7 }"),
+        (r#"O{a:U,SE"a\q":U}"#, "InvalidEscape { ch: 113, pos: 10 }"),
+        (r#"O{SE"":U}"#, "EmptyKey { pos: 2 }"),
+        ("O{SE", "UnterminatedKey { pos: 2 }"),
+    ];
+    for (input, expected) in cases {
+        let err = fdon_rs::parse_fdon_zero_copy_arena_with_options(input, &arena, strict).unwrap_err();
+        assert_eq!(format!("{:?}", err), expected, "{}", input);
+        let mut parser = fdon_rs::FdonParser::with_options(input, &arena, strict);
+        assert_eq!(format!("{:?}", parser.skip_value().unwrap_err()), expected, "skip {}", input);
+        // Validator: cả khi key bị cắt ngang giữa các chunk
+        for split in 0..input.len() {
+            let mut v = fdon_rs::Validator::with_options(strict);
+            let (a, b) = input.as_bytes().split_at(split);
+            let streamed = v.feed(a).and_then(|_| v.feed(b)).and_then(|_| v.finish()).unwrap_err();
+            assert_eq!(format!("{:?}", streamed), expected, "{} split at {}", input, split);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+
+use bumpalo::Bump;
+use fdon_rs::{parse_fdon_zero_copy_arena, validate_reader, CountingReader, FdonError, FdonReader, Validator};
+
+// Reader trả về tối đa `max` byte mỗi lần đọc, để token bị cắt ngang nhiều buffer
+struct Trickle<'a> {
+    data: &'a [u8],
+    max: usize,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.data.len().min(self.max).min(buf.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+// Mảng dài ~200KB (nhiều lần READ_CHUNK) với một lỗi ở gần cuối
+fn long_document_with_error() -> (Vec<u8>, usize) {
+    let mut doc = b"A[".to_vec();
+    for i in 0..20_000 {
+        doc.extend_from_slice(format!("SE\"item {}\\n\",", i).as_bytes());
+    }
+    let bad = doc.len() + 5;
+    doc.extend_from_slice(b"N1,SEx]");
+    (doc, bad)
+}
+
+#[test]
+fn error_deep_in_a_multi_buffer_stream_has_its_absolute_offset() {
+    let (doc, bad) = long_document_with_error();
+    let err = validate_reader(&doc[..]).unwrap_err();
+    assert!(matches!(err, FdonError::ExpectedDelimiterAfterSE { pos, .. } if pos == bad), "{:?}", err);
+
+    let err = validate_reader(Trickle { data: &doc, max: 7 }).unwrap_err();
+    assert_eq!(err.pos(), Some(bad));
+}
+
+#[test]
+fn counting_reader_maps_local_offsets_after_a_header() {
+    let (doc, bad) = long_document_with_error();
+    let header = b"fdon-dump v1\n";
+    let file: Vec<u8> = header.iter().chain(&doc).copied().collect();
+
+    let mut reader = CountingReader::new(BufReader::with_capacity(1000, Trickle { data: &file, max: 333 }));
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(reader.offset(), header.len() as u64);
+
+    // Validator chỉ thấy phần sau header: cộng offset lúc bắt đầu để ra vị trí trong file
+    let start = reader.offset() as usize;
+    let mut validator = Validator::new();
+    let mut buf = [0u8; 4096];
+    let err = loop {
+        let n = reader.read(&mut buf).unwrap();
+        assert!(n > 0, "document ended without an error");
+        if let Err(e) = validator.feed(&buf[..n]) {
+            break e;
+        }
+    };
+    assert_eq!(err.pos().map(|p| p + start), Some(header.len() + bad));
+    assert_eq!(&file[header.len() + bad - 2..header.len() + bad], b"SE");
+    // Không đọc quá buffer chứa lỗi
+    assert!(reader.offset() as usize - (header.len() + bad) < buf.len() + 1000);
+}
+
+#[test]
+fn malformed_start_of_an_endless_stream_fails_without_reading_it_all() {
+    let mut reader = CountingReader::new((&b"A[Q"[..]).chain(io::repeat(b'U')));
+    let err = validate_reader(&mut reader).unwrap_err();
+    assert!(matches!(err, FdonError::UnknownTypeSpecifier { byte: b'Q', pos: 2 }));
+    assert!(reader.offset() <= 64 * 1024);
+}
+
+#[test]
+fn fdon_reader_reports_absolute_offsets_across_values() {
+    let input = b"O{a:N1}\nA[N2]\nA[N3,]\nU\n";
+    let mut reader = FdonReader::with_chunk_size(Trickle { data: input, max: 3 }, Default::default(), 2);
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_ok());
+    let err = reader.next().unwrap().unwrap_err();
+    assert!(matches!(err, FdonError::TrailingComma { pos: 19, .. }), "{:?}", err);
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn fdon_reader_in_7_byte_chunks_matches_the_in_memory_parser() {
+    let docs = [
+        r#"O{name:SE"An \"x\"\n\u{1F600}",tags:A[S"a, b",D"2024-01-02",T"10:00:00"],n:O{big:N18446744073709551615}}"#,
+        "A[N-1.5e3,T1700000000,Btrue,Bfalse,U,A[],O{}]",
+        r#"SE"split\\across\tchunks""#,
+        "N42",
+    ];
+    let input = docs.join("\n");
+    let arena = Bump::new();
+    let mut count = 0;
+    for (read, doc) in FdonReader::with_chunk_size(Cursor::new(input.as_bytes()), Default::default(), 7).zip(docs) {
+        let expected = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+        assert!(read.unwrap() == expected, "{}", doc);
+        count += 1;
+    }
+    assert_eq!(count, docs.len());
+
+    // Cursor đọc hết một lần (chunk mặc định) cũng cho đúng các value đó
+    let all: Vec<_> = FdonReader::new(Cursor::new(input.as_bytes())).collect::<Result<_, _>>().unwrap();
+    assert_eq!(all.len(), docs.len());
+}
@@ -0,0 +1,95 @@
+use bumpalo::Bump;
+use fdon_rs::{parse_fdon_zero_copy_arena, render_error, FdonError};
+
+fn render(input: &str) -> String {
+    let arena = Bump::new();
+    let err = parse_fdon_zero_copy_arena(input, &arena).unwrap_err();
+    render_error(input, &err)
+}
+
+// Ký tự nằm ngay trên dấu ^ (đếm theo ký tự, không theo byte)
+fn char_at_caret(rendered: &str) -> char {
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", rendered);
+    let column = lines[2].chars().count() - 1;
+    assert!(lines[2].ends_with('^'));
+    lines[1].chars().nth(column).unwrap_or(' ')
+}
+
+// --- Input ngắn: in nguyên văn ---
+
+#[test]
+fn short_input_errors_at_start_middle_and_end() {
+    assert_eq!(render("Q"), "Unknown data type specifier 'Q' at position 0\nQ\n^");
+    assert_eq!(render("O{a:S\"x\"b:N2}"), "Missing comma or '}' in object at position 8\nO{a:S\"x\"b:N2}\n        ^");
+    assert_eq!(render("A[N1,]"), "Trailing comma detected in array at position 5\nA[N1,]\n     ^");
+    // Lỗi ở EOF: dấu ^ nằm ngay sau ký tự cuối
+    assert_eq!(render("A[N1"), "Missing comma or ']' in array at position 4\nA[N1\n    ^");
+}
+
+// --- Input dài: cắt thành cửa sổ quanh lỗi ---
+
+#[test]
+fn long_input_error_at_start() {
+    let input = format!("Q{}", "N".repeat(300));
+    let rendered = render(&input);
+    let snippet = rendered.lines().nth(1).unwrap();
+    assert!(!snippet.starts_with("...") && snippet.ends_with("..."), "{}", rendered);
+    assert_eq!(char_at_caret(&rendered), 'Q');
+}
+
+#[test]
+fn long_input_error_in_the_middle() {
+    let input = format!("A[{}Q,{}]", "N1,".repeat(60), "N2,".repeat(60));
+    let rendered = render(&input);
+    let snippet = rendered.lines().nth(1).unwrap();
+    assert!(snippet.starts_with("...") && snippet.ends_with("..."), "{}", rendered);
+    assert!(snippet.len() <= 100 + 6);
+    assert_eq!(char_at_caret(&rendered), 'Q');
+}
+
+#[test]
+fn long_input_error_at_the_end() {
+    let input = format!("A[{}N1]U", "N1,".repeat(100));
+    let rendered = render(&input);
+    assert!(rendered.starts_with(&format!("Extra data detected at end of file at position {}", input.len() - 1)));
+    let snippet = rendered.lines().nth(1).unwrap();
+    assert!(snippet.starts_with("...") && snippet.ends_with("]U"), "{}", rendered);
+    assert_eq!(char_at_caret(&rendered), 'U');
+
+    // EOF: ^ ngay sau ký tự cuối
+    let input = format!("A[{}N1", "N1,".repeat(100));
+    let rendered = render(&input);
+    assert_eq!(char_at_caret(&rendered), ' ');
+    assert_eq!(rendered.lines().nth(2).unwrap().len(), rendered.lines().nth(1).unwrap().chars().count() + 1);
+}
+
+#[test]
+fn window_never_splits_multibyte_characters() {
+    // Mỗi "ế" dài 3 byte: mọi vị trí cắt đều phải rơi vào ranh giới ký tự
+    for pad in 0..3 {
+        let input = format!("A[{}{}N1,]", "U,".repeat(pad), "S\"ế\",".repeat(40));
+        let arena = Bump::new();
+        let err = parse_fdon_zero_copy_arena(&input, &arena).unwrap_err();
+        let rendered = render_error(&input, &err);
+        assert!(rendered.lines().nth(1).unwrap().starts_with("..."));
+        assert_eq!(char_at_caret(&rendered), ']');
+    }
+}
+
+#[test]
+fn newlines_and_tabs_keep_the_caret_aligned() {
+    let input = "A[N1,\n\tQ]";
+    let arena = Bump::new();
+    let options = fdon_rs::ParseOptions { allow_whitespace: true, ..Default::default() };
+    let err = fdon_rs::parse_fdon_zero_copy_arena_with_options(input, &arena, options).unwrap_err();
+    let rendered = render_error(input, &err);
+    assert_eq!(rendered.lines().nth(1).unwrap(), "A[N1,  Q]");
+    assert_eq!(char_at_caret(&rendered), 'Q');
+}
+
+#[test]
+fn errors_without_a_position_render_their_message() {
+    let err = FdonError::Io(std::io::Error::other("disk on fire"));
+    assert_eq!(render_error("A[]", &err), "I/O error: disk on fire");
+}
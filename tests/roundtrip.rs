@@ -0,0 +1,161 @@
+use bumpalo::Bump;
+use fdon_rs::{
+    minify_fdon, parse_fdon_lenient, parse_fdon_zero_copy_arena, prettify_fdon, to_fdon_bytes, to_fdon_string,
+    to_fdon_writer, to_json_value, FdonArrayBuilder, FdonNumber, FdonObjectBuilder, FdonStr, FdonValue, PrettyOptions,
+};
+use serde_json::json;
+
+// Mọi loại value, lồng nhiều tầng: object chứa array chứa object...
+const MIXED: &str = r#"
+O{
+  users: A[
+    O{
+      id: N18446744073709551615,
+      neg: N-9223372036854775808,
+      name: SE"An \"Nguyễn\"\n\t\\ \u{1F600}",
+      raw: S"plain: text, with [brackets] {braces}",
+      born: D"1990-02-28",
+      wake: T"06:30:00.250",
+      seen: T1700000000,
+      score: N-0.125,
+      big: N1.5e300,
+      tiny: N2.5e-300,
+      active: Btrue,
+      banned: Bfalse,
+      note: U,
+      tags: A[S"", SE"", A[A[A[U, Btrue, N0]]], O{}],
+      history: A[
+        O{ at: T1.5, what: SE"login", meta: O{ ip: S"10.0.0.1", ok: Btrue, err: U } },
+        O{ at: T-2, what: S"logout", meta: O{ days: A[D"2024-02-29", D"2023-12-31"], times: A[T"23:59:59"] } }
+      ]
+    },
+    O{}
+  ],
+  "quoted: key": A[O{ "": N1, " ": N2 }],
+  keys: O{
+    "a b": N1, S"s: key": N2, SE"q\"u:o": N3, SE"line\nbreak": N4, SE"S\"x": N5,
+    "}": N6, "}x y": O{ "]": A[] }, a,b: N7, "c , d": N8, SE"\tt": N9
+  },
+  empty: A[],
+  nested: O{ a: O{ b: O{ c: A[ O{ d: A[ SE"deepé" ] } ] } } }
+}
+"#;
+
+fn reparse<'b>(text: &str, arena: &'b Bump) -> FdonValue<'b, 'b> {
+    let text = arena.alloc_str(text);
+    parse_fdon_zero_copy_arena(text, arena).unwrap()
+}
+
+// --- parse(serialize(parse(x))) ---
+
+#[test]
+fn mixed_document_round_trips_through_every_writer() {
+    let arena = Bump::new();
+    let minified = minify_fdon(MIXED);
+    let first = parse_fdon_zero_copy_arena(&minified, &arena).unwrap();
+
+    let text = to_fdon_string(&first);
+    let second = reparse(&text, &arena);
+    assert_eq!(second, first);
+    assert!(second.eq_strict(&first));
+    // Lần serialize thứ hai cho đúng chuỗi của lần đầu
+    assert_eq!(to_fdon_string(&second), text);
+
+    assert_eq!(to_fdon_bytes(&first), text.as_bytes());
+    let mut written = Vec::new();
+    to_fdon_writer(&first, &mut written).unwrap();
+    assert_eq!(written, text.as_bytes());
+
+    for opts in [PrettyOptions::default(), PrettyOptions { use_tabs: true, ..PrettyOptions::default() }] {
+        let pretty = prettify_fdon(&first, opts);
+        assert!(parse_fdon_lenient(&pretty, &arena).unwrap().eq_strict(&first), "{}", pretty);
+        assert_eq!(minify_fdon(&pretty), text);
+    }
+}
+
+#[test]
+fn strings_keep_their_content_across_the_round_trip() {
+    let arena = Bump::new();
+    let minified = minify_fdon(MIXED);
+    let value = reparse(&to_fdon_string(&parse_fdon_zero_copy_arena(&minified, &arena).unwrap()), &arena);
+    let user = &value["users"][0];
+    assert_eq!(user["name"].as_str(), Some("An \"Nguyễn\"\n\t\\ \u{1F600}"));
+    assert_eq!(user["raw"].as_str(), Some("plain: text, with [brackets] {braces}"));
+    assert_eq!(user["wake"].as_str(), Some("06:30:00.250"));
+    assert_eq!(user["history"][1]["meta"]["days"][0].as_str(), Some("2024-02-29"));
+    assert_eq!(value["quoted: key"][0][" "].as_i64(), Some(2));
+    let keys: Vec<&str> = value["keys"].as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["a b", "s: key", "q\"u:o", "line\nbreak", "S\"x", "}", "}x y", "a,b", "c , d", "\tt"]);
+    assert!(value["keys"]["}x y"]["]"].as_array().is_some_and(|a| a.is_empty()));
+    assert_eq!(value["nested"]["a"]["b"]["c"][0]["d"][0].as_str(), Some("deep\u{e9}"));
+    assert!(matches!(user["id"], FdonValue::Number(FdonNumber::UInt(u64::MAX))));
+    assert!(matches!(user["seen"], FdonValue::Timestamp(FdonNumber::Integer(1_700_000_000))));
+}
+
+#[test]
+fn generated_deep_mixed_tree_round_trips() {
+    let arena = Bump::new();
+    // Mỗi tầng: object chứa một array gồm đủ các loại scalar và tầng tiếp theo
+    let mut value = FdonValue::Null;
+    // 60 tầng x 2 container = 120, dưới DEFAULT_MAX_DEPTH
+    for depth in (0..60).rev() {
+        let mut items = FdonArrayBuilder::new(&arena);
+        items
+            .push(FdonValue::Number(FdonNumber::Integer(-(depth as i64))))
+            .push(FdonValue::Number(FdonNumber::UInt(u64::MAX - depth)))
+            .push(FdonValue::Number(FdonNumber::Float(depth as f64 / 3.0)))
+            .push(FdonValue::Timestamp(FdonNumber::Integer(depth as i64)))
+            .push(FdonValue::Date("2024-01-01"))
+            .push(FdonValue::Time("12:00:00"))
+            .push(FdonValue::RawString("a\"b"))
+            .push(FdonValue::EscapedString(FdonStr::Borrowed("c\nd}")))
+            .push(FdonValue::Bool(depth % 2 == 0))
+            .push(FdonValue::Null)
+            .push(value);
+        let mut obj = FdonObjectBuilder::new(&arena);
+        obj.insert("items", items.build()).insert("k:ey", FdonValue::RawString("v"));
+        value = obj.build();
+    }
+    let text = to_fdon_string(&value);
+    let back = reparse(&text, &arena);
+    // RawString chứa '"' được ghi thành SE: nội dung bằng nhau, không eq_strict
+    assert_eq!(back, value);
+    assert_eq!(to_fdon_string(&back), text);
+    assert_eq!(to_json_value(&back), to_json_value(&value));
+}
+
+// --- to_json_value ---
+
+#[test]
+fn json_value_of_the_mixed_document() {
+    let arena = Bump::new();
+    let minified = minify_fdon(MIXED);
+    let value = parse_fdon_zero_copy_arena(&minified, &arena).unwrap();
+    let json = to_json_value(&value);
+    assert_eq!(json, serde_json::to_value(&value).unwrap());
+    assert_eq!(json, value.to_json_ordered());
+    // Round-trip qua FDON không đổi JSON
+    assert_eq!(to_json_value(&reparse(&to_fdon_string(&value), &arena)), json);
+
+    let user = &json["users"][0];
+    assert_eq!(user["id"], json!(u64::MAX));
+    assert_eq!(user["neg"], json!(i64::MIN));
+    assert_eq!(user["name"], json!("An \"Nguyễn\"\n\t\\ \u{1F600}"));
+    assert_eq!(user["born"], json!("1990-02-28"));
+    assert_eq!(user["wake"], json!("06:30:00.250"));
+    assert_eq!(user["seen"], json!(1_700_000_000));
+    assert_eq!(user["score"], json!(-0.125));
+    assert_eq!(user["big"], json!(1.5e300));
+    assert_eq!(user["note"], json!(null));
+    assert_eq!(user["tags"], json!(["", "", [[[null, true, 0]]], {}]));
+    assert_eq!(
+        user["history"],
+        json!([
+            {"at": 1.5, "what": "login", "meta": {"ip": "10.0.0.1", "ok": true, "err": null}},
+            {"at": -2, "what": "logout", "meta": {"days": ["2024-02-29", "2023-12-31"], "times": ["23:59:59"]}}
+        ])
+    );
+    assert_eq!(json["users"][1], json!({}));
+    assert_eq!(json["quoted: key"], json!([{"": 1, " ": 2}]));
+    assert_eq!(json["nested"]["a"]["b"]["c"][0]["d"][0], json!("deep\u{e9}"));
+}
@@ -0,0 +1,197 @@
+use bumpalo::Bump;
+use fdon_rs::{
+    build_and_serialize, minify_fdon, parse_fdon_zero_copy_arena, prettify_fdon, to_fdon_bytes, to_fdon_string,
+    FdonArrayBuilder, FdonNumber, FdonObjectBuilder, FdonStr, FdonValue, PrettyOptions,
+};
+
+// --- to_fdon_bytes ---
+
+#[test]
+fn bytes_match_the_string_serializer() {
+    let arena = Bump::new();
+    let docs = [
+        "U",
+        r#"S"héllo wörld ✓""#,
+        r#"O{a:N1,"b:c":SE"x\ny\t\"z\"\\",d:A[N-1.5,N18446744073709551615,T5,T"10:00",D"2024-01-01"],e:O{},f:A[]}"#,
+        r#"A[Btrue,Bfalse,U,SE"é\u{1F600}",S"日本"]"#,
+    ];
+    for doc in docs {
+        let value = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+        let bytes = to_fdon_bytes(&value);
+        assert_eq!(bytes, to_fdon_string(&value).into_bytes(), "{}", doc);
+        // Output đọc lại được
+        assert!(parse_fdon_zero_copy_arena(std::str::from_utf8(&bytes).unwrap(), &arena).is_ok());
+    }
+}
+
+#[test]
+fn bytes_of_a_deep_tree() {
+    let arena = Bump::new();
+    let mut value = FdonValue::Null;
+    for _ in 0..10_000 {
+        let mut builder = FdonArrayBuilder::new(&arena);
+        builder.push(value);
+        value = builder.build();
+    }
+    let bytes = to_fdon_bytes(&value);
+    assert_eq!(bytes.len(), 10_000 * 3 + 1);
+    assert_eq!(bytes, to_fdon_string(&value).into_bytes());
+}
+
+// --- to_fdon_string ---
+
+const FIXTURE: &str = r#"
+O{
+  name: S"fdon",
+  version: N3,
+  ratio: N0.25,
+  big: N18446744073709551615,
+  neg: N-42,
+  ok: Btrue,
+  off: Bfalse,
+  none: U,
+  created: D"2024-01-02",
+  at: T"10:20:30",
+  epoch: T1700000000,
+  note: SE"line1\nline2\t\"quoted\" \\ end",
+  tags: A[S"a", S"b", A[], O{}],
+  nested: O{ empty_obj: O{}, empty_arr: A[], deep: A[O{x: N1.5}]}
+}
+"#;
+
+#[test]
+fn fixture_round_trips_to_identical_minified_bytes() {
+    let arena = Bump::new();
+    let minified = minify_fdon(FIXTURE);
+    let value = parse_fdon_zero_copy_arena(&minified, &arena).unwrap();
+    assert_eq!(to_fdon_string(&value), minified);
+}
+
+#[test]
+fn scalars_keep_their_prefixes() {
+    let arena = Bump::new();
+    let cases = [
+        ("O{}", "O{}"),
+        ("A[]", "A[]"),
+        (r#"D"2024-01-02""#, r#"D"2024-01-02""#),
+        (r#"T"10:20""#, r#"T"10:20""#),
+        ("T17", "T17"),
+        ("T1.5", "T1.5"),
+        ("N5", "N5"),
+        ("N5.0", "N5.0"),
+        ("N1e20", "N1.0e20"),
+        ("Btrue", "Btrue"),
+        ("U", "U"),
+        // SE không escape nào được ghi lại nguyên dạng SE
+        (r#"SE"plain""#, r#"SE"plain""#),
+        (r#"SE"\n\t\r\"\\""#, r#"SE"\n\t\r\"\\""#),
+        // \u được giải mã rồi ghi lại dạng ký tự
+        (r#"SE"\u00e9""#, r#"SE"é""#),
+    ];
+    for (input, expected) in cases {
+        let value = parse_fdon_zero_copy_arena(input, &arena).unwrap();
+        assert_eq!(to_fdon_string(&value), expected, "{}", input);
+    }
+}
+
+#[test]
+fn raw_string_with_a_quote_becomes_se() {
+    let arena = Bump::new();
+    let mut builder = FdonArrayBuilder::new(&arena);
+    builder.push(FdonValue::RawString("say \"hi\""));
+    builder.push(FdonValue::RawString("back\\slash"));
+    let value = builder.build();
+    let text = to_fdon_string(&value);
+    assert_eq!(text, r#"A[SE"say \"hi\"",S"back\slash"]"#);
+    let back = parse_fdon_zero_copy_arena(&text, &arena).unwrap();
+    assert_eq!(back, value);
+}
+
+#[test]
+fn keys_are_quoted_or_escaped_only_when_needed() {
+    let arena = Bump::new();
+    let mut builder = FdonObjectBuilder::new(&arena);
+    for key in ["plain", "a b", "a:b", " pad", "a\"b", "a\"b:c", "\"x", "S\"x", "SE\"y", "SE", "c:\\d"] {
+        builder.insert(key, FdonValue::Null);
+    }
+    let value = builder.build();
+    let text = to_fdon_string(&value);
+    assert_eq!(
+        text,
+        r#"O{plain:U,"a b":U,"a:b":U," pad":U,a"b:U,SE"a\"b:c":U,SE"\"x":U,SE"S\"x":U,SE"SE\"y":U,SE:U,"c:\d":U}"#
+    );
+    let back = parse_fdon_zero_copy_arena(&text, &arena).unwrap();
+    assert!(back.eq_strict(&value));
+    assert_eq!(to_fdon_string(&back), text);
+}
+
+#[test]
+fn keys_with_a_closing_brace_or_whitespace_round_trip() {
+    let arena = Bump::new();
+    let doc = r#"O{"}":N1,"} s":A[],"}a":U,"a b":N2,SE"a\nb":N3,SE"x\ty\"z":N4,"a,b":N5}"#;
+    let value = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+    let text = to_fdon_string(&value);
+    // Xuống dòng trong key được giữ nguyên trong dấu nháy
+    let expected = concat!(r#"O{"}":N1,"} s":A[],"}a":U,"a b":N2,"a"#, "\n", r#"b":N3,SE"x\ty\"z":N4,a,b:N5}"#);
+    assert_eq!(text, expected);
+    let back = parse_fdon_zero_copy_arena(&text, &arena).unwrap();
+    assert!(back.eq_strict(&value), "{}", text);
+    // minify không được nuốt khoảng trắng bên trong key
+    assert_eq!(minify_fdon(&text), text);
+    let keys: Vec<&str> = back.as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["}", "} s", "}a", "a b", "a\nb", "x\ty\"z", "a,b"]);
+}
+
+// --- prettify_fdon ---
+
+#[test]
+fn minified_pretty_output_is_the_compact_output() {
+    let arena = Bump::new();
+    let doc = r#"O{"a b":N1,"}x":A[S"a  b",O{" c d ":SE"x\ny"}],SE"t\tu":O{},plain:A[]}"#;
+    let value = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+    let options = [
+        PrettyOptions::default(),
+        PrettyOptions { use_tabs: true, ..PrettyOptions::default() },
+        PrettyOptions { indent_width: 4, space_after_colon: false, ..PrettyOptions::default() },
+    ];
+    for opts in options {
+        let pretty = prettify_fdon(&value, opts);
+        assert!(pretty.contains("\"a b\""), "{}", pretty);
+        assert_eq!(minify_fdon(&pretty), to_fdon_string(&value), "{}", pretty);
+    }
+}
+
+// --- build_and_serialize ---
+
+#[test]
+fn build_and_serialize_matches_building_then_serializing() {
+    let mut arena = Bump::new();
+    let text = build_and_serialize(&mut arena, |arena| {
+        let mut tags = FdonArrayBuilder::new(arena);
+        tags.push(FdonValue::RawString("a")).push(FdonValue::Number(FdonNumber::Integer(2)));
+        let mut obj = FdonObjectBuilder::new(arena);
+        obj.insert("name", FdonValue::EscapedString(FdonStr::Borrowed("x\ny"))).insert("tags", tags.build());
+        obj.build()
+    });
+    assert_eq!(text, r#"O{name:SE"x\ny",tags:A[S"a",N2]}"#);
+}
+
+#[test]
+fn build_and_serialize_reuses_the_arena() {
+    fn build(arena: &Bump) -> FdonValue<'_, '_> {
+        let mut items = FdonArrayBuilder::with_capacity(arena, 1000);
+        for i in 0..1000 {
+            items.push(FdonValue::Number(FdonNumber::Integer(i)));
+        }
+        items.build()
+    }
+    let mut arena = Bump::new();
+    let first = build_and_serialize(&mut arena, build);
+    let capacity = arena.allocated_bytes();
+    // Arena được reset sau mỗi lần: dung lượng không tăng dù gọi lại nhiều lần
+    for _ in 0..100 {
+        assert_eq!(build_and_serialize(&mut arena, build), first);
+    }
+    assert_eq!(arena.allocated_bytes(), capacity);
+    assert!(first.starts_with("A[N0,N1,") && first.ends_with(",N999]"));
+}
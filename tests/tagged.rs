@@ -0,0 +1,37 @@
+use bumpalo::Bump;
+use fdon_rs::{from_tagged_json, parse_fdon_zero_copy_arena, to_fdon_string, Tagged};
+use serde_json::json;
+
+const DOC: &str = r#"O{s:S"x",se:SE"a\nb",d:D"2023-01-01",t:T"10:00:00",ts:T1700000000,i:N-5,u:N18446744073709551615,f:N5.0,b:Bfalse,n:U,a:A[N1,A[],O{}],o:O{k:SE"v"}}"#;
+
+#[test]
+fn tagged_json_round_trips_back_to_fdon() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(DOC, &arena).unwrap();
+
+    let json = serde_json::to_value(Tagged(&value)).unwrap();
+    assert_eq!(json["value"]["d"], json!({"type": "date", "value": "2023-01-01"}));
+    assert_eq!(json["value"]["se"]["type"], "escaped_string");
+    assert_eq!(json["value"]["f"], json!({"type": "number", "value": 5.0}));
+
+    // Qua chuỗi JSON rồi đọc lại: cùng kiểu, cùng giá trị, cùng văn bản FDON
+    let text = serde_json::to_string(&Tagged(&value)).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+    let back = from_tagged_json(&parsed, &arena).unwrap();
+    assert!(back.eq_strict(&value), "{}", back);
+    // serde_json::Map chỉ giữ thứ tự key khi bật `preserve_order`
+    if cfg!(feature = "preserve_order") {
+        assert_eq!(to_fdon_string(&back), to_fdon_string(&value));
+    }
+}
+
+#[test]
+fn untagged_stays_the_default_and_bad_input_is_none() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(r#"A[D"2023-01-01",S"2023-01-01"]"#, &arena).unwrap();
+    assert_eq!(serde_json::to_string(&value).unwrap(), r#"["2023-01-01","2023-01-01"]"#);
+
+    for bad in [json!(1), json!({"type": "date"}), json!({"type": "nope", "value": 1}), json!({"value": 1})] {
+        assert!(from_tagged_json(&bad, &arena).is_none(), "{}", bad);
+    }
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "test-util")]
+
+use fdon_rs::test_util::{count_allocations, CountingAllocator, TrackingArena};
+use fdon_rs::{is_valid, parse_fdon_zero_copy_arena, FdonValue};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+#[test]
+fn zero_copy_parse_into_presized_arena_allocates_only_the_work_stack() {
+    let arena = TrackingArena::with_capacity(4096);
+    // Lần đầu: ahash khởi tạo seed toàn cục (một lần cho cả process)
+    parse_fdon_zero_copy_arena("O{}", &arena).unwrap();
+
+    let (value, allocs) = count_allocations(|| parse_fdon_zero_copy_arena(r#"S"x""#, &arena));
+    assert!(value.is_ok());
+    assert_eq!(allocs.count, 0);
+
+    // Container: đúng một lần cấp phát heap cho stack của parser, còn lại nằm trong Arena
+    let (value, allocs) = count_allocations(|| parse_fdon_zero_copy_arena(r#"O{a:N1,b:S"x",c:A[Btrue,O{d:U}]}"#, &arena));
+    assert!(value.is_ok());
+    assert_eq!(allocs.count, 1);
+    assert_eq!(arena.stats().chunks, 1);
+    assert!(arena.stats().used_bytes > 0);
+}
+
+#[test]
+fn owned_copy_allocates_exactly_once_per_container_and_string() {
+    let arena = TrackingArena::with_capacity(4096);
+    let value = parse_fdon_zero_copy_arena(r#"A[S"a",S"b",N1]"#, &arena).unwrap();
+    // Vec của mảng + 2 String
+    let (_owned, allocs) = count_allocations(|| value.to_owned_value());
+    assert_eq!(allocs.count, 3);
+}
+
+#[test]
+fn unescaped_string_is_the_only_arena_growth() {
+    let arena = TrackingArena::with_capacity(4096);
+    let before = arena.stats();
+    let value = parse_fdon_zero_copy_arena(r#"SE"plain""#, &arena).unwrap();
+    assert!(matches!(value, FdonValue::EscapedString(ref s) if s.is_borrowed()));
+    assert_eq!(arena.stats().since(&before).used_bytes, 0);
+
+    let value = parse_fdon_zero_copy_arena(r#"SE"a\nb""#, &arena).unwrap();
+    assert!(matches!(value, FdonValue::EscapedString(ref s) if !s.is_borrowed()));
+    assert!(arena.stats().since(&before).used_bytes >= 3);
+}
+
+#[test]
+fn stats_grow_with_chunks_and_reset_frees_them() {
+    let mut arena = TrackingArena::with_capacity(64);
+    let doc = format!("A[{}]", vec!["SE\"x\\ny\""; 200].join(","));
+    parse_fdon_zero_copy_arena(&doc, &arena).unwrap();
+    let grown = arena.stats();
+    assert!(grown.chunks > 1);
+    assert!(grown.reserved_bytes >= grown.used_bytes);
+    arena.reset();
+    assert_eq!(arena.stats().chunks, 1);
+    assert_eq!(arena.stats().used_bytes, 0);
+}
+
+#[test]
+fn is_valid_does_not_allocate() {
+    let docs = [
+        r#"O{a:N1,b:A[S"x",SE"y\n\u{1F600}",D"2024-01-02"],c:O{d:U}}"#,
+        "A[N1,]",
+        "O{a:N1",
+        "",
+    ];
+    for doc in docs {
+        let (ok, allocs) = count_allocations(|| is_valid(doc));
+        assert_eq!(ok, doc.starts_with("O{a:N1,"), "{}", doc);
+        assert_eq!(allocs.count, 0, "{}", doc);
+    }
+    // Tới 128 tầng vẫn không cấp phát
+    let deep = format!("{}{}", "A[".repeat(128), "]".repeat(128));
+    let (ok, allocs) = count_allocations(|| is_valid(&deep));
+    assert!(ok);
+    assert_eq!(allocs.count, 0);
+}
@@ -0,0 +1,50 @@
+use bumpalo::Bump;
+use fdon_rs::{parse_fdon_zero_copy_arena_with_options, FdonError, ParseOptions, Validator};
+
+fn limited(max: usize) -> ParseOptions {
+    ParseOptions { max_token_scan: Some(max), ..ParseOptions::default() }
+}
+
+// Parser và Validator phải cùng chấp nhận / cùng lỗi
+fn check(input: &str, options: ParseOptions) -> Result<(), FdonError> {
+    let arena = Bump::new();
+    let parsed = parse_fdon_zero_copy_arena_with_options(input, &arena, options).map(|_| ());
+    let mut v = Validator::with_options(options);
+    let streamed = input.as_bytes().chunks(3).try_for_each(|b| v.feed(b)).and_then(|_| v.finish());
+    assert_eq!(format!("{:?}", streamed), format!("{:?}", parsed), "{}", input);
+    parsed
+}
+
+// --- max_token_scan ---
+
+#[test]
+fn tokens_at_the_limit_pass_and_one_byte_more_fails() {
+    // (trước token, sau token, vị trí bắt đầu token)
+    let shapes = [("O{", ":U}", 2), ("A[S\"", "\"]", 4), ("A[SE\"", "\"]", 5), ("A[D\"", "\"]", 4), ("A[N", "]", 3)];
+    for (before, after, start) in shapes {
+        let token = |n: usize| if before.ends_with('N') { "1".repeat(n) } else { "x".repeat(n) };
+        let ok = format!("{}{}{}", before, token(8), after);
+        let over = format!("{}{}{}", before, token(9), after);
+        assert!(check(&ok, limited(8)).is_ok(), "{}", ok);
+        let err = check(&over, limited(8)).unwrap_err();
+        assert!(matches!(err, FdonError::TokenTooLong { pos } if pos == start), "{}: {:?}", over, err);
+        assert!(check(&over, ParseOptions::default()).is_ok());
+    }
+}
+
+#[test]
+fn escapes_count_as_written() {
+    // `\n` là 2 byte trong input: 4 escape = 8 byte, vừa đủ
+    assert!(check(r#"SE"\n\n\n\n""#, limited(8)).is_ok());
+    assert!(matches!(check(r#"SE"\n\n\n\nx""#, limited(8)), Err(FdonError::TokenTooLong { pos: 3 })));
+}
+
+#[test]
+fn unterminated_huge_string_fails_at_the_limit() {
+    let input = format!("A[S\"{}", "y".repeat(1 << 20));
+    let err = check(&input, limited(64)).unwrap_err();
+    assert!(matches!(err, FdonError::TokenTooLong { pos: 4 }), "{:?}", err);
+    // Không giới hạn: vẫn là lỗi, nhưng là lỗi thiếu dấu đóng chứ không phải TokenTooLong
+    let err = check(&input, ParseOptions::default()).unwrap_err();
+    assert!(!matches!(err, FdonError::TokenTooLong { .. }), "{:?}", err);
+}
@@ -0,0 +1,68 @@
+use bumpalo::Bump;
+use fdon_rs::{parse_fdon_zero_copy_arena_with_options, FdonError, FdonParser, ParseOptions, Validator};
+
+// Parser, skip_value và Validator (nạp từng byte) phải cho cùng kết quả
+fn check_all(input: &str, options: ParseOptions) -> Result<String, FdonError> {
+    let arena = Bump::new();
+    let parsed = parse_fdon_zero_copy_arena_with_options(input, &arena, options)
+        .map(|v| v.as_str().unwrap().to_string());
+
+    let mut parser = FdonParser::with_options(input, &arena, options);
+    let skipped = parser.skip_value();
+    assert_eq!(format!("{:?}", skipped.err()), format!("{:?}", parsed.as_ref().err()), "skip_value: {}", input);
+
+    let mut v = Validator::with_options(options);
+    let streamed = input.as_bytes().chunks(1).try_for_each(|b| v.feed(b)).and_then(|_| v.finish());
+    assert_eq!(format!("{:?}", streamed.err()), format!("{:?}", parsed.as_ref().err()), "Validator: {}", input);
+    parsed
+}
+
+// --- \uXXXX và \u{...} ---
+
+#[test]
+fn unicode_escapes_decode_to_utf8() {
+    let options = ParseOptions::default();
+    for (input, expected) in [
+        (r#"SE"\u0041""#, "A"),
+        (r#"SE"caf\u00e9!""#, "café!"),
+        (r#"SE"\u{1F600}""#, "😀"),
+        (r#"SE"\u{41}\u{e9}""#, "Aé"),
+        (r#"SE"\uD83D\uDE00""#, "😀"),
+        (r#"SE"a\"\u20AC\\""#, "a\"€\\"),
+    ] {
+        assert_eq!(check_all(input, options).unwrap(), expected, "{}", input);
+    }
+}
+
+#[test]
+fn malformed_unicode_escapes_point_at_the_backslash() {
+    let options = ParseOptions::default();
+    for input in [
+        r#"SE"ab\u00G1""#,
+        r#"SE"ab\u12""#,
+        r#"SE"ab\u{}""#,
+        r#"SE"ab\u{1234567}""#,
+        r#"SE"ab\u{110000}""#,
+        r#"SE"ab\uD83D""#,
+        r#"SE"ab\uDE00""#,
+        r#"SE"ab\uD83DA""#,
+    ] {
+        let err = check_all(input, options).unwrap_err();
+        assert!(matches!(err, FdonError::InvalidUnicodeEscape { pos: 5 }), "{}: {:?}", input, err);
+    }
+}
+
+// --- Delimiter khác '"' ---
+
+#[test]
+fn escaped_delimiter_next_to_unicode_escapes() {
+    let options = ParseOptions { string_delimiter: b'`', ..ParseOptions::default() };
+    assert_eq!(check_all(r"SE`a\`b\``", options).unwrap(), "a`b`");
+    assert_eq!(check_all(r"SE`\u{60}\``", options).unwrap(), "``");
+
+    // strict_escapes: escape delimiter và \u vẫn hợp lệ, escape lạ thì không
+    let strict = ParseOptions { strict_escapes: true, ..options };
+    assert_eq!(check_all(r"SE`\`é`", strict).unwrap(), "`é");
+    let err = check_all(r"SE`\`\q`", strict).unwrap_err();
+    assert!(matches!(err, FdonError::InvalidEscape { ch: b'q', pos: 5 }), "{:?}", err);
+}
@@ -0,0 +1,282 @@
+use std::cmp::Ordering;
+
+use bumpalo::Bump;
+use fdon_rs::{
+    parse_fdon_zero_copy_arena, prettify_fdon, to_fdon_string, FdonNumber, FdonStr, FdonValue, PrettyOptions,
+};
+
+fn float(f: f64) -> FdonValue<'static, 'static> {
+    FdonValue::Number(FdonNumber::Float(f))
+}
+
+// --- total_cmp / sort_arrays_recursive ---
+
+#[test]
+fn total_cmp_puts_negative_nan_first_and_nan_last() {
+    let neg_nan = float(-f64::NAN);
+    let nan = float(f64::NAN);
+    for n in [float(f64::NEG_INFINITY), FdonValue::Number(FdonNumber::Integer(i64::MIN)), float(0.0), float(f64::INFINITY)] {
+        assert_eq!(neg_nan.total_cmp(&n), Ordering::Less);
+        assert_eq!(nan.total_cmp(&n), Ordering::Greater);
+    }
+    assert_eq!(FdonValue::Number(FdonNumber::Integer(1)).total_cmp(&float(1.0)), Ordering::Equal);
+}
+
+#[test]
+fn sort_arrays_orders_kinds_then_values() {
+    let arena = Bump::new();
+    let mut value = parse_fdon_zero_copy_arena(r#"A[O{},A[],T"t",D"d",S"b",SE"a",T5,N2.5,N-1,Btrue,Bfalse,U]"#, &arena).unwrap();
+    value.sort_arrays_recursive();
+    let expected = parse_fdon_zero_copy_arena(r#"A[U,Bfalse,Btrue,N-1,N2.5,T5,SE"a",S"b",D"d",T"t",A[],O{}]"#, &arena).unwrap();
+    assert!(value.eq_strict(&expected), "{}", value);
+}
+
+// --- to_string_map ---
+
+#[test]
+fn string_map_of_a_flat_config() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(
+        r#"O{HOST:S"localhost",PORT:N8080,RATIO:N0.5,DEBUG:Btrue,MOTD:SE"hi\nthere",DAY:D"2024-01-02",AT:T"10:00",TS:T17}"#,
+        &arena,
+    )
+    .unwrap();
+    let map = value.to_string_map().unwrap();
+    let expected = [
+        ("HOST", "localhost"),
+        ("PORT", "8080"),
+        ("RATIO", "0.5"),
+        ("DEBUG", "true"),
+        ("MOTD", "hi\nthere"),
+        ("DAY", "2024-01-02"),
+        ("AT", "10:00"),
+        ("TS", "17"),
+    ];
+    assert_eq!(map.len(), expected.len());
+    for (k, v) in expected {
+        assert_eq!(map.get(k).map(String::as_str), Some(v), "{}", k);
+    }
+}
+
+#[test]
+fn string_map_rejects_nested_and_null_values() {
+    let arena = Bump::new();
+    for doc in ["O{a:S\"x\",b:U}", "O{a:A[]}", "O{a:O{}}", "A[S\"x\"]", "S\"x\""] {
+        let value = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+        assert!(value.to_string_map().is_none(), "{}", doc);
+    }
+    let empty = parse_fdon_zero_copy_arena("O{}", &arena).unwrap();
+    assert!(empty.to_string_map().unwrap().is_empty());
+}
+
+// --- approx_eq ---
+
+#[test]
+fn approx_eq_tolerates_float_error_only() {
+    let arena = Bump::new();
+    let parse = |s: &'static str| parse_fdon_zero_copy_arena(s, &arena).unwrap();
+    let a = parse(r#"O{x:N0.30000000000000004,y:A[N1,N2.0],s:S"t",t:T5}"#);
+    let b = parse(r#"O{t:T5.0000001,y:A[N1.0000001,N2],s:SE"t",x:N0.3}"#);
+    assert!(a.approx_eq(&b, 1e-6));
+    assert!(!a.approx_eq(&b, 1e-9));
+    assert_ne!(a, b);
+
+    // Số nguyên với nhau: so chính xác, epsilon không áp dụng
+    assert!(!parse("N1").approx_eq(&parse("N2"), 5.0));
+    assert!(parse("N1").approx_eq(&parse("N2.5"), 1.5));
+    // Khác loại, khác độ dài, khác key: không bằng
+    assert!(!parse("N1").approx_eq(&parse("T1"), 1.0));
+    assert!(!parse("A[N1]").approx_eq(&parse("A[N1,N1]"), 1.0));
+    assert!(!parse("O{a:N1}").approx_eq(&parse("O{b:N1}"), 1.0));
+}
+
+#[test]
+fn approx_eq_nan_and_infinity() {
+    assert!(float(f64::NAN).approx_eq(&float(f64::NAN), 0.0));
+    assert!(!float(f64::NAN).approx_eq(&float(1.0), f64::INFINITY));
+    assert!(float(f64::INFINITY).approx_eq(&float(f64::INFINITY), 0.0));
+    assert!(!float(f64::INFINITY).approx_eq(&float(f64::NEG_INFINITY), f64::MAX));
+    assert!(!float(f64::INFINITY).approx_eq(&float(f64::MAX), 1.0));
+}
+
+// --- map_numbers ---
+
+fn scale(n: &FdonNumber) -> FdonNumber {
+    match *n {
+        FdonNumber::Integer(i) => FdonNumber::Integer(i * 10),
+        FdonNumber::UInt(u) => FdonNumber::UInt(u.saturating_mul(10)),
+        FdonNumber::Float(f) => FdonNumber::Float(f * 10.0),
+    }
+}
+
+#[test]
+fn map_numbers_reaches_every_nested_number() {
+    let arena = Bump::new();
+    let doc = r#"O{a:N1,b:A[N2.5,O{c:N-3,d:S"N4"}],e:T5,f:T"10:00",g:A[A[N10000000000000000000]]}"#;
+    let mut value = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+    value.map_numbers(scale);
+    // Timestamp và chuỗi giữ nguyên
+    assert_eq!(
+        to_fdon_string(&value),
+        r#"O{a:N10,b:A[N25.0,O{c:N-30,d:S"N4"}],e:T5,f:T"10:00",g:A[A[N18446744073709551615]]}"#
+    );
+}
+
+#[test]
+fn map_numbers_and_timestamps_includes_numeric_timestamps() {
+    let arena = Bump::new();
+    let mut value = parse_fdon_zero_copy_arena(r#"A[N1,T2,T"2024",N0.25]"#, &arena).unwrap();
+    value.map_numbers_and_timestamps(|n| match *n {
+        FdonNumber::Float(f) => FdonNumber::Float(f.round()),
+        other => scale(&other),
+    });
+    assert_eq!(to_fdon_string(&value), r#"A[N10,T20,T"2024",N0.0]"#);
+
+    // Giá trị gốc là số
+    let mut root = parse_fdon_zero_copy_arena("N7", &arena).unwrap();
+    root.map_numbers(|_| FdonNumber::Integer(0));
+    assert_eq!(root.as_i64(), Some(0));
+}
+
+// --- Display ---
+
+#[test]
+fn display_is_the_fdon_text() {
+    let arena = Bump::new();
+    let doc = r#"O{f:N1.50,g:N2e3,i:N-7,s:S"a b",e:SE"x\ny",q:S"x\y",l:A[Btrue,U,D"2024-01-02",T"10:00",T5]}"#;
+    let value = parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+    let text = r#"O{f:N1.5,g:N2000.0,i:N-7,s:S"a b",e:SE"x\ny",q:S"x\y",l:A[Btrue,U,D"2024-01-02",T"10:00",T5]}"#;
+    assert_eq!(format!("{}", value), text);
+    assert_eq!(value.to_string(), to_fdon_string(&value));
+    assert_eq!(format!("{:#}", value), prettify_fdon(&value, PrettyOptions::default()));
+    assert_eq!(format!("{:#}", parse_fdon_zero_copy_arena("A[N1]", &arena).unwrap()), "A[\n  N1\n]");
+
+    // Raw string chứa dấu nháy thành SE; float không hữu hạn thành U
+    assert_eq!(FdonValue::RawString("say \"hi\"").to_string(), r#"SE"say \"hi\"""#);
+    assert_eq!(float(f64::NAN).to_string(), "U");
+}
+
+#[test]
+fn numbers_display_without_their_tag() {
+    assert_eq!(FdonNumber::Integer(-3).to_string(), "-3");
+    assert_eq!(FdonNumber::UInt(u64::MAX).to_string(), "18446744073709551615");
+    assert_eq!(FdonNumber::Float(5.0).to_string(), "5.0");
+    assert_eq!(FdonNumber::Float(0.1).to_string(), "0.1");
+    assert_eq!(FdonNumber::Float(1.5e300).to_string(), "1.5e300");
+    assert_eq!(FdonNumber::Float(f64::NAN).to_string(), "NaN");
+    assert_eq!(FdonNumber::Float(f64::NEG_INFINITY).to_string(), "-inf");
+}
+
+// --- clone_into / move_to_arena ---
+
+const NESTED: &str = r#"O{name:S"fdon",SE"k\"e:y":A[SE"x\ny",SE"plain",O{n:N1.5,t:T5,d:D"2024-01-02"}],e:O{},u:U}"#;
+
+#[test]
+fn clone_into_outlives_the_source_arena() {
+    let target = Bump::new();
+    let copy = {
+        let source = Bump::new();
+        let value = parse_fdon_zero_copy_arena(NESTED, &source).unwrap();
+        let copy = value.clone_into(&target);
+        assert!(copy.eq_strict(&value));
+        // source bị drop khi ra khỏi block; input NESTED vẫn còn
+        copy
+    };
+    let reparsed = parse_fdon_zero_copy_arena(NESTED, &target).unwrap();
+    assert!(copy.eq_strict(&reparsed));
+    assert_eq!(to_fdon_string(&copy), NESTED);
+    assert_eq!(copy["k\"e:y"][0].as_str(), Some("x\ny"));
+
+    // Chuỗi không escape vẫn trỏ vào input, không bị copy
+    let input = NESTED.as_bytes().as_ptr_range();
+    assert!(input.contains(&copy["name"].as_str().unwrap().as_ptr()));
+    assert!(matches!(&copy["k\"e:y"][1], FdonValue::EscapedString(s) if s.is_borrowed()));
+    assert!(matches!(&copy["k\"e:y"][0], FdonValue::EscapedString(FdonStr::Owned(_))));
+}
+
+#[test]
+fn clone_into_the_same_arena_is_independent() {
+    let arena = Bump::new();
+    let value = parse_fdon_zero_copy_arena(NESTED, &arena).unwrap();
+    let mut copy = value.clone_into(&arena);
+    copy.map_numbers(|_| FdonNumber::Integer(0));
+    assert_eq!(copy["k\"e:y"][2]["n"].as_i64(), Some(0));
+    // Bản gốc không đổi
+    assert_eq!(value["k\"e:y"][2]["n"].as_f64(), Some(1.5));
+    assert_eq!(to_fdon_string(&value), NESTED);
+}
+
+#[test]
+fn move_to_arena_survives_resetting_the_source() {
+    let mut source = Bump::new();
+    let target = Bump::new();
+    let moved = parse_fdon_zero_copy_arena(NESTED, &source).unwrap().move_to_arena(&target);
+    source.reset();
+    // Arena cũ được dùng lại cho cây khác, không ảnh hưởng cây đã chuyển
+    let other = parse_fdon_zero_copy_arena(r#"O{SE"\u{1F600}":SE"\t"}"#, &source).unwrap();
+    assert_eq!(other["\u{1F600}"].as_str(), Some("\t"));
+    assert!(moved.eq_strict(&parse_fdon_zero_copy_arena(NESTED, &target).unwrap()));
+    assert_eq!(to_fdon_string(&moved), NESTED);
+}
+
+// --- PartialEq / eq_strict ---
+
+#[test]
+fn objects_compare_regardless_of_entry_order() {
+    let arena = Bump::new();
+    let parse = |doc| parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+    let a = parse(r#"O{a:N1,b:O{x:S"1",y:A[U,Btrue]},c:A[]}"#);
+    let b = parse(r#"O{c:A[],b:O{y:A[U,Btrue],x:S"1"},a:N1}"#);
+    assert_eq!(a, b);
+    assert!(a.eq_strict(&b));
+
+    // Thiếu key, thừa key hoặc value khác thì không bằng
+    assert_ne!(a, parse(r#"O{a:N1,b:O{x:S"1",y:A[U,Btrue]}}"#));
+    assert_ne!(a, parse(r#"O{a:N1,b:O{x:S"1",y:A[U,Btrue]},c:A[],d:U}"#));
+    assert_ne!(a, parse(r#"O{a:N1,b:O{x:S"1",y:A[U,Btrue]},d:A[]}"#));
+    // Array so theo vị trí
+    assert_ne!(a, parse(r#"O{a:N1,b:O{x:S"1",y:A[Btrue,U]},c:A[]}"#));
+    assert_ne!(parse("A[N1,N2]"), parse("A[N1,N2,N3]"));
+}
+
+#[test]
+fn numbers_compare_by_value_unless_strict() {
+    let (int, uint) = (FdonNumber::Integer(1), FdonNumber::UInt(1));
+    assert_eq!(int, FdonNumber::Float(1.0));
+    assert_eq!(int, uint);
+    assert_eq!(uint, FdonNumber::Float(1.0));
+    assert_eq!(FdonNumber::Float(0.0), FdonNumber::Float(-0.0));
+    assert_ne!(int, FdonNumber::Float(1.5));
+    // So sánh chính xác, không đi qua f64: 2^53 + 1 không bằng 2^53 dạng float
+    assert_ne!(FdonNumber::Integer((1 << 53) + 1), FdonNumber::Float((1u64 << 53) as f64));
+    assert!(!int.eq_strict(&FdonNumber::Float(1.0)));
+    assert!(!int.eq_strict(&uint));
+    assert!(int.eq_strict(&FdonNumber::Integer(1)));
+
+    // NaN bằng chính nó với ==, không với eq_strict
+    assert_eq!(float(f64::NAN), float(f64::NAN));
+    assert!(!float(f64::NAN).eq_strict(&float(f64::NAN)));
+
+    let arena = Bump::new();
+    let parse = |doc| parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+    assert_eq!(parse("A[N1,T2]"), parse("A[N1.0,T2.0]"));
+    assert!(!parse("A[N1,T2]").eq_strict(&parse("A[N1.0,T2.0]")));
+}
+
+#[test]
+fn kinds_and_string_forms() {
+    let arena = Bump::new();
+    let parse = |doc| parse_fdon_zero_copy_arena(doc, &arena).unwrap();
+    // S và SE cùng nội dung: bằng nhau, nhưng không eq_strict
+    assert_eq!(parse(r#"S"a""#), parse(r#"SE"a""#));
+    assert!(!parse(r#"S"a""#).eq_strict(&parse(r#"SE"a""#)));
+    assert_eq!(FdonValue::EscapedString(FdonStr::Borrowed("a\nb")), parse(r#"SE"a\nb""#));
+    assert!(parse(r#"SE"a\nb""#).eq_strict(&parse(r#"SE"a\nb""#)));
+
+    // Loại khác nhau thì không bằng nhau, dù cùng nội dung
+    assert_ne!(parse("N5"), parse("T5"));
+    assert_ne!(parse(r#"S"2024-01-01""#), parse(r#"D"2024-01-01""#));
+    assert_ne!(parse(r#"T"10:00""#), parse(r#"S"10:00""#));
+    assert_ne!(parse("U"), parse("Bfalse"));
+    assert_ne!(parse("O{}"), parse("A[]"));
+}